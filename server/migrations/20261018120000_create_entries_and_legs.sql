@@ -0,0 +1,28 @@
+CREATE TABLE IF NOT EXISTS entries (
+  id uuid PRIMARY KEY, -- uuid
+  title text NOT NULL,
+  category text,
+  posting_date TIMESTAMPTZ NOT NULL -- timestamp of the transaction
+);
+
+CREATE TABLE IF NOT EXISTS legs (
+  id uuid PRIMARY KEY, -- uuid
+  entry_id uuid NOT NULL REFERENCES entries(id) ON DELETE CASCADE,
+  position integer NOT NULL, -- order of the leg inside its entry
+  account_id uuid NOT NULL REFERENCES accounts(id),
+  amount numeric NOT NULL -- added to the account's balance
+);
+
+CREATE INDEX IF NOT EXISTS legs_entry_id_idx ON legs (entry_id);
+CREATE INDEX IF NOT EXISTS legs_account_id_idx ON legs (account_id);
+
+-- Every two-account posting becomes an entry with a credit and a debit leg
+INSERT INTO entries (id, title, category, posting_date)
+SELECT id, title, category, posting_date FROM postings;
+
+INSERT INTO legs (id, entry_id, position, account_id, amount)
+SELECT gen_random_uuid(), id, 0, source_account_id, -amount FROM postings
+UNION ALL
+SELECT gen_random_uuid(), id, 1, destination_account_id, amount FROM postings;
+
+DROP TABLE postings;
@@ -0,0 +1,5 @@
+ALTER TABLE accounts
+  ADD COLUMN IF NOT EXISTS account_type text NOT NULL DEFAULT 'asset'
+  CHECK (account_type IN ('asset', 'liability', 'income', 'expense', 'equity'));
+
+CREATE INDEX IF NOT EXISTS accounts_account_type_idx ON accounts (account_type);
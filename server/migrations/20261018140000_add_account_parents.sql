@@ -0,0 +1,20 @@
+ALTER TABLE accounts
+  ADD COLUMN IF NOT EXISTS parent_id uuid REFERENCES accounts(id);
+
+CREATE INDEX IF NOT EXISTS accounts_parent_id_idx ON accounts (parent_id);
+
+-- Create the missing intermediate accounts of existing colon-separated names
+WITH RECURSIVE prefixes (name, account_type) AS (
+  SELECT regexp_replace(name, ':[^:]*$', ''), account_type FROM accounts WHERE name LIKE '%:%'
+  UNION
+  SELECT regexp_replace(name, ':[^:]*$', ''), account_type FROM prefixes WHERE name LIKE '%:%'
+)
+INSERT INTO accounts (id, name, account_type)
+SELECT gen_random_uuid(), name, min(account_type) FROM prefixes GROUP BY name
+ON CONFLICT (name) DO NOTHING;
+
+UPDATE accounts AS child
+SET parent_id = parent.id
+FROM accounts AS parent
+WHERE child.name LIKE '%:%'
+  AND parent.name = regexp_replace(child.name, ':[^:]*$', '');
@@ -0,0 +1,41 @@
+CREATE TABLE categories (
+  id uuid PRIMARY KEY,
+  name text NOT NULL UNIQUE,
+  parent_id uuid REFERENCES categories(id)
+);
+
+CREATE INDEX categories_parent_id_idx ON categories (parent_id);
+
+-- Normalize existing categories the way category names are validated: lowercase, colon-separated
+-- segments without surrounding whitespace, and no empty segments
+UPDATE entries
+SET category = NULLIF(
+  btrim(
+    regexp_replace(lower(regexp_replace(trim(category), '\s*:\s*', ':', 'g')), ':+', ':', 'g'),
+    ':'
+  ),
+  ''
+)
+WHERE category IS NOT NULL;
+
+-- Backfill the categories in use, along with their ancestors
+WITH RECURSIVE names (name) AS (
+  SELECT DISTINCT category FROM entries WHERE category IS NOT NULL
+  UNION
+  SELECT regexp_replace(name, ':[^:]*$', '') FROM names WHERE name LIKE '%:%'
+)
+INSERT INTO categories (id, name)
+SELECT gen_random_uuid(), name FROM names;
+
+UPDATE categories AS child
+SET parent_id = parent.id
+FROM categories AS parent
+WHERE child.name LIKE '%:%'
+  AND parent.name = regexp_replace(child.name, ':[^:]*$', '');
+
+-- Renaming a category renames it in its transactions too
+ALTER TABLE entries
+  ADD CONSTRAINT entries_category_fkey
+  FOREIGN KEY (category) REFERENCES categories(name) ON UPDATE CASCADE;
+
+CREATE INDEX entries_category_idx ON entries (category);
@@ -0,0 +1,15 @@
+CREATE TABLE budgets (
+  id uuid PRIMARY KEY,
+  -- The first day of the budgeted month
+  month date NOT NULL CHECK (extract(day FROM month) = 1),
+  category_id uuid REFERENCES categories(id) ON DELETE CASCADE,
+  account_id uuid REFERENCES accounts(id) ON DELETE CASCADE,
+  amount numeric NOT NULL CHECK (amount >= 0),
+  rollover boolean NOT NULL DEFAULT false,
+  CHECK ((category_id IS NULL) <> (account_id IS NULL))
+);
+
+CREATE UNIQUE INDEX budgets_category_month_idx ON budgets (category_id, month)
+  WHERE category_id IS NOT NULL;
+CREATE UNIQUE INDEX budgets_account_month_idx ON budgets (account_id, month)
+  WHERE account_id IS NOT NULL;
@@ -0,0 +1,6 @@
+-- What identifies an imported transaction, so that importing the same statement twice does not
+-- duplicate it: either the id it has at the institution it was imported from, like the FITID of
+-- an OFX statement, or a hash of the imported row
+ALTER TABLE entries ADD COLUMN import_fingerprint text;
+
+CREATE UNIQUE INDEX entries_import_fingerprint_idx ON entries (import_fingerprint);
@@ -0,0 +1,15 @@
+-- Every import of a file, so that the transactions it created can be rolled back together
+CREATE TABLE import_batches (
+  id uuid PRIMARY KEY,
+  source text NOT NULL, -- name of the imported file
+  format text NOT NULL, -- csv or ofx
+  profile text, -- import profile CSV files were read with
+  account_id uuid NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+  row_count integer NOT NULL, -- rows in the file, whether they were imported or not
+  created_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+ALTER TABLE entries
+  ADD COLUMN import_batch_id uuid REFERENCES import_batches(id) ON DELETE SET NULL;
+
+CREATE INDEX entries_import_batch_id_idx ON entries (import_batch_id);
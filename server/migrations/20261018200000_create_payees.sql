@@ -0,0 +1,10 @@
+-- Who a transaction was paid to or received from, like a merchant, shared by all the
+-- transactions whose titles normalize to the same name
+CREATE TABLE payees (
+  id uuid PRIMARY KEY,
+  name text NOT NULL UNIQUE
+);
+
+ALTER TABLE entries ADD COLUMN payee_id uuid REFERENCES payees(id) ON DELETE SET NULL;
+
+CREATE INDEX entries_payee_id_idx ON entries (payee_id);
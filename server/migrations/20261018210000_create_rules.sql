@@ -0,0 +1,21 @@
+-- Free-form labels of a transaction, like "vacation" or "reimbursable"
+ALTER TABLE entries ADD COLUMN tags text[] NOT NULL DEFAULT '{}';
+
+-- Categorization rules, tried in order of priority: the first rule whose conditions all hold
+-- for a transaction sets its category, destination account and tags
+CREATE TABLE rules (
+  id uuid PRIMARY KEY,
+  name text NOT NULL,
+  priority integer NOT NULL DEFAULT 0, -- lower priorities are tried first
+  -- Conditions, ignored when NULL
+  title_pattern text, -- regular expression the title must match
+  min_amount numeric, -- the amount moved must be at least this much
+  max_amount numeric, -- the amount moved must be at most this much
+  -- Accounts and payees used by a rule cannot be deleted, rather than silently losing the rule
+  source_account_id uuid REFERENCES accounts(id) ON DELETE RESTRICT,
+  payee_id uuid REFERENCES payees(id) ON DELETE RESTRICT,
+  -- Actions
+  category text REFERENCES categories(name) ON UPDATE CASCADE ON DELETE RESTRICT,
+  destination_account_id uuid REFERENCES accounts(id) ON DELETE RESTRICT,
+  tags text[] NOT NULL DEFAULT '{}'
+);
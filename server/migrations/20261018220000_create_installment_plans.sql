@@ -0,0 +1,17 @@
+-- Purchases paid in monthly installments, like the "parcela 3/10" lines of credit card
+-- statements. Each installment is a transaction of its own, linked to the plan.
+CREATE TABLE installment_plans (
+  id uuid PRIMARY KEY,
+  title text NOT NULL,
+  account_id uuid NOT NULL REFERENCES accounts(id) ON DELETE CASCADE, -- the account paying, like a credit card
+  total_amount numeric NOT NULL, -- the price of the whole purchase
+  installment_count integer NOT NULL CHECK (installment_count > 0),
+  first_posting_date timestamptz NOT NULL
+);
+
+CREATE INDEX installment_plans_account_id_idx ON installment_plans (account_id);
+
+ALTER TABLE entries ADD COLUMN installment_plan_id uuid REFERENCES installment_plans(id) ON DELETE SET NULL;
+ALTER TABLE entries ADD COLUMN installment_number integer;
+
+CREATE UNIQUE INDEX entries_installment_idx ON entries (installment_plan_id, installment_number);
@@ -0,0 +1,7 @@
+-- The billing cycle and limit of liability accounts that are credit cards
+CREATE TABLE credit_cards (
+  account_id uuid PRIMARY KEY REFERENCES accounts(id) ON DELETE CASCADE,
+  closing_day integer NOT NULL CHECK (closing_day BETWEEN 1 AND 31), -- statements close at the end of this day
+  due_day integer NOT NULL CHECK (due_day BETWEEN 1 AND 31),
+  credit_limit numeric NOT NULL CHECK (credit_limit >= 0)
+);
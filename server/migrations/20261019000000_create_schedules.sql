@@ -0,0 +1,28 @@
+-- Transactions that repeat, posted by the server as each occurrence comes due
+CREATE TABLE schedules (
+  id uuid PRIMARY KEY,
+  title text NOT NULL,
+  amount numeric NOT NULL CHECK (amount > 0),
+  -- Accounts used by a schedule cannot be deleted, rather than silently losing the schedule
+  source_account_id uuid NOT NULL REFERENCES accounts(id) ON DELETE RESTRICT,
+  destination_account_id uuid NOT NULL REFERENCES accounts(id) ON DELETE RESTRICT,
+  category text REFERENCES categories(name) ON UPDATE CASCADE ON DELETE RESTRICT,
+  frequency text NOT NULL CHECK (frequency IN ('weekly', 'monthly', 'last_business_day', 'yearly')),
+  every integer NOT NULL CHECK (every > 0), -- repeats every this many weeks, months or years
+  day integer CHECK (day BETWEEN 1 AND 31), -- the day of monthly schedules
+  start_date date NOT NULL,
+  end_date date
+);
+
+-- Occurrences that must not be posted
+CREATE TABLE skipped_occurrences (
+  schedule_id uuid NOT NULL REFERENCES schedules(id) ON DELETE CASCADE,
+  occurrence_date date NOT NULL,
+  PRIMARY KEY (schedule_id, occurrence_date)
+);
+
+-- The occurrence each posted transaction is for, so that none is posted twice
+ALTER TABLE entries
+  ADD COLUMN schedule_id uuid REFERENCES schedules(id) ON DELETE SET NULL,
+  ADD COLUMN occurrence_date date;
+CREATE UNIQUE INDEX entries_schedule_occurrence_idx ON entries (schedule_id, occurrence_date);
@@ -0,0 +1,2 @@
+-- The asset account each statement is paid from, which cash flow forecasts take the payments out of
+ALTER TABLE credit_cards ADD COLUMN payment_account_id uuid REFERENCES accounts(id) ON DELETE SET NULL;
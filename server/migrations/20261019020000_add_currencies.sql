@@ -0,0 +1,17 @@
+-- The ISO 4217 currency each account counts its balance in, and each leg its amount in.
+-- Everything recorded so far is in reais.
+ALTER TABLE accounts
+  ADD COLUMN currency text NOT NULL DEFAULT 'BRL' CHECK (currency ~ '^[A-Z]{3}$');
+ALTER TABLE legs
+  ADD COLUMN currency text NOT NULL DEFAULT 'BRL' CHECK (currency ~ '^[A-Z]{3}$');
+
+-- The rate legs in two currencies were converted at: one exchange_rate_base is worth
+-- exchange_rate exchange_rate_quote
+ALTER TABLE entries
+  ADD COLUMN exchange_rate_base text,
+  ADD COLUMN exchange_rate_quote text,
+  ADD COLUMN exchange_rate numeric CHECK (exchange_rate > 0),
+  ADD CONSTRAINT entries_exchange_rate_complete CHECK (
+    (exchange_rate IS NULL) = (exchange_rate_base IS NULL)
+    AND (exchange_rate IS NULL) = (exchange_rate_quote IS NULL)
+  );
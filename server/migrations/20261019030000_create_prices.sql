@@ -0,0 +1,18 @@
+-- Accounts can hold commodities other than currencies, like stocks, named by their symbol
+ALTER TABLE accounts
+  DROP CONSTRAINT accounts_currency_check,
+  ADD CONSTRAINT accounts_currency_check CHECK (currency ~ '^[A-Z][A-Z0-9]{1,9}$');
+ALTER TABLE legs
+  DROP CONSTRAINT legs_currency_check,
+  ADD CONSTRAINT legs_currency_check CHECK (currency ~ '^[A-Z][A-Z0-9]{1,9}$');
+
+-- Historical prices: one unit of commodity was worth rate currency on date
+CREATE TABLE prices (
+  id uuid PRIMARY KEY,
+  commodity text NOT NULL CHECK (commodity ~ '^[A-Z][A-Z0-9]{1,9}$'),
+  currency text NOT NULL CHECK (currency ~ '^[A-Z][A-Z0-9]{1,9}$'),
+  date date NOT NULL,
+  rate numeric NOT NULL CHECK (rate > 0),
+  CHECK (commodity <> currency),
+  UNIQUE (commodity, currency, date)
+);
@@ -9,8 +9,9 @@ async fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
     dotenvy::dotenv().ok();
 
+    // Logs go to stderr, so that reports printed to stdout can be piped
     let subscriber =
-        telemetry::get_subscriber("cli".to_string(), "debug".to_string(), std::io::stdout);
+        telemetry::get_subscriber("cli".to_string(), "debug".to_string(), std::io::stderr);
     telemetry::init_subscriber(subscriber);
 
     let config = get_configuration(Some(PathBuf::from(".")))?;
@@ -20,5 +21,5 @@ async fn main() -> color_eyre::Result<()> {
 
     let cli = Cli::parse();
 
-    cli.run(service).await
+    cli.run(service, &config).await
 }
@@ -1,44 +1,47 @@
 use std::path::PathBuf;
 
-use chrono::NaiveDate;
-use clap::Parser;
+use chrono::{Datelike, Days, NaiveDate, Utc};
+use chrono_tz::Tz;
+use clap::{Args, Parser, Subcommand};
+use color_eyre::eyre::eyre;
 use rust_decimal::Decimal;
-use serde::Deserialize;
+use uuid::Uuid;
 
 use crate::{
+    configuration::{Settings, get_import_profiles},
     models::{
-        account::AccountName,
-        transaction::{CreateTransactionRequest, TransactionTitle},
+        account::{AccountName, AccountType, CreateAccountRequest},
+        budget::Month,
+        currency::Currency,
+        import_batch::{ImportBatch, ImportFormat, ImportReport, ImportRequest, ImportRowStatus},
+        installment::InstallmentCommitments,
+        report::{BalanceSheet, IncomeStatement, ReportSection},
     },
     service::BerryService,
 };
 
-#[derive(Deserialize)]
-struct CreditCardTransaction {
-    #[serde(alias = "Data", deserialize_with = "deserialize_date")]
-    date: NaiveDate,
-    #[serde(alias = "Descrição")]
-    title: String,
-    #[serde(alias = "Valor")]
-    amount: Decimal,
-    #[serde(default)]
-    category: Option<String>,
-}
-
-fn deserialize_date<'de, D>(deserializer: D) -> Result<NaiveDate, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    let s = String::deserialize(deserializer)?;
-    if s.contains("/") {
-        NaiveDate::parse_from_str(&s, "%d/%m/%Y").map_err(serde::de::Error::custom)
-    } else {
-        NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(serde::de::Error::custom)
-    }
-}
-
 #[derive(Debug, Parser)]
 pub struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Import the transactions of a CSV or OFX file
+    Import(ImportArgs),
+    /// List the imported files, the most recent first
+    Imports,
+    /// List what an account, like a credit card, still has to pay in installments
+    Installments(InstallmentsArgs),
+    /// Delete the transactions of an imported file, reverting their effect on balances
+    Rollback(RollbackArgs),
+    /// Print a financial report
+    Report(ReportArgs),
+}
+
+#[derive(Debug, Args)]
+struct ImportArgs {
     /// The input file containing financial data.
     #[arg(short, long)]
     file: PathBuf,
@@ -46,39 +49,410 @@ pub struct Cli {
     /// Name of the source account
     #[arg(short, long)]
     source_account: String,
+
+    /// Type of the source account, used if it does not exist yet
+    #[arg(long, default_value_t = AccountType::Liability)]
+    source_account_type: AccountType,
+
+    /// The format of the input file, either `csv` or `ofx`
+    #[arg(long, default_value_t = ImportFormat::Csv)]
+    format: ImportFormat,
+
+    /// The import profile describing the layout of CSV files, from
+    /// `configuration/import_profiles.yaml`
+    #[arg(long, default_value = "credit_card")]
+    profile: String,
+
+    /// Print what would be imported without writing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Debug, Args)]
+struct InstallmentsArgs {
+    /// Name of the paying account, like `liabilities:card`
+    account: String,
+}
+
+#[derive(Debug, Args)]
+struct RollbackArgs {
+    /// The id of the import batch, as printed by the import or listed by `imports`
+    batch_id: Uuid,
+}
+
+#[derive(Debug, Args)]
+struct ReportArgs {
+    #[command(subcommand)]
+    kind: ReportKind,
+
+    /// The first day of the report, like `2026-01-01`
+    #[arg(long, global = true)]
+    from: Option<NaiveDate>,
+
+    /// The day after the last day of the report, like `2026-02-01`
+    #[arg(long, global = true)]
+    to: Option<NaiveDate>,
+
+    /// The currency or commodity to report amounts in, like USD
+    #[arg(long, global = true, default_value_t = Currency::BRL)]
+    currency: Currency,
+
+    /// Print the report as JSON, as returned by the API, instead of a table
+    #[arg(long, global = true)]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Copy, Subcommand)]
+enum ReportKind {
+    /// Income and expenses per month
+    IncomeStatement,
+    /// Assets, liabilities and equity at the end of each month
+    BalanceSheet,
 }
 
 impl Cli {
-    pub async fn run(&self, service: BerryService) -> color_eyre::Result<()> {
-        let mut rdr = csv::Reader::from_path(&self.file)?;
-        let credit_card_account_name = AccountName::new(&self.source_account)?;
-        let credit_card_account = service
-            .get_or_create_account(&credit_card_account_name)
-            .await?;
-
-        for result in rdr.deserialize() {
-            let transaction: CreditCardTransaction = result?;
-
-            let destination_account_name = AccountName::new(&transaction.title)?;
-            let destination_account = service
-                .get_or_create_account(&destination_account_name)
-                .await?;
-            let title = TransactionTitle::new(&transaction.title)?;
-            let req = CreateTransactionRequest::new(
-                title,
-                transaction.amount,
-                credit_card_account.id(),
-                destination_account.id(),
-                transaction.category,
-                Some(transaction.date.into()),
-            );
-            let response = service.create_transaction(&req).await;
-            match response {
-                Ok(tx) => tracing::info!(transaction = ?tx, "Transaction successfully created"),
-                Err(err) => tracing::error!(error = %err, "Failed to create transaction"),
+    /// Run the requested command. Dates are interpreted as local dates in the configured
+    /// timezone.
+    ///
+    /// Imports end by printing how many rows were new, already imported or failed.
+    pub async fn run(&self, service: BerryService, settings: &Settings) -> color_eyre::Result<()> {
+        let timezone = settings.application.timezone;
+        match &self.command {
+            Command::Import(args) => {
+                let report = args.run(service, settings).await?;
+                println!("{}", render_import_report(&report, timezone));
+                Ok(())
             }
+            Command::Imports => {
+                let batches = service.list_import_batches().await?;
+                println!("{}", render_import_batches(&batches, timezone));
+                Ok(())
+            }
+            Command::Installments(args) => {
+                let account = service
+                    .get_account_by_name(&AccountName::new(&args.account)?)
+                    .await?;
+                let commitments = service.get_installment_commitments(account.id()).await?;
+                println!("{}", render_installment_commitments(&commitments, timezone));
+                Ok(())
+            }
+            Command::Rollback(args) => {
+                let deleted = service.rollback_import_batch(args.batch_id).await?;
+                println!(
+                    "Rolled back import batch {}, deleting {deleted} transactions",
+                    args.batch_id
+                );
+                Ok(())
+            }
+            Command::Report(args) => args.run(service, timezone).await,
+        }
+    }
+}
+
+impl ImportArgs {
+    /// Import the file's transactions into the source account, each of them moving money
+    /// between the source account and the configured default expense or income account.
+    async fn run(
+        &self,
+        service: BerryService,
+        settings: &Settings,
+    ) -> color_eyre::Result<ImportReport> {
+        let account = CreateAccountRequest::new(
+            AccountName::new(&self.source_account)?,
+            self.source_account_type,
+        );
+        let source = self
+            .file
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.file.display().to_string());
+        let mut req = ImportRequest::new(source, self.format, account, std::fs::read(&self.file)?)
+            .with_dry_run(self.dry_run)
+            .with_settings(settings.import.clone());
+
+        if self.format == ImportFormat::Csv {
+            let mut profiles = get_import_profiles(None)?;
+            let profile = profiles.remove(&self.profile).ok_or_else(|| {
+                let mut names: Vec<_> = profiles.keys().map(String::as_str).collect();
+                names.sort();
+                eyre!(
+                    "there is no import profile named {:?}, the available ones are: {}",
+                    self.profile,
+                    names.join(", ")
+                )
+            })?;
+            req = req.with_profile(&self.profile, profile);
         }
 
+        Ok(service.import(&req, settings.application.timezone).await?)
+    }
+}
+
+/// Describe the outcome of an import. Dry runs list every row, while actual imports only list
+/// the rows that failed.
+fn render_import_report(report: &ImportReport, timezone: Tz) -> String {
+    let mut lines = Vec::new();
+    match report.batch() {
+        Some(batch) => lines.push(format!(
+            "Imported {} as batch {}: {}",
+            batch.source(),
+            batch.id(),
+            report.summary()
+        )),
+        None => lines.push(format!(
+            "Dry run, nothing was written: {}",
+            report.summary()
+        )),
+    }
+
+    for row in report.rows() {
+        let status = match row.status() {
+            ImportRowStatus::Created { .. } if report.dry_run() => "new".to_string(),
+            ImportRowStatus::Duplicate { .. } if report.dry_run() => "duplicate".to_string(),
+            ImportRowStatus::Failed { error } => format!("failed: {error}"),
+            _ => continue,
+        };
+        let parts = [
+            row.line().map(|line| format!("line {line}:")),
+            row.posting_date()
+                .map(|date| date.with_timezone(&timezone).date_naive().to_string()),
+            row.amount().map(|amount| format!("{amount:.2}")),
+            row.title().map(str::to_string),
+            Some(format!("({status})")),
+        ];
+        lines.push(format!(
+            "  {}",
+            parts.into_iter().flatten().collect::<Vec<_>>().join(" ")
+        ));
+    }
+
+    lines.join("\n")
+}
+
+fn render_import_batches(batches: &[ImportBatch], timezone: Tz) -> String {
+    let mut rows = vec![vec![
+        "Batch".to_string(),
+        "Imported at".to_string(),
+        "File".to_string(),
+        "Rows".to_string(),
+        "Transactions".to_string(),
+    ]];
+    rows.extend(batches.iter().map(|batch| {
+        vec![
+            batch.id().to_string(),
+            batch
+                .created_at()
+                .with_timezone(&timezone)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            batch.source().to_string(),
+            batch.row_count().to_string(),
+            batch.transaction_count().to_string(),
+        ]
+    }));
+
+    render_table(&rows)
+}
+
+fn render_installment_commitments(commitments: &InstallmentCommitments, timezone: Tz) -> String {
+    let mut rows = vec![vec![
+        "Purchase".to_string(),
+        "Installments left".to_string(),
+        "Next".to_string(),
+        "Left to pay".to_string(),
+    ]];
+    rows.extend(commitments.plans().iter().map(|commitment| {
+        let plan = commitment.plan();
+        vec![
+            plan.title().to_string(),
+            format!(
+                "{} of {}",
+                commitment.remaining_count(),
+                plan.installment_count()
+            ),
+            commitment
+                .next_posting_date()
+                .map(|date| date.with_timezone(&timezone).date_naive().to_string())
+                .unwrap_or_default(),
+            format!("{:.2}", commitment.remaining_amount()),
+        ]
+    }));
+    rows.push(vec![
+        "Total".to_string(),
+        String::new(),
+        String::new(),
+        format!("{:.2}", commitments.remaining_amount()),
+    ]);
+
+    render_table(&rows)
+}
+
+impl ReportArgs {
+    /// Print the report to stdout. It defaults to the current year up to today in `timezone`.
+    async fn run(&self, service: BerryService, timezone: Tz) -> color_eyre::Result<()> {
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+        let from = self
+            .from
+            .unwrap_or_else(|| today.with_ordinal(1).expect("every year has a first day"));
+        let to = self.to.unwrap_or_else(|| today + Days::new(1));
+
+        let output = match self.kind {
+            ReportKind::IncomeStatement => {
+                let report = service
+                    .get_income_statement(from, to, self.currency, timezone)
+                    .await?;
+                if self.json {
+                    serde_json::to_string_pretty(&report)?
+                } else {
+                    render_income_statement(&report)
+                }
+            }
+            ReportKind::BalanceSheet => {
+                let report = service
+                    .get_balance_sheet(from, to, self.currency, timezone)
+                    .await?;
+                if self.json {
+                    serde_json::to_string_pretty(&report)?
+                } else {
+                    render_balance_sheet(&report)
+                }
+            }
+        };
+        println!("{output}");
+
         Ok(())
     }
 }
+
+/// Append the rows of a report section: a title, one indented row per account and the totals.
+/// The total column is only added when `with_total` is set.
+fn push_section(
+    rows: &mut Vec<Vec<String>>,
+    title: &str,
+    section: &ReportSection,
+    with_total: bool,
+) {
+    let row = |label: String, amounts: &[Decimal], total: Decimal| {
+        let mut row = vec![label];
+        row.extend(amounts.iter().map(|amount| format!("{amount:.2}")));
+        if with_total {
+            row.push(format!("{total:.2}"));
+        }
+        row
+    };
+
+    rows.push(vec![title.to_string()]);
+    for line in section.lines() {
+        rows.push(row(
+            format!("  {}", line.account_name()),
+            line.amounts(),
+            line.total(),
+        ));
+    }
+    rows.push(row(
+        format!("Total {}", title.to_lowercase()),
+        section.totals(),
+        section.total(),
+    ));
+}
+
+fn render_income_statement(report: &IncomeStatement) -> String {
+    let mut header = vec![String::new()];
+    header.extend(report.months().iter().map(Month::to_string));
+    header.push("Total".to_string());
+
+    let mut rows = vec![header];
+    push_section(&mut rows, "Income", report.income(), true);
+    push_section(&mut rows, "Expenses", report.expenses(), true);
+    let mut net_income = vec!["Net income".to_string()];
+    net_income.extend(report.net_income().iter().map(|a| format!("{a:.2}")));
+    net_income.push(format!("{:.2}", report.net_income_total()));
+    rows.push(net_income);
+
+    format!(
+        "Income statement from {} to {}\n\n{}",
+        report.from(),
+        report.to(),
+        render_table(&rows)
+    )
+}
+
+fn render_balance_sheet(report: &BalanceSheet) -> String {
+    let mut header = vec![String::new()];
+    header.extend(report.months().iter().map(Month::to_string));
+
+    let mut rows = vec![header];
+    push_section(&mut rows, "Assets", report.assets(), false);
+    push_section(&mut rows, "Liabilities", report.liabilities(), false);
+    push_section(&mut rows, "Equity", report.equity(), false);
+    let mut retained_earnings = vec!["Retained earnings".to_string()];
+    retained_earnings.extend(report.retained_earnings().iter().map(|a| format!("{a:.2}")));
+    rows.push(retained_earnings);
+
+    format!(
+        "Balance sheet from {} to {}\n\n{}",
+        report.from(),
+        report.to(),
+        render_table(&rows)
+    )
+}
+
+/// Lay out `rows` in columns, with the first one aligned to the left and the others, which hold
+/// amounts, aligned to the right.
+fn render_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or_default();
+    let widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(column, (cell, width))| {
+                    if column == 0 {
+                        format!("{cell:<width$}")
+                    } else {
+                        format!("  {cell:>width$}")
+                    }
+                })
+                .collect::<String>()
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_table_aligns_labels_left_and_amounts_right() {
+        let rows = vec![
+            vec!["".to_string(), "2026-01".to_string()],
+            vec!["Income".to_string()],
+            vec!["  salary".to_string(), "1000.00".to_string()],
+            vec!["Total income".to_string(), "5.00".to_string()],
+        ];
+
+        assert_eq!(
+            render_table(&rows),
+            [
+                "              2026-01",
+                "Income",
+                "  salary      1000.00",
+                "Total income     5.00",
+            ]
+            .join("\n")
+        );
+    }
+}
@@ -1,15 +1,21 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
+use chrono_tz::Tz;
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 use sqlx::postgres::{PgConnectOptions, PgSslMode};
 
+use crate::import::csv::CsvProfile;
+use crate::models::account::AccountName;
 use crate::utils::deserialize_number_from_string;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Settings {
     pub application: ApplicationSettings,
     pub database: DatabaseSettings,
+    #[serde(default)]
+    pub import: ImportSettings,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -17,6 +23,38 @@ pub struct ApplicationSettings {
     #[serde(deserialize_with = "deserialize_number_from_string")]
     pub port: u16,
     pub host: String,
+    /// The timezone used to interpret dates and times that do not carry a UTC offset
+    pub timezone: Tz,
+    /// How often, in seconds, the occurrences of scheduled transactions that came due are posted
+    #[serde(
+        default = "default_scheduler_period",
+        deserialize_with = "deserialize_number_from_string"
+    )]
+    pub scheduler_period: u64,
+}
+
+fn default_scheduler_period() -> u64 {
+    60
+}
+
+/// How imported transactions are booked
+#[derive(Debug, Deserialize, Clone)]
+pub struct ImportSettings {
+    /// The account money leaving the imported account goes to, whatever the payee
+    pub default_expense_account: AccountName,
+    /// The account money coming into the imported account comes from, whatever the payee
+    pub default_income_account: AccountName,
+}
+
+impl Default for ImportSettings {
+    fn default() -> Self {
+        Self {
+            default_expense_account: AccountName::new("expenses:uncategorized")
+                .expect("the default expense account has a valid name"),
+            default_income_account: AccountName::new("income:uncategorized")
+                .expect("the default income account has a valid name"),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -79,6 +117,21 @@ pub fn get_configuration(base_path: Option<PathBuf>) -> Result<Settings, config:
     settings.try_deserialize::<Settings>()
 }
 
+/// Load the named CSV import profiles from `configuration/import_profiles.yaml`, under
+/// `base_path` or the current directory.
+pub fn get_import_profiles(
+    base_path: Option<PathBuf>,
+) -> Result<HashMap<String, CsvProfile>, config::ConfigError> {
+    let base_path = base_path
+        .unwrap_or_else(|| std::env::current_dir().expect("Failed to determine current directory"));
+    let path = base_path.join("configuration").join("import_profiles.yaml");
+
+    config::Config::builder()
+        .add_source(config::File::from(path))
+        .build()?
+        .try_deserialize()
+}
+
 /// The possible runtime environment for our application.
 pub enum Environment {
     Local,
@@ -108,3 +161,18 @@ impl TryFrom<String> for Environment {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::get_import_profiles;
+
+    #[test]
+    fn the_bundled_import_profiles_are_valid() {
+        let profiles =
+            get_import_profiles(Some(PathBuf::from(env!("CARGO_MANIFEST_DIR")))).unwrap();
+
+        assert!(profiles.contains_key("credit_card"));
+    }
+}
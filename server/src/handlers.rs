@@ -1,19 +1,106 @@
+pub mod apply_rules;
 pub mod create_account;
+pub mod create_budget;
+pub mod create_category;
+pub mod create_import;
+pub mod create_price;
+pub mod create_rule;
+pub mod create_schedule;
 pub mod create_transaction;
 pub mod delete_account;
+pub mod delete_budget;
+pub mod delete_category;
+pub mod delete_price;
+pub mod delete_rule;
+pub mod delete_schedule;
 pub mod delete_transaction;
+pub mod extractors;
 pub mod get_account;
+pub mod get_account_tree;
+pub mod get_balance_history;
+pub mod get_balance_sheet;
+pub mod get_budget_report;
+pub mod get_card_statements;
+pub mod get_cash_flow_forecast;
+pub mod get_category;
+pub mod get_income_statement;
+pub mod get_installment_commitments;
+pub mod get_price;
+pub mod get_rule;
+pub mod get_schedule;
 pub mod get_transaction;
+pub mod import_prices;
+pub mod list_account_postings;
 pub mod list_accounts;
+pub mod list_categories;
+pub mod list_import_batches;
+pub mod list_payees;
+pub mod list_prices;
+pub mod list_rules;
+pub mod list_schedules;
 pub mod list_transactions;
+pub mod list_upcoming_occurrences;
+pub mod lookup_price;
 pub mod rename_account;
+pub mod rollback_import_batch;
+pub mod set_credit_card;
+pub mod skip_occurrence;
+pub mod update_budget;
+pub mod update_category;
+pub mod update_price;
+pub mod update_rule;
+pub mod update_schedule;
+pub mod update_transaction;
 
+pub use apply_rules::apply_rules;
 pub use create_account::create_account;
+pub use create_budget::create_budget;
+pub use create_category::create_category;
+pub use create_import::create_import;
+pub use create_price::create_price;
+pub use create_rule::create_rule;
+pub use create_schedule::create_schedule;
 pub use create_transaction::create_transaction;
 pub use delete_account::delete_account;
+pub use delete_budget::delete_budget;
+pub use delete_category::delete_category;
+pub use delete_price::delete_price;
+pub use delete_rule::delete_rule;
+pub use delete_schedule::delete_schedule;
 pub use delete_transaction::delete_transaction;
 pub use get_account::{find_account_by_name, get_account};
+pub use get_account_tree::get_account_tree;
+pub use get_balance_history::get_balance_history;
+pub use get_balance_sheet::get_balance_sheet;
+pub use get_budget_report::get_budget_report;
+pub use get_card_statements::{get_card_statement, get_card_statements};
+pub use get_cash_flow_forecast::get_cash_flow_forecast;
+pub use get_category::get_category;
+pub use get_income_statement::get_income_statement;
+pub use get_installment_commitments::get_installment_commitments;
+pub use get_price::get_price;
+pub use get_rule::get_rule;
+pub use get_schedule::get_schedule;
 pub use get_transaction::get_transaction;
+pub use import_prices::import_prices;
+pub use list_account_postings::list_account_postings;
 pub use list_accounts::list_accounts;
+pub use list_categories::list_categories;
+pub use list_import_batches::list_import_batches;
+pub use list_payees::list_payees;
+pub use list_prices::list_prices;
+pub use list_rules::list_rules;
+pub use list_schedules::list_schedules;
 pub use list_transactions::list_transactions;
+pub use list_upcoming_occurrences::list_upcoming_occurrences;
+pub use lookup_price::lookup_price;
 pub use rename_account::rename_account;
+pub use rollback_import_batch::rollback_import_batch;
+pub use set_credit_card::set_credit_card;
+pub use skip_occurrence::skip_occurrence;
+pub use update_budget::update_budget;
+pub use update_category::update_category;
+pub use update_price::update_price;
+pub use update_rule::update_rule;
+pub use update_schedule::update_schedule;
+pub use update_transaction::update_transaction;
@@ -0,0 +1,36 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use serde::Deserialize;
+
+use crate::models::rule::{ApplyRulesError, ApplyRulesReport};
+use crate::server::AppState;
+
+#[derive(Deserialize, Default)]
+pub struct ApplyRulesQuery {
+    /// Only report what would change, without writing anything. Defaults to `false`.
+    #[serde(default)]
+    preview: bool,
+}
+
+/// Re-apply the rules to all existing transactions
+pub async fn apply_rules(
+    State(state): State<AppState>,
+    Query(query): Query<ApplyRulesQuery>,
+) -> Result<Json<ApplyRulesReport>, (StatusCode, &'static str)> {
+    let report = state
+        .service
+        .apply_rules(query.preview)
+        .await
+        .map_err(|err| match err {
+            ApplyRulesError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+
+    Ok(Json(report))
+}
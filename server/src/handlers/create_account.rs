@@ -1,19 +1,28 @@
-use axum::{Form, Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State, http::StatusCode};
 use serde::Deserialize;
 
 use crate::{
-    models::account::{Account, AccountName, CreateAccountError},
+    handlers::extractors::JsonOrForm,
+    models::account::{
+        Account, AccountName, AccountType, CreateAccountError, CreateAccountRequest,
+    },
+    models::currency::Currency,
     server::AppState,
 };
 
 #[derive(Deserialize, Debug)]
-pub struct CreateAccountRequest {
+pub struct CreateAccountRequestBody {
     name: String,
+    /// Defaults to [AccountType::Asset]
+    #[serde(rename = "type", default)]
+    account_type: AccountType,
+    /// An ISO 4217 code or a commodity symbol like PETR4, defaults to [Currency::BRL]
+    currency: Option<String>,
 }
 
 pub async fn create_account(
     State(state): State<AppState>,
-    Form(body): Form<CreateAccountRequest>,
+    JsonOrForm(body): JsonOrForm<CreateAccountRequestBody>,
 ) -> Result<(StatusCode, Json<Account>), (StatusCode, String)> {
     let account_name = AccountName::new(&body.name).map_err(|e| {
         tracing::error!(error = ?e);
@@ -24,9 +33,19 @@ pub async fn create_account(
         )
     })?;
 
+    let currency = body
+        .currency
+        .as_deref()
+        .map(Currency::new)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+        .unwrap_or_default();
+
     let account = state
         .service
-        .create_account(&account_name)
+        .create_account(
+            &CreateAccountRequest::new(account_name, body.account_type).with_currency(currency),
+        )
         .await
         .map_err(|err| match err {
             CreateAccountError::Duplicate { name } => (
@@ -0,0 +1,72 @@
+use axum::{Json, extract::State, http::StatusCode};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::handlers::create_transaction::parse_id;
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::budget::{Budget, BudgetTarget, CreateBudgetError, CreateBudgetRequest, Month};
+use crate::server::AppState;
+
+/// The body of a request to create a budget, which targets either a `category_id` or an
+/// `account_id`
+#[derive(Deserialize, Debug)]
+pub struct CreateBudgetRequestBody {
+    /// A year and a month, like `2026-03`
+    month: String,
+    category_id: Option<String>,
+    account_id: Option<String>,
+    amount: Decimal,
+    /// Defaults to `false`
+    #[serde(default)]
+    rollover: bool,
+}
+
+pub async fn create_budget(
+    State(state): State<AppState>,
+    JsonOrForm(body): JsonOrForm<CreateBudgetRequestBody>,
+) -> Result<(StatusCode, Json<Budget>), (StatusCode, String)> {
+    let month = body
+        .month
+        .parse::<Month>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let target = match (body.category_id, body.account_id) {
+        (Some(id), None) => parse_id(&id, "category_id").map(BudgetTarget::Category),
+        (None, Some(id)) => parse_id(&id, "account_id").map(BudgetTarget::Account),
+        _ => {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "a budget needs either a category_id or an account_id".to_string(),
+            ));
+        }
+    }
+    .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let budget = state
+        .service
+        .create_budget(&CreateBudgetRequest::new(
+            month,
+            target,
+            body.amount,
+            body.rollover,
+        ))
+        .await
+        .map_err(|e| match e {
+            CreateBudgetError::CategoryNotFound { .. }
+            | CreateBudgetError::AccountNotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            CreateBudgetError::Duplicate { .. }
+            | CreateBudgetError::NotAnExpenseAccount { .. }
+            | CreateBudgetError::NegativeAmount => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            CreateBudgetError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(budget)))
+}
@@ -0,0 +1,40 @@
+use axum::{Json, extract::State, http::StatusCode};
+use serde::Deserialize;
+
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::category::{Category, CategoryName, CreateCategoryError};
+use crate::server::AppState;
+
+#[derive(Deserialize, Debug)]
+pub struct CategoryRequestBody {
+    /// A colon-separated name like `food:restaurants`. Missing parent categories are created.
+    pub(crate) name: String,
+}
+
+pub async fn create_category(
+    State(state): State<AppState>,
+    JsonOrForm(body): JsonOrForm<CategoryRequestBody>,
+) -> Result<(StatusCode, Json<Category>), (StatusCode, String)> {
+    let name =
+        CategoryName::new(&body.name).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let category = state
+        .service
+        .create_category(&name)
+        .await
+        .map_err(|e| match e {
+            CreateCategoryError::Duplicate { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            CreateCategoryError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(category)))
+}
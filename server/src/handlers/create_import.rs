@@ -0,0 +1,131 @@
+use axum::Json;
+use axum::extract::{Multipart, State};
+use axum::http::StatusCode;
+
+use crate::models::account::{AccountName, AccountType, CreateAccountRequest};
+use crate::models::import_batch::{ImportError, ImportFormat, ImportReport, ImportRequest};
+use crate::server::AppState;
+
+/// The profile CSV files are read with when the request does not name one, like in the `cli`
+const DEFAULT_PROFILE: &str = "credit_card";
+
+/// Import a file uploaded as `multipart/form-data`, with the fields:
+///
+/// - `file`: the file itself, whose name is recorded in the import batch
+/// - `source_account`: the name of the account to import into, created if it does not exist
+/// - `source_account_type`: the type of that account if it is created, `liability` by default
+/// - `format`: `csv` or `ofx`, guessed from the file's extension by default
+/// - `profile`: the import profile CSV files are read with, `credit_card` by default
+/// - `dry_run`: `true` to only report what would be imported
+///
+/// Responds with what happened to each row of the file.
+pub async fn create_import(
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ImportReport>), (StatusCode, String)> {
+    let bad_request = |message: String| (StatusCode::BAD_REQUEST, message);
+
+    let mut file = None;
+    let mut source_account = None;
+    let mut source_account_type = AccountType::Liability;
+    let mut format = None;
+    let mut profile = None;
+    let mut dry_run = false;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| bad_request(e.to_string()))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        if name == "file" {
+            let file_name = field.file_name().unwrap_or("upload").to_string();
+            let contents = field
+                .bytes()
+                .await
+                .map_err(|e| bad_request(e.to_string()))?;
+            file = Some((file_name, contents.to_vec()));
+            continue;
+        }
+
+        let value = field.text().await.map_err(|e| bad_request(e.to_string()))?;
+        match name.as_str() {
+            "source_account" => {
+                source_account = Some(AccountName::new(&value).map_err(|_| {
+                    bad_request("source_account must be a nonempty string".to_string())
+                })?)
+            }
+            "source_account_type" => {
+                source_account_type = value.parse().map_err(|e| bad_request(format!("{e}")))?
+            }
+            "format" => format = Some(value.parse().map_err(|e| bad_request(format!("{e}")))?),
+            "profile" => profile = Some(value),
+            "dry_run" => {
+                dry_run = value
+                    .parse()
+                    .map_err(|_| bad_request(format!("\"{value}\" is not a valid dry_run")))?
+            }
+            _ => return Err(bad_request(format!("unexpected field \"{name}\""))),
+        }
+    }
+
+    let (file_name, contents) = file.ok_or_else(|| bad_request("missing file".to_string()))?;
+    let source_account =
+        source_account.ok_or_else(|| bad_request("missing source_account".to_string()))?;
+    let format = format.unwrap_or_else(|| guess_format(&file_name));
+
+    let mut req = ImportRequest::new(
+        file_name,
+        format,
+        CreateAccountRequest::new(source_account, source_account_type),
+        contents,
+    )
+    .with_dry_run(dry_run)
+    .with_settings(state.import_settings.clone());
+    if format == ImportFormat::Csv {
+        let name = profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string());
+        let csv_profile = state.import_profiles.get(&name).cloned().ok_or_else(|| {
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!("there is no import profile named \"{name}\""),
+            )
+        })?;
+        req = req.with_profile(name, csv_profile);
+    }
+
+    let report = state
+        .service
+        .import(&req, state.timezone)
+        .await
+        .map_err(|e| match e {
+            ImportError::MissingProfile | ImportError::Csv(_) | ImportError::Ofx(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            ImportError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    let status = if dry_run {
+        StatusCode::OK
+    } else {
+        StatusCode::CREATED
+    };
+
+    Ok((status, Json(report)))
+}
+
+/// OFX and QFX files are recognized by their extension, everything else is read as CSV
+fn guess_format(file_name: &str) -> ImportFormat {
+    let extension = file_name
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_lowercase());
+    match extension.as_deref() {
+        Some("ofx" | "qfx") => ImportFormat::Ofx,
+        _ => ImportFormat::Csv,
+    }
+}
@@ -0,0 +1,50 @@
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::currency::Currency;
+use crate::models::price::{CreatePriceError, CreatePriceRequest, Price};
+use crate::server::AppState;
+
+/// The body of a request to create a price or to replace all of its fields: what one unit of
+/// `commodity` was worth in `currency` on `date`
+#[derive(Deserialize, Debug)]
+pub struct PriceRequestBody {
+    commodity: Currency,
+    currency: Currency,
+    date: NaiveDate,
+    rate: Decimal,
+}
+
+impl From<PriceRequestBody> for CreatePriceRequest {
+    fn from(body: PriceRequestBody) -> Self {
+        CreatePriceRequest::new(body.commodity, body.currency, body.date, body.rate)
+    }
+}
+
+pub async fn create_price(
+    State(state): State<AppState>,
+    JsonOrForm(body): JsonOrForm<PriceRequestBody>,
+) -> Result<(StatusCode, Json<Price>), (StatusCode, String)> {
+    let price = state
+        .service
+        .create_price(&body.into())
+        .await
+        .map_err(|e| match e {
+            CreatePriceError::Invalid(_) | CreatePriceError::Duplicate { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            CreatePriceError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(price)))
+}
@@ -0,0 +1,92 @@
+use axum::{Json, extract::State, http::StatusCode};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::category::CategoryName;
+use crate::models::rule::{
+    CreateRuleError, CreateRuleRequest, Rule, RuleActions, RuleConditions, RuleValidationError,
+};
+use crate::server::AppState;
+
+/// The body of a request to create a rule or to replace all of its fields. Conditions left out
+/// always hold, and at least one action is required.
+#[derive(Deserialize, Debug)]
+pub struct RuleRequestBody {
+    name: String,
+    /// Rules with lower priorities are tried first. Defaults to `0`.
+    #[serde(default)]
+    priority: i32,
+    /// A regular expression the title must match, like `(?i)^uber`
+    title_pattern: Option<String>,
+    min_amount: Option<Decimal>,
+    max_amount: Option<Decimal>,
+    source_account_id: Option<Uuid>,
+    payee_id: Option<Uuid>,
+    category: Option<CategoryName>,
+    destination_account_id: Option<Uuid>,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+impl From<RuleRequestBody> for CreateRuleRequest {
+    fn from(body: RuleRequestBody) -> Self {
+        CreateRuleRequest::new(
+            body.name,
+            body.priority,
+            RuleConditions {
+                title_pattern: body.title_pattern,
+                min_amount: body.min_amount,
+                max_amount: body.max_amount,
+                source_account_id: body.source_account_id,
+                payee_id: body.payee_id,
+            },
+            RuleActions {
+                category: body.category,
+                destination_account_id: body.destination_account_id,
+                tags: body.tags,
+            },
+        )
+    }
+}
+
+/// Map the reasons a rule is not valid to a status code: references to missing records are
+/// not found, anything else cannot be processed
+pub fn invalid_rule(e: RuleValidationError) -> (StatusCode, String) {
+    match e {
+        RuleValidationError::AccountNotFound { .. }
+        | RuleValidationError::PayeeNotFound { .. }
+        | RuleValidationError::CategoryNotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+        RuleValidationError::EmptyName
+        | RuleValidationError::InvalidPattern { .. }
+        | RuleValidationError::InvalidAmountRange
+        | RuleValidationError::NoActions
+        | RuleValidationError::CurrencyMismatch { .. } => {
+            (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+        }
+    }
+}
+
+pub async fn create_rule(
+    State(state): State<AppState>,
+    JsonOrForm(body): JsonOrForm<RuleRequestBody>,
+) -> Result<(StatusCode, Json<Rule>), (StatusCode, String)> {
+    let rule = state
+        .service
+        .create_rule(&body.into())
+        .await
+        .map_err(|e| match e {
+            CreateRuleError::Invalid(e) => invalid_rule(e),
+            CreateRuleError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(rule)))
+}
@@ -0,0 +1,89 @@
+use axum::{Json, extract::State, http::StatusCode};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::category::CategoryName;
+use crate::models::schedule::{
+    CreateScheduleError, CreateScheduleRequest, Recurrence, Schedule, ScheduleValidationError,
+    ScheduledTransfer,
+};
+use crate::models::transaction::TransactionTitle;
+use crate::server::AppState;
+
+/// The body of a request to create a schedule or to replace all of its fields. The recurrence
+/// is given by a `frequency`, one of `weekly`, `monthly`, `last_business_day` and `yearly`, an
+/// `interval`, which defaults to `1`, and for monthly schedules the `day`.
+#[derive(Deserialize, Debug)]
+pub struct ScheduleRequestBody {
+    title: TransactionTitle,
+    amount: Decimal,
+    source_account_id: Uuid,
+    destination_account_id: Uuid,
+    category: Option<CategoryName>,
+    #[serde(flatten)]
+    recurrence: Recurrence,
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+}
+
+impl From<ScheduleRequestBody> for CreateScheduleRequest {
+    fn from(body: ScheduleRequestBody) -> Self {
+        CreateScheduleRequest::new(
+            ScheduledTransfer {
+                title: body.title,
+                amount: body.amount,
+                source_account_id: body.source_account_id,
+                destination_account_id: body.destination_account_id,
+                category: body.category,
+            },
+            body.recurrence,
+            body.start_date,
+            body.end_date,
+        )
+    }
+}
+
+/// Map the reasons a schedule is not valid to a status code: references to missing records
+/// are not found, anything else cannot be processed
+pub fn invalid_schedule(e: ScheduleValidationError) -> (StatusCode, String) {
+    match e {
+        ScheduleValidationError::AccountNotFound { .. }
+        | ScheduleValidationError::CategoryNotFound { .. } => {
+            (StatusCode::NOT_FOUND, e.to_string())
+        }
+        ScheduleValidationError::NonPositiveAmount
+        | ScheduleValidationError::SameAccounts
+        | ScheduleValidationError::InvalidInterval { .. }
+        | ScheduleValidationError::InvalidDay { .. }
+        | ScheduleValidationError::InvalidDateRange
+        | ScheduleValidationError::CurrencyMismatch { .. } => {
+            (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+        }
+    }
+}
+
+pub async fn create_schedule(
+    State(state): State<AppState>,
+    JsonOrForm(body): JsonOrForm<ScheduleRequestBody>,
+) -> Result<(StatusCode, Json<Schedule>), (StatusCode, String)> {
+    let schedule = state
+        .service
+        .create_schedule(&body.into())
+        .await
+        .map_err(|e| match e {
+            CreateScheduleError::Invalid(e) => invalid_schedule(e),
+            CreateScheduleError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(schedule)))
+}
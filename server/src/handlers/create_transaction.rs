@@ -2,89 +2,239 @@ use std::str::FromStr as _;
 
 use anyhow::Context as _;
 use axum::Json;
+use axum::extract::State;
 use axum::http::StatusCode;
-use axum::{Form, extract::State};
-use chrono::NaiveDateTime;
+use chrono_tz::Tz;
 use rust_decimal::Decimal;
 use serde::Deserialize;
 use thiserror::Error;
 use uuid::Uuid;
 
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::category::{CategoryName, CategoryNameEmptyError};
+use crate::models::currency::{Currency, ExchangeRate, InvalidExchangeRateError};
 use crate::models::transaction::{
-    CreateTransactionError, CreateTransactionRequest, Transaction, TransactionTitle,
+    CreateTransactionError, CreateTransactionRequest, Leg, PostingDateParseError, Transaction,
+    TransactionTitle, TransactionTitleEmptyError, parse_posting_date,
 };
 use crate::server::AppState;
 
+/// The body of a request to create a transaction.
+///
+/// A transaction is either described by its `legs`, or, for the common case of moving money
+/// between two accounts, by an `amount`, a `source_account_id` and a `destination_account_id`.
 #[derive(Deserialize, Debug)]
 pub struct CreateTransactionRequestBody {
     title: String,
-    amount: Decimal,
-    source_account_id: String,
-    destination_account_id: String,
+    legs: Option<Vec<LegBody>>,
+    amount: Option<Decimal>,
+    source_account_id: Option<String>,
+    destination_account_id: Option<String>,
+    /// The name of an existing category
     category: Option<String>,
-    posting_date: Option<NaiveDateTime>,
+    /// Either an RFC 3339 date and time, or a date and time without an offset, which is
+    /// interpreted in the server's configured timezone
+    posting_date: Option<String>,
+    /// Split the transaction into this many monthly installments, the first one posted at the
+    /// posting date
+    installments: Option<u32>,
+    /// Required when the accounts are in two currencies
+    exchange_rate: Option<ExchangeRateBody>,
+}
+
+/// How much one unit of `base` is worth in `quote`
+#[derive(Deserialize, Debug)]
+pub struct ExchangeRateBody {
+    base: String,
+    quote: String,
+    rate: Decimal,
+}
+
+impl ExchangeRateBody {
+    pub(crate) fn into_domain_model(self) -> Result<ExchangeRate, TransactionRequestBodyError> {
+        let currency = |raw: &str, field: &str| {
+            Currency::new(raw).map_err(|_| TransactionRequestBodyError::InvalidField {
+                field: field.to_string(),
+            })
+        };
+
+        Ok(ExchangeRate::new(
+            currency(&self.base, "exchange_rate.base")?,
+            currency(&self.quote, "exchange_rate.quote")?,
+            self.rate,
+        )?)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct LegBody {
+    account_id: String,
+    amount: Decimal,
+}
+
+impl LegBody {
+    pub(crate) fn into_domain_model(self) -> Result<Leg, TransactionRequestBodyError> {
+        Ok(Leg::new(
+            parse_id(&self.account_id, "legs.account_id")?,
+            self.amount,
+        ))
+    }
 }
 
 impl CreateTransactionRequestBody {
-    fn into_domain_model(self) -> anyhow::Result<CreateTransactionRequest> {
+    fn into_domain_model(
+        self,
+        timezone: Tz,
+    ) -> Result<CreateTransactionRequest, TransactionRequestBodyError> {
         let title = TransactionTitle::new(&self.title)?;
-        let source_account_id = Uuid::from_str(&self.source_account_id)
-            .context("Could not parse the source account's ID")
-            .map_err(|e| {
-                tracing::error!("{:?}\n{}", e, e.backtrace());
-
-                CreateTransactionRequestBodyParseError {
-                    field: "source_account_id".to_string(),
-                }
-            })?;
-        let destination_account_id = Uuid::from_str(&self.destination_account_id)
-            .context("Failed to parse destination account's ID")
-            .map_err(|e| {
-                tracing::error!("{:?}\n{}", e, e.backtrace());
-
-                CreateTransactionRequestBodyParseError {
-                    field: "destination_account_id".to_string(),
-                }
-            })?;
-
-        Ok(CreateTransactionRequest::new(
-            title,
-            self.amount,
-            source_account_id,
-            destination_account_id,
-            self.category,
-            self.posting_date,
+        let category = self
+            .category
+            .as_deref()
+            .map(parse_category)
+            .transpose()?
+            .flatten();
+        let posting_date = self
+            .posting_date
+            .as_deref()
+            .map(|raw| parse_posting_date(raw, timezone))
+            .transpose()?;
+
+        let installments = self.installments.unwrap_or(1);
+        let exchange_rate = self
+            .exchange_rate
+            .map(ExchangeRateBody::into_domain_model)
+            .transpose()?;
+        let with_exchange_rate = |req: CreateTransactionRequest| match exchange_rate {
+            Some(exchange_rate) => req.with_exchange_rate(exchange_rate),
+            None => req,
+        };
+
+        if let Some(legs) = self.legs {
+            let legs = legs
+                .into_iter()
+                .map(LegBody::into_domain_model)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            return Ok(with_exchange_rate(
+                CreateTransactionRequest::new(title, legs, category, posting_date)
+                    .with_installments(installments),
+            ));
+        }
+
+        let amount = positive_amount(self.amount.ok_or(missing("amount"))?)?;
+        let source_account_id = self.source_account_id.ok_or(missing("source_account_id"))?;
+        let destination_account_id = self
+            .destination_account_id
+            .ok_or(missing("destination_account_id"))?;
+
+        Ok(with_exchange_rate(
+            CreateTransactionRequest::transfer(
+                title,
+                amount,
+                parse_id(&source_account_id, "source_account_id")?,
+                parse_id(&destination_account_id, "destination_account_id")?,
+                category,
+                posting_date,
+            )
+            .with_installments(installments),
         ))
     }
 }
 
+pub(crate) fn parse_id(raw: &str, field: &str) -> Result<Uuid, TransactionRequestBodyError> {
+    Uuid::from_str(raw)
+        .with_context(|| format!("Could not parse the {field} field"))
+        .map_err(|e| {
+            tracing::error!("{:?}\n{}", e, e.backtrace());
+
+            TransactionRequestBodyError::InvalidField {
+                field: field.to_string(),
+            }
+        })
+}
+
+/// Parse the category field of a request body. An empty category means no category.
+pub(crate) fn parse_category(
+    raw: &str,
+) -> Result<Option<CategoryName>, TransactionRequestBodyError> {
+    if raw.trim().is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(CategoryName::new(raw)?))
+    }
+}
+
+/// Check that the amount moved by a transfer is positive, as a negative one would silently swap
+/// its source and destination
+pub(crate) fn positive_amount(amount: Decimal) -> Result<Decimal, TransactionRequestBodyError> {
+    if amount <= Decimal::ZERO {
+        return Err(TransactionRequestBodyError::NonPositiveAmount { amount });
+    }
+
+    Ok(amount)
+}
+
+fn missing(field: &str) -> TransactionRequestBodyError {
+    TransactionRequestBodyError::MissingField {
+        field: field.to_string(),
+    }
+}
+
+/// Specifies errors that may arise from parsing the body of a request to create or update a
+/// [Transaction]
 #[derive(Debug, Error)]
-#[error("Failed to parse the following body field: {field}")]
-pub struct CreateTransactionRequestBodyParseError {
-    field: String,
+pub enum TransactionRequestBodyError {
+    #[error("Missing the following body field: {field}")]
+    MissingField { field: String },
+    #[error("Failed to parse the following body field: {field}")]
+    InvalidField { field: String },
+    #[error("the amount of a transfer must be positive, not {amount}")]
+    NonPositiveAmount { amount: Decimal },
+    #[error(transparent)]
+    InvalidTitle(#[from] TransactionTitleEmptyError),
+    #[error(transparent)]
+    InvalidPostingDate(#[from] PostingDateParseError),
+    #[error(transparent)]
+    InvalidCategory(#[from] CategoryNameEmptyError),
+    #[error(transparent)]
+    InvalidExchangeRate(#[from] InvalidExchangeRateError),
 }
 
 pub async fn create_transaction(
     State(state): State<AppState>,
-    Form(body): Form<CreateTransactionRequestBody>,
+    JsonOrForm(body): JsonOrForm<CreateTransactionRequestBody>,
 ) -> Result<(StatusCode, Json<Transaction>), (StatusCode, String)> {
     let req = body
-        .into_domain_model()
-        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+        .into_domain_model(state.timezone)
+        .map_err(|e| match e {
+            TransactionRequestBodyError::MissingField { .. }
+            | TransactionRequestBodyError::NonPositiveAmount { .. }
+            | TransactionRequestBodyError::InvalidExchangeRate(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            _ => (StatusCode::BAD_REQUEST, e.to_string()),
+        })?;
 
     let transaction = state
         .service
         .create_transaction(&req)
         .await
         .map_err(|e| match e {
-            CreateTransactionError::SourceAccountNotFound { id } => (
+            CreateTransactionError::AccountNotFound { id } => (
                 StatusCode::NOT_FOUND,
-                format!("could not find source account {}", id),
-            ),
-            CreateTransactionError::DestinationAccountNotFound { id } => (
-                StatusCode::NOT_FOUND,
-                format!("could not find destination account {}", id),
+                format!("could not find account {}", id),
             ),
+            CreateTransactionError::NotEnoughLegs
+            | CreateTransactionError::Unbalanced { .. }
+            | CreateTransactionError::MissingExchangeRate { .. }
+            | CreateTransactionError::ExchangeRateMismatch { .. }
+            | CreateTransactionError::TooManyCurrencies { .. }
+            | CreateTransactionError::CategoryNotFound { .. }
+            | CreateTransactionError::Duplicate { .. }
+            | CreateTransactionError::InvalidInstallmentCount { .. }
+            | CreateTransactionError::OccurrencePosted { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
             CreateTransactionError::Unknown(cause) => {
                 tracing::error!("{:?}\n{}", cause, cause.backtrace());
 
@@ -18,6 +18,13 @@ pub async fn delete_account(
                 StatusCode::UNPROCESSABLE_ENTITY,
                 format!("account with id {} does not exist.", id),
             ),
+            DeleteAccountError::InUse { id } => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                format!(
+                    "account with id {} still has sub-accounts, transactions, rules or schedules.",
+                    id
+                ),
+            ),
             DeleteAccountError::Unknown(e) => {
                 tracing::error!("{:?}", e);
                 (
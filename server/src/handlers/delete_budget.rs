@@ -0,0 +1,25 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::budget::DeleteBudgetError;
+use crate::server::AppState;
+
+pub async fn delete_budget(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state.service.delete_budget(id).await.map_err(|e| match e {
+        DeleteBudgetError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+        DeleteBudgetError::Unknown(cause) => {
+            tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal server error".to_string(),
+            )
+        }
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,30 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::category::DeleteCategoryError;
+use crate::server::AppState;
+
+pub async fn delete_category(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .service
+        .delete_category(id)
+        .await
+        .map_err(|e| match e {
+            DeleteCategoryError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            DeleteCategoryError::InUse { .. } => (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+            DeleteCategoryError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
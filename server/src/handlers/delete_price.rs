@@ -0,0 +1,25 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::price::DeletePriceError;
+use crate::server::AppState;
+
+pub async fn delete_price(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state.service.delete_price(id).await.map_err(|e| match e {
+        DeletePriceError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+        DeletePriceError::Unknown(cause) => {
+            tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal server error".to_string(),
+            )
+        }
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,29 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::schedule::DeleteScheduleError;
+use crate::server::AppState;
+
+pub async fn delete_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .service
+        .delete_schedule(id)
+        .await
+        .map_err(|e| match e {
+            DeleteScheduleError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            DeleteScheduleError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,40 @@
+use axum::extract::{FromRequest, Request};
+use axum::response::{IntoResponse, Response};
+use axum::{Form, Json};
+use http::header::CONTENT_TYPE;
+use serde::de::DeserializeOwned;
+
+/// Extracts a request body either as JSON or as a URL-encoded form, depending on the request's
+/// `Content-Type` header.
+///
+/// Forms are enough for flat request bodies, while JSON is needed for nested ones, such as a
+/// transaction with many legs.
+pub struct JsonOrForm<T>(pub T);
+
+impl<S, T> FromRequest<S> for JsonOrForm<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let is_json = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("application/json"));
+
+        if is_json {
+            let Json(body) = Json::<T>::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            Ok(Self(body))
+        } else {
+            let Form(body) = Form::<T>::from_request(req, state)
+                .await
+                .map_err(IntoResponse::into_response)?;
+            Ok(Self(body))
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::{
+    models::account::{AccountTreeNode, ListAccountsError},
+    server::AppState,
+};
+
+pub async fn get_account_tree(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<AccountTreeNode>>, (StatusCode, &'static str)> {
+    let tree = state
+        .service
+        .get_account_tree()
+        .await
+        .map_err(|err| match err {
+            ListAccountsError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+
+    Ok(Json(tree))
+}
@@ -0,0 +1,75 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+use serde::Deserialize;
+
+use crate::handlers::create_transaction::parse_id;
+use crate::handlers::get_income_statement::report_period;
+use crate::models::currency::Currency;
+use crate::models::report::{BalanceHistory, GetBalanceHistoryError, Interval};
+use crate::server::AppState;
+
+#[derive(Deserialize, Default)]
+pub struct BalanceHistoryQuery {
+    /// The first day of the history. Defaults to the first day of the current year in the
+    /// server's timezone.
+    from: Option<NaiveDate>,
+    /// The day after the last day of the history. Defaults to tomorrow in the server's timezone.
+    to: Option<NaiveDate>,
+    /// One of `day`, `week` or `month` (the default)
+    interval: Option<Interval>,
+    /// Comma-separated ids of the accounts to include. Defaults to all of them.
+    account_id: Option<String>,
+    /// The currency or commodity to report balances in, like USD. Defaults to BRL.
+    currency: Option<Currency>,
+}
+
+pub async fn get_balance_history(
+    State(state): State<AppState>,
+    Query(query): Query<BalanceHistoryQuery>,
+) -> Result<Json<BalanceHistory>, (StatusCode, String)> {
+    let (from, to) = report_period(query.from, query.to, state.timezone);
+    let account_ids = query
+        .account_id
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .map(|raw| parse_id(raw, "account_id"))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let history = state
+        .service
+        .get_balance_history(
+            from,
+            to,
+            query.interval.unwrap_or_default(),
+            &account_ids,
+            query.currency.unwrap_or_default(),
+            state.timezone,
+        )
+        .await
+        .map_err(|e| match e {
+            GetBalanceHistoryError::AccountNotFound { .. } => {
+                (StatusCode::NOT_FOUND, e.to_string())
+            }
+            GetBalanceHistoryError::InvalidPeriod { .. }
+            | GetBalanceHistoryError::TooManyPeriods { .. }
+            | GetBalanceHistoryError::Conversion(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            GetBalanceHistoryError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(history))
+}
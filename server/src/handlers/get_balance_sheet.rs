@@ -0,0 +1,34 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+
+use crate::handlers::get_income_statement::ReportQuery;
+use crate::models::report::{BalanceSheet, GetReportError};
+use crate::server::AppState;
+
+pub async fn get_balance_sheet(
+    State(state): State<AppState>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Json<BalanceSheet>, (StatusCode, String)> {
+    let (from, to) = query.period(state.timezone);
+
+    let report = state
+        .service
+        .get_balance_sheet(from, to, query.currency(), state.timezone)
+        .await
+        .map_err(|e| match e {
+            GetReportError::InvalidPeriod { .. } | GetReportError::Conversion(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            GetReportError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(report))
+}
@@ -0,0 +1,44 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::models::budget::{BudgetLine, GetBudgetReportError, Month};
+use crate::server::AppState;
+
+#[derive(Deserialize, Default)]
+pub struct BudgetReportQuery {
+    /// A year and a month, like `2026-03`. Defaults to the current month in the server's
+    /// timezone.
+    month: Option<String>,
+}
+
+pub async fn get_budget_report(
+    State(state): State<AppState>,
+    Query(query): Query<BudgetReportQuery>,
+) -> Result<Json<Vec<BudgetLine>>, (StatusCode, String)> {
+    let month = match query.month {
+        Some(raw) => raw
+            .parse::<Month>()
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?,
+        None => Month::of(Utc::now().with_timezone(&state.timezone).date_naive()),
+    };
+
+    let lines = state
+        .service
+        .get_budget_report(month, state.timezone)
+        .await
+        .map_err(|e| match e {
+            GetBudgetReportError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(lines))
+}
@@ -0,0 +1,76 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::models::budget::Month;
+use crate::models::credit_card::{CardStatements, GetStatementsError, Statement};
+use crate::server::AppState;
+
+#[derive(Deserialize, Default)]
+pub struct CardStatementsQuery {
+    /// The first statement, like `2026-03`. Defaults to five months before the open one.
+    from: Option<String>,
+    /// The last statement. Defaults to the latest one with postings, or the open one if that
+    /// is later.
+    to: Option<String>,
+}
+
+/// The statements of a credit card account and its available credit
+pub async fn get_card_statements(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<CardStatementsQuery>,
+) -> Result<Json<CardStatements>, (StatusCode, String)> {
+    let from = query.from.as_deref().map(parse_month).transpose()?;
+    let to = query.to.as_deref().map(parse_month).transpose()?;
+
+    let statements = state
+        .service
+        .get_card_statements(id, from, to, state.timezone)
+        .await
+        .map_err(statements_error)?;
+
+    Ok(Json(statements))
+}
+
+/// The statement of a credit card account that closes in the given month
+pub async fn get_card_statement(
+    State(state): State<AppState>,
+    Path((id, month)): Path<(Uuid, String)>,
+) -> Result<Json<Statement>, (StatusCode, String)> {
+    let month = parse_month(&month)?;
+
+    let statement = state
+        .service
+        .get_card_statement(id, month, state.timezone)
+        .await
+        .map_err(statements_error)?;
+
+    Ok(Json(statement))
+}
+
+fn parse_month(raw: &str) -> Result<Month, (StatusCode, String)> {
+    raw.parse::<Month>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+}
+
+fn statements_error(e: GetStatementsError) -> (StatusCode, String) {
+    match e {
+        GetStatementsError::AccountNotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+        GetStatementsError::NotACreditCard { .. }
+        | GetStatementsError::InvalidRange { .. }
+        | GetStatementsError::TooManyStatements { .. } => {
+            (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+        }
+        GetStatementsError::Unknown(cause) => {
+            tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal server error".to_string(),
+            )
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::handlers::create_transaction::parse_id;
+use crate::models::forecast::{CashFlowForecast, GetCashFlowForecastError};
+use crate::server::AppState;
+
+#[derive(Deserialize, Default)]
+pub struct CashFlowForecastQuery {
+    /// How many days after today to project. Defaults to 30.
+    days: Option<u32>,
+    /// Balances below this are flagged. Defaults to zero.
+    threshold: Option<Decimal>,
+    /// Comma-separated ids of the asset accounts to include. Defaults to all of them.
+    account_id: Option<String>,
+}
+
+pub async fn get_cash_flow_forecast(
+    State(state): State<AppState>,
+    Query(query): Query<CashFlowForecastQuery>,
+) -> Result<Json<CashFlowForecast>, (StatusCode, String)> {
+    let account_ids = query
+        .account_id
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .map(|raw| parse_id(raw, "account_id"))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let forecast = state
+        .service
+        .get_cash_flow_forecast(
+            query.days.unwrap_or(30),
+            query.threshold.unwrap_or_default(),
+            &account_ids,
+            state.timezone,
+        )
+        .await
+        .map_err(|e| match e {
+            GetCashFlowForecastError::AccountNotFound { .. } => {
+                (StatusCode::NOT_FOUND, e.to_string())
+            }
+            GetCashFlowForecastError::InvalidDays { .. }
+            | GetCashFlowForecastError::NotAnAssetAccount { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            GetCashFlowForecastError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(forecast))
+}
@@ -0,0 +1,30 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::category::{Category, GetCategoryError};
+use crate::server::AppState;
+
+pub async fn get_category(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Category>, (StatusCode, String)> {
+    let category = state
+        .service
+        .get_category_by_id(id)
+        .await
+        .map_err(|e| match e {
+            GetCategoryError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            GetCategoryError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(category))
+}
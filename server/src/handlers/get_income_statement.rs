@@ -0,0 +1,75 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use chrono::{Datelike, Days, NaiveDate, Utc};
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+use crate::models::currency::Currency;
+use crate::models::report::{GetReportError, IncomeStatement};
+use crate::server::AppState;
+
+#[derive(Deserialize, Default)]
+pub struct ReportQuery {
+    /// The first day of the report, like `2026-01-01`. Defaults to the first day of the current
+    /// year in the server's timezone.
+    from: Option<NaiveDate>,
+    /// The day after the last day of the report. Defaults to tomorrow in the server's timezone,
+    /// so that the report includes today.
+    to: Option<NaiveDate>,
+    /// The currency or commodity to report amounts in, like USD. Defaults to BRL.
+    currency: Option<Currency>,
+}
+
+impl ReportQuery {
+    /// Resolve the period of the report, filling in the defaults for the missing dates.
+    pub(crate) fn period(&self, timezone: Tz) -> (NaiveDate, NaiveDate) {
+        report_period(self.from, self.to, timezone)
+    }
+
+    /// The currency to report amounts in
+    pub(crate) fn currency(&self) -> Currency {
+        self.currency.unwrap_or_default()
+    }
+}
+
+/// Fill in the missing dates of a report period: from the first day of the current year in
+/// `timezone` to tomorrow.
+pub(crate) fn report_period(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    timezone: Tz,
+) -> (NaiveDate, NaiveDate) {
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+    let from = from.unwrap_or_else(|| today.with_ordinal(1).expect("every year has a first day"));
+    let to = to.unwrap_or_else(|| today + Days::new(1));
+
+    (from, to)
+}
+
+pub async fn get_income_statement(
+    State(state): State<AppState>,
+    Query(query): Query<ReportQuery>,
+) -> Result<Json<IncomeStatement>, (StatusCode, String)> {
+    let (from, to) = query.period(state.timezone);
+
+    let report = state
+        .service
+        .get_income_statement(from, to, query.currency(), state.timezone)
+        .await
+        .map_err(|e| match e {
+            GetReportError::InvalidPeriod { .. } | GetReportError::Conversion(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            GetReportError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(report))
+}
@@ -0,0 +1,33 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::installment::{GetInstallmentCommitmentsError, InstallmentCommitments};
+use crate::server::AppState;
+
+/// What an account, like a credit card, is still committed to pay in installments
+pub async fn get_installment_commitments(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<InstallmentCommitments>, (StatusCode, String)> {
+    let commitments = state
+        .service
+        .get_installment_commitments(id)
+        .await
+        .map_err(|e| match e {
+            GetInstallmentCommitmentsError::AccountNotFound { .. } => {
+                (StatusCode::NOT_FOUND, e.to_string())
+            }
+            GetInstallmentCommitmentsError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(commitments))
+}
@@ -0,0 +1,26 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::price::{GetPriceError, Price};
+use crate::server::AppState;
+
+pub async fn get_price(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Price>, (StatusCode, String)> {
+    let price = state.service.get_price(id).await.map_err(|e| match e {
+        GetPriceError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+        GetPriceError::Unknown(cause) => {
+            tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal server error".to_string(),
+            )
+        }
+    })?;
+
+    Ok(Json(price))
+}
@@ -0,0 +1,26 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::rule::{GetRuleError, Rule};
+use crate::server::AppState;
+
+pub async fn get_rule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Rule>, (StatusCode, String)> {
+    let rule = state.service.get_rule(id).await.map_err(|e| match e {
+        GetRuleError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+        GetRuleError::Unknown(cause) => {
+            tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal server error".to_string(),
+            )
+        }
+    })?;
+
+    Ok(Json(rule))
+}
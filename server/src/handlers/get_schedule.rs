@@ -0,0 +1,26 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::schedule::{GetScheduleError, Schedule};
+use crate::server::AppState;
+
+pub async fn get_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Schedule>, (StatusCode, String)> {
+    let schedule = state.service.get_schedule(id).await.map_err(|e| match e {
+        GetScheduleError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+        GetScheduleError::Unknown(cause) => {
+            tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal server error".to_string(),
+            )
+        }
+    })?;
+
+    Ok(Json(schedule))
+}
@@ -0,0 +1,55 @@
+use axum::Json;
+use axum::extract::{Multipart, State};
+use axum::http::StatusCode;
+
+use crate::models::price::{ImportPricesError, PriceImportReport};
+use crate::server::AppState;
+
+/// Import a CSV file of prices uploaded as `multipart/form-data` in its `file` field, with the
+/// columns `commodity`, `currency`, `date` and `rate`.
+///
+/// Responds with how many prices were created or updated, and the rows that failed.
+pub async fn import_prices(
+    State(state): State<AppState>,
+    mut multipart: Multipart,
+) -> Result<Json<PriceImportReport>, (StatusCode, String)> {
+    let bad_request = |message: String| (StatusCode::BAD_REQUEST, message);
+
+    let mut file = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| bad_request(e.to_string()))?
+    {
+        let name = field.name().unwrap_or_default().to_string();
+        if name != "file" {
+            return Err(bad_request(format!("unexpected field \"{name}\"")));
+        }
+        let contents = field
+            .bytes()
+            .await
+            .map_err(|e| bad_request(e.to_string()))?;
+        file = Some(contents.to_vec());
+    }
+    let contents = file.ok_or_else(|| bad_request("missing file".to_string()))?;
+
+    let report = state
+        .service
+        .import_prices(&contents)
+        .await
+        .map_err(|e| match e {
+            ImportPricesError::Malformed(_) | ImportPricesError::MissingColumn { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            ImportPricesError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(report))
+}
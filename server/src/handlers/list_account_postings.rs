@@ -0,0 +1,75 @@
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::models::transaction::{
+    AccountPosting, ListAccountPostingsError, SortDirection, TransactionCursor,
+};
+use crate::server::AppState;
+use crate::service::{Page, PaginationParameters};
+
+#[derive(Deserialize, Default)]
+pub struct ListAccountPostingsQuery {
+    /// The `page` is 1-indexed
+    page: Option<u32>,
+    /// How many items per page
+    per_page: Option<u32>,
+    /// The `next_cursor` of the previous page, to list the postings that follow it instead of
+    /// using `page`
+    cursor: Option<String>,
+    /// Either `asc` or `desc` (the default), by posting date
+    direction: Option<SortDirection>,
+}
+
+pub async fn list_account_postings(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<ListAccountPostingsQuery>,
+) -> Result<Json<Page<AccountPosting>>, (StatusCode, String)> {
+    let after = query
+        .cursor
+        .as_deref()
+        .map(str::parse::<TransactionCursor>)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let pagination_parameters =
+        if query.page.is_none() && query.per_page.is_none() && after.is_none() {
+            None
+        } else {
+            let limit = query.per_page.unwrap_or(20).min(100) as i64;
+            let offset = match after {
+                Some(_) => 0,
+                None => query.page.unwrap_or(1).saturating_sub(1) as i64 * limit,
+            };
+
+            Some(PaginationParameters { limit, offset })
+        };
+
+    let postings = state
+        .service
+        .list_account_postings(
+            id,
+            query.direction.unwrap_or_default(),
+            pagination_parameters,
+            after,
+        )
+        .await
+        .map_err(|e| match e {
+            ListAccountPostingsError::AccountNotFound { .. } => {
+                (StatusCode::NOT_FOUND, e.to_string())
+            }
+            ListAccountPostingsError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(postings))
+}
@@ -1,22 +1,55 @@
-use axum::{Json, extract::State, http::StatusCode};
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use serde::Deserialize;
 
 use crate::{
-    models::account::{Account, ListAccountsError},
+    models::account::{Account, AccountName, AccountType, ListAccountsError},
     server::AppState,
+    service::Page,
 };
 
+#[derive(Deserialize, Default)]
+pub struct ListAccountsQuery {
+    /// Only list accounts of this type
+    #[serde(rename = "type")]
+    account_type: Option<AccountType>,
+    /// How many accounts per page. Without it or a `cursor`, all accounts are listed.
+    per_page: Option<u32>,
+    /// The `next_cursor` of the previous page
+    cursor: Option<String>,
+}
+
 pub async fn list_accounts(
     State(state): State<AppState>,
-) -> Result<Json<Vec<Account>>, (StatusCode, &'static str)> {
+    Query(query): Query<ListAccountsQuery>,
+) -> Result<Json<Page<Account>>, (StatusCode, String)> {
+    let after = query
+        .cursor
+        .as_deref()
+        .map(AccountName::new)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let limit = if query.per_page.is_none() && after.is_none() {
+        None
+    } else {
+        Some(query.per_page.unwrap_or(20).min(100) as i64)
+    };
+
     let accounts = state
         .service
-        .list_accounts()
+        .list_accounts(query.account_type, after.as_ref(), limit)
         .await
         .map_err(|err| match err {
             ListAccountsError::Unknown(cause) => {
                 tracing::error!("{:?}\n{}", cause, cause.backtrace());
 
-                (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
             }
         })?;
 
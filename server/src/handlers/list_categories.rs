@@ -0,0 +1,22 @@
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::models::category::{Category, ListCategoriesError};
+use crate::server::AppState;
+
+pub async fn list_categories(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Category>>, (StatusCode, &'static str)> {
+    let categories = state
+        .service
+        .list_categories()
+        .await
+        .map_err(|err| match err {
+            ListCategoriesError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+
+    Ok(Json(categories))
+}
@@ -0,0 +1,22 @@
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::models::import_batch::{ImportBatch, ListImportBatchesError};
+use crate::server::AppState;
+
+pub async fn list_import_batches(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ImportBatch>>, (StatusCode, &'static str)> {
+    let batches = state
+        .service
+        .list_import_batches()
+        .await
+        .map_err(|err| match err {
+            ListImportBatchesError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+
+    Ok(Json(batches))
+}
@@ -0,0 +1,18 @@
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::models::payee::{ListPayeesError, Payee};
+use crate::server::AppState;
+
+pub async fn list_payees(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Payee>>, (StatusCode, &'static str)> {
+    let payees = state.service.list_payees().await.map_err(|err| match err {
+        ListPayeesError::Unknown(cause) => {
+            tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+            (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        }
+    })?;
+
+    Ok(Json(payees))
+}
@@ -0,0 +1,36 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+use crate::models::currency::Currency;
+use crate::models::price::{ListPricesError, Price};
+use crate::server::AppState;
+
+#[derive(Deserialize, Default)]
+pub struct ListPricesQuery {
+    /// Only list the prices of this commodity
+    commodity: Option<Currency>,
+    /// Only list the prices in this currency
+    currency: Option<Currency>,
+}
+
+/// List the prices by commodity, currency and date
+pub async fn list_prices(
+    State(state): State<AppState>,
+    Query(query): Query<ListPricesQuery>,
+) -> Result<Json<Vec<Price>>, (StatusCode, &'static str)> {
+    let prices = state
+        .service
+        .list_prices(query.commodity, query.currency)
+        .await
+        .map_err(|err| match err {
+            ListPricesError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+
+    Ok(Json(prices))
+}
@@ -0,0 +1,19 @@
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::models::rule::{ListRulesError, Rule};
+use crate::server::AppState;
+
+/// List the rules in the order they are tried
+pub async fn list_rules(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Rule>>, (StatusCode, &'static str)> {
+    let rules = state.service.list_rules().await.map_err(|err| match err {
+        ListRulesError::Unknown(cause) => {
+            tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+            (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        }
+    })?;
+
+    Ok(Json(rules))
+}
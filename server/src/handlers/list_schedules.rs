@@ -0,0 +1,23 @@
+use axum::{Json, extract::State, http::StatusCode};
+
+use crate::models::schedule::{ListSchedulesError, Schedule};
+use crate::server::AppState;
+
+/// List the schedules by title
+pub async fn list_schedules(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<Schedule>>, (StatusCode, &'static str)> {
+    let schedules = state
+        .service
+        .list_schedules()
+        .await
+        .map_err(|err| match err {
+            ListSchedulesError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+            }
+        })?;
+
+    Ok(Json(schedules))
+}
@@ -1,9 +1,15 @@
 use axum::extract::Query;
 use axum::http::StatusCode;
 use axum::{Json, extract::State};
+use rust_decimal::Decimal;
 use serde::Deserialize;
 
-use crate::service::PaginationParameters;
+use crate::handlers::create_transaction::{parse_category, parse_id};
+use crate::models::transaction::{
+    SortDirection, TransactionCursor, TransactionFilters, TransactionSort, TransactionSortField,
+    parse_posting_date,
+};
+use crate::service::{Page, PaginationParameters};
 use crate::{models::transaction::Transaction, server::AppState};
 
 #[derive(Deserialize, Default)]
@@ -12,27 +18,91 @@ pub struct ListTransactionsQuery {
     page: Option<u32>,
     /// How many items per page
     per_page: Option<u32>,
+    /// The `next_cursor` of the previous page, to list the transactions that follow it instead
+    /// of using `page`. Only supported when sorting by posting date.
+    cursor: Option<String>,
+    /// Only transactions with a leg on this account, on either side
+    account_id: Option<String>,
+    /// Only transactions posted at or after this date
+    from: Option<String>,
+    /// Only transactions posted strictly before this date
+    to: Option<String>,
+    min_amount: Option<Decimal>,
+    max_amount: Option<Decimal>,
+    /// Only transactions in this category or its subcategories
+    category: Option<String>,
+    /// Only transactions whose title contains this text, ignoring case
+    title: Option<String>,
+    /// One of `posting_date` (the default), `amount` or `title`
+    sort: Option<TransactionSortField>,
+    /// Either `asc` or `desc` (the default)
+    direction: Option<SortDirection>,
 }
 
 pub async fn list_transactions(
-    State(AppState { service }): State<AppState>,
-    Query(pagination): Query<ListTransactionsQuery>,
-) -> Result<Json<Vec<Transaction>>, (StatusCode, String)> {
-    let pagination_parameters = if let ListTransactionsQuery {
-        page: None,
-        per_page: None,
-    } = pagination
-    {
-        None
-    } else {
-        let limit = pagination.per_page.unwrap_or(20).min(100) as i64;
-        let offset = pagination.page.unwrap_or(1).saturating_sub(1) as i64 * limit;
-
-        Some(PaginationParameters { limit, offset })
+    State(AppState {
+        service, timezone, ..
+    }): State<AppState>,
+    Query(query): Query<ListTransactionsQuery>,
+) -> Result<Json<Page<Transaction>>, (StatusCode, String)> {
+    let after = query
+        .cursor
+        .as_deref()
+        .map(str::parse::<TransactionCursor>)
+        .transpose()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let sort = TransactionSort {
+        field: query.sort.unwrap_or_default(),
+        direction: query.direction.unwrap_or_default(),
+    };
+    if after.is_some() && sort.field != TransactionSortField::PostingDate {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "cursors are only supported when sorting by posting_date".to_string(),
+        ));
+    }
+
+    let pagination_parameters =
+        if query.page.is_none() && query.per_page.is_none() && after.is_none() {
+            None
+        } else {
+            let limit = query.per_page.unwrap_or(20).min(100) as i64;
+            let offset = match after {
+                Some(_) => 0,
+                None => query.page.unwrap_or(1).saturating_sub(1) as i64 * limit,
+            };
+
+            Some(PaginationParameters { limit, offset })
+        };
+
+    let parse_date = |raw: Option<&str>| {
+        raw.map(|raw| parse_posting_date(raw, timezone))
+            .transpose()
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))
+    };
+    let filters = TransactionFilters {
+        account_id: query
+            .account_id
+            .as_deref()
+            .map(|raw| parse_id(raw, "account_id"))
+            .transpose()
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?,
+        from: parse_date(query.from.as_deref())?,
+        to: parse_date(query.to.as_deref())?,
+        min_amount: query.min_amount,
+        max_amount: query.max_amount,
+        category: query
+            .category
+            .as_deref()
+            .map(parse_category)
+            .transpose()
+            .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?
+            .flatten(),
+        title: query.title.filter(|title| !title.is_empty()),
     };
 
     let transactions = service
-        .list_transactions(pagination_parameters)
+        .list_transactions(&filters, sort, pagination_parameters, after)
         .await
         .map_err(|e| {
             tracing::error!(error = ?e, "an error occurred when listing all transactions");
@@ -0,0 +1,47 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use chrono::{Days, NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::models::schedule::{ListUpcomingOccurrencesError, UpcomingOccurrence};
+use crate::server::AppState;
+
+#[derive(Deserialize, Default)]
+pub struct UpcomingOccurrencesQuery {
+    /// The last day to list occurrences up to, included. Defaults to 30 days from today in the
+    /// server's timezone.
+    until: Option<NaiveDate>,
+}
+
+/// List the occurrences of the schedules that are not posted yet, skipped ones included
+pub async fn list_upcoming_occurrences(
+    State(state): State<AppState>,
+    Query(query): Query<UpcomingOccurrencesQuery>,
+) -> Result<Json<Vec<UpcomingOccurrence>>, (StatusCode, String)> {
+    let until = query
+        .until
+        .unwrap_or_else(|| Utc::now().with_timezone(&state.timezone).date_naive() + Days::new(30));
+
+    let occurrences = state
+        .service
+        .list_upcoming_occurrences(until, state.timezone)
+        .await
+        .map_err(|err| match err {
+            ListUpcomingOccurrencesError::TooFarAhead { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, err.to_string())
+            }
+            ListUpcomingOccurrencesError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(occurrences))
+}
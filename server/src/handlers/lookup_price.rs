@@ -0,0 +1,45 @@
+use axum::Json;
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use chrono::{NaiveDate, Utc};
+use serde::Deserialize;
+
+use crate::models::currency::Currency;
+use crate::models::price::{LookupPriceError, PriceQuote};
+use crate::server::AppState;
+
+#[derive(Deserialize)]
+pub struct LookupPriceQuery {
+    commodity: Currency,
+    currency: Currency,
+    /// The day to find the rate on. Defaults to today in the server's timezone.
+    date: Option<NaiveDate>,
+}
+
+/// Find the rate of a commodity in a currency on a day, from the latest price on or before it
+pub async fn lookup_price(
+    State(state): State<AppState>,
+    Query(query): Query<LookupPriceQuery>,
+) -> Result<Json<PriceQuote>, (StatusCode, String)> {
+    let date = query
+        .date
+        .unwrap_or_else(|| Utc::now().with_timezone(&state.timezone).date_naive());
+
+    let quote = state
+        .service
+        .lookup_price(query.commodity, query.currency, date)
+        .await
+        .map_err(|e| match e {
+            LookupPriceError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            LookupPriceError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(quote))
+}
@@ -37,6 +37,9 @@ pub async fn rename_account(
                 StatusCode::UNPROCESSABLE_ENTITY,
                 format!("account with name {} already exists", name),
             ),
+            e @ UpdateAccountError::MovedUnderItself { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
             UpdateAccountError::Unknown(cause) => {
                 tracing::error!("{:?}\n{}", cause, cause.backtrace());
 
@@ -0,0 +1,31 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::models::import_batch::RollbackImportBatchError;
+use crate::server::AppState;
+
+/// Delete the transactions of an import batch, reverting their effect on account balances, and
+/// the batch itself
+pub async fn rollback_import_batch(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, (StatusCode, String)> {
+    state
+        .service
+        .rollback_import_batch(id)
+        .await
+        .map_err(|e| match e {
+            RollbackImportBatchError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            RollbackImportBatchError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
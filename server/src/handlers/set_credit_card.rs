@@ -0,0 +1,52 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::credit_card::{CreditCard, SetCreditCardError, SetCreditCardRequest};
+use crate::server::AppState;
+
+#[derive(Deserialize, Debug)]
+pub struct SetCreditCardRequestBody {
+    closing_day: u32,
+    due_day: u32,
+    credit_limit: Decimal,
+    /// The asset account statements are paid from
+    payment_account_id: Option<Uuid>,
+}
+
+/// Make a liability account a credit card, or change its billing cycle and limit
+pub async fn set_credit_card(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    JsonOrForm(body): JsonOrForm<SetCreditCardRequestBody>,
+) -> Result<Json<CreditCard>, (StatusCode, String)> {
+    let req = SetCreditCardRequest::new(body.closing_day, body.due_day, body.credit_limit)
+        .with_payment_account(body.payment_account_id);
+    let card = state
+        .service
+        .set_credit_card(id, &req)
+        .await
+        .map_err(|e| match e {
+            SetCreditCardError::AccountNotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            SetCreditCardError::NotALiabilityAccount { .. }
+            | SetCreditCardError::NotAnAssetAccount { .. }
+            | SetCreditCardError::InvalidDay { .. }
+            | SetCreditCardError::NegativeLimit => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            SetCreditCardError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(card))
+}
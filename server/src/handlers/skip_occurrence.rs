@@ -0,0 +1,45 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::schedule::{SkipOccurrenceError, UpcomingOccurrence};
+use crate::server::AppState;
+
+#[derive(Deserialize, Debug)]
+pub struct SkipOccurrenceRequestBody {
+    /// The day of the occurrence, like `2026-03-05`
+    date: NaiveDate,
+}
+
+/// Skip one occurrence of a schedule, so that it is never posted
+pub async fn skip_occurrence(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    JsonOrForm(body): JsonOrForm<SkipOccurrenceRequestBody>,
+) -> Result<Json<UpcomingOccurrence>, (StatusCode, String)> {
+    let occurrence = state
+        .service
+        .skip_occurrence(id, body.date)
+        .await
+        .map_err(|e| match e {
+            SkipOccurrenceError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            SkipOccurrenceError::NotAnOccurrence { .. }
+            | SkipOccurrenceError::AlreadyPosted { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            SkipOccurrenceError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(occurrence))
+}
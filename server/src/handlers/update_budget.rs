@@ -0,0 +1,42 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::budget::{Budget, UpdateBudgetError, UpdateBudgetRequest};
+use crate::server::AppState;
+
+/// The body of a request to update a budget. Missing fields are left as they are.
+#[derive(Deserialize, Debug)]
+pub struct UpdateBudgetRequestBody {
+    amount: Option<Decimal>,
+    rollover: Option<bool>,
+}
+
+pub async fn update_budget(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    JsonOrForm(body): JsonOrForm<UpdateBudgetRequestBody>,
+) -> Result<Json<Budget>, (StatusCode, String)> {
+    let budget = state
+        .service
+        .update_budget(id, &UpdateBudgetRequest::new(body.amount, body.rollover))
+        .await
+        .map_err(|e| match e {
+            UpdateBudgetError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            UpdateBudgetError::NegativeAmount => (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()),
+            UpdateBudgetError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(budget))
+}
@@ -0,0 +1,41 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::handlers::create_category::CategoryRequestBody;
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::category::{Category, CategoryName, UpdateCategoryError};
+use crate::server::AppState;
+
+/// Rename a category. Its subcategories and transactions follow the new name.
+pub async fn update_category(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    JsonOrForm(body): JsonOrForm<CategoryRequestBody>,
+) -> Result<Json<Category>, (StatusCode, String)> {
+    let name =
+        CategoryName::new(&body.name).map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let category = state
+        .service
+        .rename_category(id, &name)
+        .await
+        .map_err(|e| match e {
+            UpdateCategoryError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            UpdateCategoryError::Duplicate { .. }
+            | UpdateCategoryError::MovedUnderItself { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            UpdateCategoryError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(category))
+}
@@ -0,0 +1,37 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::handlers::create_price::PriceRequestBody;
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::price::{Price, UpdatePriceError};
+use crate::server::AppState;
+
+/// Replace all the fields of a price, which takes the same body as creating one
+pub async fn update_price(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    JsonOrForm(body): JsonOrForm<PriceRequestBody>,
+) -> Result<Json<Price>, (StatusCode, String)> {
+    let price = state
+        .service
+        .update_price(id, &body.into())
+        .await
+        .map_err(|e| match e {
+            UpdatePriceError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            UpdatePriceError::Invalid(_) | UpdatePriceError::Duplicate { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            UpdatePriceError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(price))
+}
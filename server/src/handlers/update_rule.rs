@@ -0,0 +1,35 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::handlers::create_rule::{RuleRequestBody, invalid_rule};
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::rule::{Rule, UpdateRuleError};
+use crate::server::AppState;
+
+/// Replace all fields of a rule, which takes the same body as creating one
+pub async fn update_rule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    JsonOrForm(body): JsonOrForm<RuleRequestBody>,
+) -> Result<Json<Rule>, (StatusCode, String)> {
+    let rule = state
+        .service
+        .update_rule(id, &body.into())
+        .await
+        .map_err(|e| match e {
+            UpdateRuleError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            UpdateRuleError::Invalid(e) => invalid_rule(e),
+            UpdateRuleError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(rule))
+}
@@ -0,0 +1,36 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use uuid::Uuid;
+
+use crate::handlers::create_schedule::{ScheduleRequestBody, invalid_schedule};
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::schedule::{Schedule, UpdateScheduleError};
+use crate::server::AppState;
+
+/// Edit the series of a schedule by replacing all of its fields, which takes the same body as
+/// creating one. Occurrences already posted are left as they are.
+pub async fn update_schedule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    JsonOrForm(body): JsonOrForm<ScheduleRequestBody>,
+) -> Result<Json<Schedule>, (StatusCode, String)> {
+    let schedule = state
+        .service
+        .update_schedule(id, &body.into())
+        .await
+        .map_err(|e| match e {
+            UpdateScheduleError::NotFound { .. } => (StatusCode::NOT_FOUND, e.to_string()),
+            UpdateScheduleError::Invalid(e) => invalid_schedule(e),
+            UpdateScheduleError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(schedule))
+}
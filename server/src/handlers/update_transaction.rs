@@ -0,0 +1,146 @@
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::handlers::create_transaction::{
+    ExchangeRateBody, LegBody, TransactionRequestBodyError, parse_category, parse_id,
+    positive_amount,
+};
+use crate::handlers::extractors::JsonOrForm;
+use crate::models::transaction::{
+    LegsUpdate, Transaction, TransactionTitle, UpdateTransactionError, UpdateTransactionRequest,
+    parse_posting_date,
+};
+use crate::server::AppState;
+
+/// The body of a request to update a transaction. Missing fields are left as they are.
+///
+/// The legs are either replaced through `legs`, or, for transactions that move money between
+/// two accounts, changed through `amount`, `source_account_id` and `destination_account_id`.
+/// An empty `category` removes the transaction's category.
+///
+/// `exchange_rate` replaces the rate the legs are converted at. Given without new legs, the
+/// amount of a transfer is converted again at the new rate.
+#[derive(Deserialize, Debug)]
+pub struct UpdateTransactionRequestBody {
+    title: Option<String>,
+    legs: Option<Vec<LegBody>>,
+    amount: Option<Decimal>,
+    source_account_id: Option<String>,
+    destination_account_id: Option<String>,
+    category: Option<String>,
+    posting_date: Option<String>,
+    exchange_rate: Option<ExchangeRateBody>,
+}
+
+impl UpdateTransactionRequestBody {
+    fn into_domain_model(
+        self,
+        timezone: Tz,
+    ) -> Result<UpdateTransactionRequest, TransactionRequestBodyError> {
+        let title = self
+            .title
+            .as_deref()
+            .map(TransactionTitle::new)
+            .transpose()?;
+        let category = self.category.as_deref().map(parse_category).transpose()?;
+        let posting_date = self
+            .posting_date
+            .as_deref()
+            .map(|raw| parse_posting_date(raw, timezone))
+            .transpose()?;
+        let exchange_rate = self
+            .exchange_rate
+            .map(ExchangeRateBody::into_domain_model)
+            .transpose()?;
+
+        let legs = match self.legs {
+            Some(legs) => Some(LegsUpdate::Replace(
+                legs.into_iter()
+                    .map(LegBody::into_domain_model)
+                    .collect::<Result<Vec<_>, _>>()?,
+            )),
+            None if self.amount.is_some()
+                || self.source_account_id.is_some()
+                || self.destination_account_id.is_some()
+                || exchange_rate.is_some() =>
+            {
+                Some(LegsUpdate::Transfer {
+                    amount: self.amount.map(positive_amount).transpose()?,
+                    source_account_id: self
+                        .source_account_id
+                        .as_deref()
+                        .map(|id| parse_id(id, "source_account_id"))
+                        .transpose()?,
+                    destination_account_id: self
+                        .destination_account_id
+                        .as_deref()
+                        .map(|id| parse_id(id, "destination_account_id"))
+                        .transpose()?,
+                })
+            }
+            None => None,
+        };
+
+        let req = UpdateTransactionRequest::new(title, legs, category, posting_date);
+
+        Ok(match exchange_rate {
+            Some(exchange_rate) => req.with_exchange_rate(exchange_rate),
+            None => req,
+        })
+    }
+}
+
+pub async fn update_transaction(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    JsonOrForm(body): JsonOrForm<UpdateTransactionRequestBody>,
+) -> Result<Json<Transaction>, (StatusCode, String)> {
+    let req = body
+        .into_domain_model(state.timezone)
+        .map_err(|e| match e {
+            TransactionRequestBodyError::NonPositiveAmount { .. }
+            | TransactionRequestBodyError::InvalidExchangeRate(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            _ => (StatusCode::BAD_REQUEST, e.to_string()),
+        })?;
+
+    let transaction = state
+        .service
+        .update_transaction(id, &req)
+        .await
+        .map_err(|e| match e {
+            UpdateTransactionError::TransactionNotFound { id } => (
+                StatusCode::NOT_FOUND,
+                format!("transaction with id {} does not exist", id),
+            ),
+            UpdateTransactionError::AccountNotFound { id } => (
+                StatusCode::NOT_FOUND,
+                format!("could not find account {}", id),
+            ),
+            UpdateTransactionError::NotEnoughLegs
+            | UpdateTransactionError::Unbalanced { .. }
+            | UpdateTransactionError::MissingExchangeRate { .. }
+            | UpdateTransactionError::ExchangeRateMismatch { .. }
+            | UpdateTransactionError::TooManyCurrencies { .. }
+            | UpdateTransactionError::CategoryNotFound { .. }
+            | UpdateTransactionError::NotATransfer { .. } => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string())
+            }
+            UpdateTransactionError::Unknown(cause) => {
+                tracing::error!("{:?}\n{}", cause, cause.backtrace());
+
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal server error".to_string(),
+                )
+            }
+        })?;
+
+    Ok(Json(transaction))
+}
@@ -0,0 +1,303 @@
+//! Parsers for the files banks export, turning them into transactions
+
+pub mod csv;
+pub mod ofx;
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::import::csv::CsvProfile;
+use crate::models::account::AccountType;
+use crate::models::category::{CategoryName, CategoryNameEmptyError};
+use crate::models::import_batch::{ImportError, ImportFormat};
+use crate::models::transaction::{
+    CreateTransactionRequest, Leg, TransactionTitle, TransactionTitleEmptyError, local_to_utc,
+};
+
+/// A transaction read from an imported file, whatever its format
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportRow {
+    /// The line of the file the transaction is on, for formats that have lines
+    line: Option<u64>,
+    posting_date: DateTime<Utc>,
+    title: String,
+    /// Positive amounts are money coming into the account, negative ones are money leaving it
+    amount: Decimal,
+    category: Option<String>,
+    fingerprint: String,
+}
+
+impl ImportRow {
+    pub fn line(&self) -> Option<u64> {
+        self.line
+    }
+
+    pub fn posting_date(&self) -> DateTime<Utc> {
+        self.posting_date
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// The row's category, if it has one and it is a valid category name
+    pub fn category_name(&self) -> Result<Option<CategoryName>, CategoryNameEmptyError> {
+        self.category.as_deref().map(CategoryName::new).transpose()
+    }
+
+    /// The type of the account on the other side of the transaction: an expense account for
+    /// money leaving the imported account, and an income account otherwise
+    pub fn counterpart_type(&self) -> AccountType {
+        if self.amount.is_sign_negative() {
+            AccountType::Expense
+        } else {
+            AccountType::Income
+        }
+    }
+
+    /// Turn the row into a [CreateTransactionRequest] between the imported account and a
+    /// `counterpart`, which receives the opposite amount, carrying the row's fingerprint.
+    pub fn request(
+        &self,
+        account_id: Uuid,
+        counterpart_id: Uuid,
+        category: Option<CategoryName>,
+    ) -> Result<CreateTransactionRequest, TransactionTitleEmptyError> {
+        let request = CreateTransactionRequest::new(
+            TransactionTitle::new(&self.title)?,
+            vec![
+                Leg::new(account_id, self.amount),
+                Leg::new(counterpart_id, -self.amount),
+            ],
+            category,
+            Some(self.posting_date),
+        );
+
+        Ok(request.with_import_fingerprint(&self.fingerprint))
+    }
+}
+
+/// A row of an imported file that could not be read
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("{error}")]
+pub struct InvalidRow {
+    pub line: Option<u64>,
+    pub error: String,
+}
+
+/// Read the transactions of a file imported into the account with id `account_id`, which CSV
+/// fingerprints are made of. CSV files are read according to `profile`, and their dates are
+/// interpreted as local dates in `timezone`, like OFX dates without an offset.
+///
+/// The file as a whole fails to be read if it is malformed. Otherwise, each row is read on its
+/// own, so that one malformed row does not prevent importing the others.
+pub fn read_rows(
+    format: ImportFormat,
+    profile: Option<&CsvProfile>,
+    contents: &[u8],
+    account_id: Uuid,
+    timezone: Tz,
+) -> Result<Vec<Result<ImportRow, InvalidRow>>, ImportError> {
+    match format {
+        ImportFormat::Csv => {
+            let profile = profile.ok_or(ImportError::MissingProfile)?;
+            let mut fingerprints = Fingerprints::new();
+
+            Ok(profile
+                .read(contents)?
+                .into_iter()
+                .map(|record| {
+                    let record = record.map_err(|e| InvalidRow {
+                        line: e.line(),
+                        error: e.to_string(),
+                    })?;
+                    let posting_date =
+                        local_to_utc(record.date().and_time(NaiveTime::MIN), timezone).map_err(
+                            |e| InvalidRow {
+                                line: Some(record.line()),
+                                error: e.to_string(),
+                            },
+                        )?;
+
+                    Ok(ImportRow {
+                        line: Some(record.line()),
+                        posting_date,
+                        title: record.title().to_string(),
+                        amount: record.amount(),
+                        category: record
+                            .category()
+                            .map(str::trim)
+                            .filter(|category| !category.is_empty())
+                            .map(str::to_string),
+                        fingerprint: fingerprints.next(
+                            "csv",
+                            account_id,
+                            record.date(),
+                            record.amount(),
+                            record.title(),
+                        ),
+                    })
+                })
+                .collect())
+        }
+        ImportFormat::Ofx => {
+            let statements = ofx::parse(&ofx::decode(contents), timezone)?;
+
+            Ok(statements
+                .iter()
+                .flat_map(|statement| {
+                    statement.transactions().iter().map(|transaction| {
+                        Ok(ImportRow {
+                            line: None,
+                            posting_date: transaction.posting_date(),
+                            title: transaction.name().to_string(),
+                            amount: transaction.amount(),
+                            category: None,
+                            fingerprint: statement.fingerprint(transaction),
+                        })
+                    })
+                })
+                .collect())
+        }
+    }
+}
+
+/// Hands out import fingerprints for rows that have no id of their own, made of a hash of the
+/// source account, the date, the amount and the normalized title.
+///
+/// Identical rows in the same file, like two coffees bought on the same day, are told apart by
+/// the number of times the hash was already seen. Importing the same file, or an overlapping one,
+/// again thus yields the same fingerprints.
+#[derive(Debug, Default)]
+pub struct Fingerprints {
+    seen: HashMap<String, usize>,
+}
+
+impl Fingerprints {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The fingerprint of the next row, prefixed by the kind of file it comes from
+    pub fn next(
+        &mut self,
+        kind: &str,
+        account_id: Uuid,
+        date: NaiveDate,
+        amount: Decimal,
+        title: &str,
+    ) -> String {
+        let hash = hash_row(account_id, date, amount, title);
+        let occurrence = self.seen.entry(hash.clone()).or_default();
+        *occurrence += 1;
+        format!("{kind}:{hash}:{occurrence}")
+    }
+}
+
+/// A stable hash of a row's fields. Titles are compared case-insensitively and regardless of
+/// whitespace, and amounts regardless of trailing zeros.
+fn hash_row(account_id: Uuid, date: NaiveDate, amount: Decimal, title: &str) -> String {
+    let title = title
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let digest = Sha256::new()
+        .chain_update(account_id.as_bytes())
+        .chain_update(date.to_string())
+        .chain_update([0])
+        .chain_update(amount.normalize().to_string())
+        .chain_update([0])
+        .chain_update(title)
+        .finalize();
+    hex::encode(digest)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    const OFX: &str = "<OFX><CREDITCARDMSGSRSV1><CCSTMTTRNRS><CCSTMTRS>
+<CCACCTFROM><ACCTID>4111</ACCTID></CCACCTFROM>
+<BANKTRANLIST><STMTTRN>
+<DTPOSTED>20260203083000</DTPOSTED><TRNAMT>-12.99</TRNAMT><FITID>abc</FITID><NAME>Bookstore</NAME>
+</STMTTRN></BANKTRANLIST>
+</CCSTMTRS></CCSTMTTRNRS></CREDITCARDMSGSRSV1></OFX>";
+
+    #[test]
+    fn ofx_rows_move_the_amount_between_the_account_and_its_counterpart() {
+        let card = Uuid::new_v4();
+        let books = Uuid::new_v4();
+
+        let rows = read_rows(ImportFormat::Ofx, None, OFX.as_bytes(), card, Tz::UTC).unwrap();
+        let row = rows[0].as_ref().unwrap();
+        let request = row.request(card, books, None).unwrap();
+
+        assert_eq!(None, row.line());
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 2, 3, 8, 30, 0).unwrap(),
+            row.posting_date()
+        );
+        assert_eq!(AccountType::Expense, row.counterpart_type());
+        assert_eq!(dec!(-12.99), request.legs()[0].amount());
+        assert_eq!(card, request.legs()[0].account_id());
+        assert_eq!(dec!(12.99), request.legs()[1].amount());
+        assert_eq!(Some("ofx:4111:abc"), request.import_fingerprint());
+    }
+
+    #[test]
+    fn csv_files_need_a_profile() {
+        assert!(matches!(
+            read_rows(ImportFormat::Csv, None, b"", Uuid::new_v4(), Tz::UTC),
+            Err(ImportError::MissingProfile)
+        ));
+    }
+
+    #[test]
+    fn fingerprints_ignore_case_whitespace_and_trailing_zeros() {
+        let account_id = Uuid::new_v4();
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+
+        let first = Fingerprints::new().next("csv", account_id, date, dec!(-12.50), "Bakery  Ltd");
+        let second = Fingerprints::new().next("csv", account_id, date, dec!(-12.5), " bakery ltd");
+
+        assert_eq!(first, second);
+        assert!(first.starts_with("csv:"));
+        assert!(first.ends_with(":1"));
+    }
+
+    #[test]
+    fn fingerprints_tell_identical_rows_apart() {
+        let account_id = Uuid::new_v4();
+        let date = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+        let mut fingerprints = Fingerprints::new();
+
+        let first = fingerprints.next("csv", account_id, date, dec!(-5), "Coffee");
+        let second = fingerprints.next("csv", account_id, date, dec!(-5), "Coffee");
+        let other_account = fingerprints.next("csv", Uuid::new_v4(), date, dec!(-5), "Coffee");
+
+        assert_ne!(first, second);
+        assert_eq!(first.trim_end_matches(":1"), second.trim_end_matches(":2"));
+        assert!(other_account.ends_with(":1"));
+    }
+}
@@ -0,0 +1,476 @@
+//! CSV exports, read according to a [CsvProfile] that describes the layout of a bank's files.
+//!
+//! Profiles are named and loaded from `configuration/import_profiles.yaml`, see
+//! [get_import_profiles](crate::configuration::get_import_profiles).
+
+use chrono::NaiveDate;
+use csv::StringRecord;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+/// Specifies errors that may arise from reading a CSV file with a [CsvProfile]
+#[derive(Debug, thiserror::Error)]
+pub enum CsvImportError {
+    #[error("invalid import profile: {reason}")]
+    InvalidProfile { reason: String },
+    #[error("the file is not valid UTF-8, try another encoding")]
+    InvalidEncoding,
+    #[error("the file has no {column} column")]
+    MissingColumn { column: String },
+    #[error("line {line} has no {column}")]
+    MissingValue { line: u64, column: String },
+    #[error("line {line}: \"{raw}\" does not match any of the profile's date formats")]
+    InvalidDate { line: u64, raw: String },
+    #[error("line {line}: \"{raw}\" is not a valid amount")]
+    InvalidAmount { line: u64, raw: String },
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+}
+
+impl CsvImportError {
+    /// The line of the file the error is about, if it is about a single row
+    pub fn line(&self) -> Option<u64> {
+        match self {
+            CsvImportError::MissingValue { line, .. }
+            | CsvImportError::InvalidDate { line, .. }
+            | CsvImportError::InvalidAmount { line, .. } => Some(*line),
+            CsvImportError::Csv(err) => err.position().map(|position| position.line()),
+            _ => None,
+        }
+    }
+}
+
+/// How to read the CSV export of a bank
+#[derive(Clone, Debug, Deserialize)]
+pub struct CsvProfile {
+    /// The character separating fields
+    #[serde(default = "default_delimiter")]
+    delimiter: String,
+    #[serde(default)]
+    encoding: Encoding,
+    /// How many lines to skip before the header row, like a title or the account's details
+    #[serde(default)]
+    skip_rows: usize,
+    /// Whether the file has a header row. Without one, columns can only be given by index.
+    #[serde(default = "default_has_headers")]
+    has_headers: bool,
+    columns: CsvColumns,
+    /// The formats dates may have, in `chrono`'s syntax, tried in order
+    #[serde(default = "default_date_formats")]
+    date_formats: Vec<String>,
+    #[serde(default = "default_decimal_separator")]
+    decimal_separator: String,
+    #[serde(default)]
+    thousands_separator: Option<String>,
+    /// Amounts are read as money coming into the account, and money leaving it when negative.
+    /// Files where spending is positive, like most credit card statements, need this set.
+    #[serde(default)]
+    invert_amounts: bool,
+}
+
+/// Where to find each field of a transaction. Either `amount` or at least one of `debit` and
+/// `credit` must be set.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CsvColumns {
+    date: Column,
+    title: Column,
+    #[serde(default)]
+    amount: Option<Column>,
+    /// Money leaving the account, as a positive amount
+    #[serde(default)]
+    debit: Option<Column>,
+    /// Money coming into the account, as a positive amount
+    #[serde(default)]
+    credit: Option<Column>,
+    #[serde(default)]
+    category: Option<Column>,
+}
+
+/// A column of a CSV file: its 0-based index, its header, or a list of headers it may have
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(untagged)]
+pub enum Column {
+    Index(usize),
+    Name(String),
+    AnyOf(Vec<String>),
+}
+
+impl Column {
+    /// The index of this column among the `headers`, if there is such a column
+    fn resolve(&self, headers: Option<&StringRecord>) -> Option<usize> {
+        let position = |name: &str| {
+            headers?
+                .iter()
+                .position(|header| header.trim().eq_ignore_ascii_case(name.trim()))
+        };
+
+        match self {
+            Column::Index(index) => Some(*index),
+            Column::Name(name) => position(name),
+            Column::AnyOf(names) => names.iter().find_map(|name| position(name)),
+        }
+    }
+}
+
+impl std::fmt::Display for Column {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Column::Index(index) => write!(f, "#{index}"),
+            Column::Name(name) => write!(f, "\"{name}\""),
+            Column::AnyOf(names) => write!(f, "\"{}\"", names.join("\" or \"")),
+        }
+    }
+}
+
+/// The text encoding of a file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    /// ISO-8859-1, which is also how Windows-1252 files are read
+    Latin1,
+}
+
+impl Encoding {
+    fn decode(&self, bytes: &[u8]) -> Result<String, CsvImportError> {
+        let text = match self {
+            Encoding::Utf8 => std::str::from_utf8(bytes)
+                .map_err(|_| CsvImportError::InvalidEncoding)?
+                .to_string(),
+            Encoding::Latin1 => bytes.iter().map(|&byte| byte as char).collect(),
+        };
+
+        Ok(text.trim_start_matches('\u{feff}').to_string())
+    }
+}
+
+/// A transaction read from a CSV file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvRecord {
+    /// The line of the file the transaction is on, starting at 1
+    line: u64,
+    date: NaiveDate,
+    title: String,
+    /// Positive amounts are money coming into the account, negative ones are money leaving it
+    amount: Decimal,
+    category: Option<String>,
+}
+
+impl CsvRecord {
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn category(&self) -> Option<&str> {
+        self.category.as_deref()
+    }
+}
+
+/// The indices of the columns of a file
+struct Indices {
+    date: usize,
+    title: usize,
+    amount: Option<usize>,
+    debit: Option<usize>,
+    credit: Option<usize>,
+    category: Option<usize>,
+}
+
+impl CsvProfile {
+    /// Read the transactions of a CSV file.
+    ///
+    /// The file as a whole fails to be read if it does not match the profile, like when a column
+    /// is missing. Otherwise, each row is read on its own, so that one malformed row does not
+    /// prevent importing the others.
+    pub fn read(
+        &self,
+        bytes: &[u8],
+    ) -> Result<Vec<Result<CsvRecord, CsvImportError>>, CsvImportError> {
+        let delimiter = match self.delimiter.as_bytes() {
+            [delimiter] => *delimiter,
+            _ => {
+                return Err(CsvImportError::InvalidProfile {
+                    reason: format!("\"{}\" is not a single character delimiter", self.delimiter),
+                });
+            }
+        };
+        if self.columns.amount.is_none()
+            && self.columns.debit.is_none()
+            && self.columns.credit.is_none()
+        {
+            return Err(CsvImportError::InvalidProfile {
+                reason: "either an amount column or debit and credit columns are needed"
+                    .to_string(),
+            });
+        }
+
+        let text = self.encoding.decode(bytes)?;
+        let mut rest = text.as_str();
+        for _ in 0..self.skip_rows {
+            rest = rest
+                .split_once('\n')
+                .map(|(_, rest)| rest)
+                .unwrap_or_default();
+        }
+
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(self.has_headers)
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(rest.as_bytes());
+        let headers = if self.has_headers {
+            Some(reader.headers()?.clone())
+        } else {
+            None
+        };
+        let required = |column: &Column| {
+            column
+                .resolve(headers.as_ref())
+                .ok_or_else(|| CsvImportError::MissingColumn {
+                    column: column.to_string(),
+                })
+        };
+        let optional = |column: &Option<Column>| column.as_ref().map(required).transpose();
+        let indices = Indices {
+            date: required(&self.columns.date)?,
+            title: required(&self.columns.title)?,
+            amount: optional(&self.columns.amount)?,
+            debit: optional(&self.columns.debit)?,
+            credit: optional(&self.columns.credit)?,
+            // Files may leave out the category column altogether
+            category: self
+                .columns
+                .category
+                .as_ref()
+                .and_then(|column| column.resolve(headers.as_ref())),
+        };
+
+        let skipped_lines = self.skip_rows as u64;
+        Ok(reader
+            .records()
+            .map(|record| {
+                let record = record?;
+                let line = record.position().map(|p| p.line()).unwrap_or_default() + skipped_lines;
+
+                self.record(&record, &indices, line)
+            })
+            .collect())
+    }
+
+    fn record(
+        &self,
+        record: &StringRecord,
+        indices: &Indices,
+        line: u64,
+    ) -> Result<CsvRecord, CsvImportError> {
+        let value = |index: usize| record.get(index).filter(|value| !value.is_empty());
+        let required = |index: usize, column: &str| {
+            value(index).ok_or_else(|| CsvImportError::MissingValue {
+                line,
+                column: column.to_string(),
+            })
+        };
+        let amount = |raw: &str| {
+            self.parse_amount(raw)
+                .ok_or_else(|| CsvImportError::InvalidAmount {
+                    line,
+                    raw: raw.to_string(),
+                })
+        };
+
+        let raw_date = required(indices.date, "date")?;
+        let date = self
+            .date_formats
+            .iter()
+            .find_map(|format| NaiveDate::parse_from_str(raw_date, format).ok())
+            .ok_or_else(|| CsvImportError::InvalidDate {
+                line,
+                raw: raw_date.to_string(),
+            })?;
+
+        let amount = match indices.amount {
+            Some(index) => amount(required(index, "amount")?)?,
+            None => {
+                let debit = indices.debit.and_then(value);
+                let credit = indices.credit.and_then(value);
+                if debit.is_none() && credit.is_none() {
+                    return Err(CsvImportError::MissingValue {
+                        line,
+                        column: "debit or credit".to_string(),
+                    });
+                }
+
+                let debit = debit.map(amount).transpose()?.unwrap_or_default();
+                let credit = credit.map(amount).transpose()?.unwrap_or_default();
+                credit.abs() - debit.abs()
+            }
+        };
+
+        Ok(CsvRecord {
+            line,
+            date,
+            title: required(indices.title, "title")?.to_string(),
+            amount: if self.invert_amounts { -amount } else { amount },
+            category: indices.category.and_then(value).map(str::to_string),
+        })
+    }
+
+    /// Parse an amount with the profile's separators. Currency symbols are ignored, and amounts
+    /// in parentheses are negative.
+    fn parse_amount(&self, raw: &str) -> Option<Decimal> {
+        let raw = raw.trim();
+        let mut normalized = raw.to_string();
+        if let Some(separator) = &self.thousands_separator {
+            normalized = normalized.replace(separator.as_str(), "");
+        }
+        normalized = normalized.replace(self.decimal_separator.as_str(), ".");
+        let normalized: String = normalized
+            .chars()
+            .filter(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.'))
+            .collect();
+
+        let amount = normalized.parse::<Decimal>().ok()?;
+        if raw.starts_with('(') && raw.ends_with(')') {
+            Some(-amount)
+        } else {
+            Some(amount)
+        }
+    }
+}
+
+fn default_delimiter() -> String {
+    ",".to_string()
+}
+
+fn default_has_headers() -> bool {
+    true
+}
+
+fn default_date_formats() -> Vec<String> {
+    vec!["%Y-%m-%d".to_string()]
+}
+
+fn default_decimal_separator() -> String {
+    ".".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+
+    use super::{CsvImportError, CsvProfile};
+
+    fn profile(yaml: &str) -> CsvProfile {
+        config::Config::builder()
+            .add_source(config::File::from_str(yaml, config::FileFormat::Yaml))
+            .build()
+            .unwrap()
+            .try_deserialize()
+            .unwrap()
+    }
+
+    #[test]
+    fn profiles_map_named_columns_and_invert_amounts() {
+        let profile = profile(
+            r#"
+columns:
+  date: [date, Data]
+  title: Descrição
+  amount: Valor
+  category: category
+date_formats: ["%d/%m/%Y", "%Y-%m-%d"]
+invert_amounts: true
+"#,
+        );
+        let file = "Data,Descrição,Valor\n15/01/2026,Padaria,12.50\n2026-01-16,Refund,-3\n";
+
+        let records: Vec<_> = profile
+            .read(file.as_bytes())
+            .unwrap()
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+
+        assert_eq!(2, records.len());
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2026, 1, 15).unwrap(),
+            records[0].date()
+        );
+        assert_eq!("Padaria", records[0].title());
+        assert_eq!(dec!(-12.50), records[0].amount());
+        assert_eq!(None, records[0].category());
+        assert_eq!(dec!(3), records[1].amount());
+        assert_eq!(3, records[1].line());
+    }
+
+    #[test]
+    fn profiles_read_latin1_files_with_decimal_commas_and_debit_credit_columns() {
+        let profile = profile(
+            r#"
+delimiter: ";"
+encoding: latin1
+skip_rows: 2
+columns:
+  date: 0
+  title: 1
+  debit: 2
+  credit: 3
+date_formats: ["%d/%m/%Y"]
+decimal_separator: ","
+thousands_separator: "."
+"#,
+        );
+        let file = b"Extrato\nConta 1234\nData;Hist\xf3rico;D\xe9bito;Cr\xe9dito\n\
+            01/02/2026;Sal\xe1rio;;R$ 1.500,00\n02/02/2026;Aluguel;(1.200,50);\n03/02/2026;Nada;;\n";
+
+        let rows = profile.read(file).unwrap();
+
+        assert_eq!(3, rows.len());
+        let salary = rows[0].as_ref().unwrap();
+        assert_eq!("Salário", salary.title());
+        assert_eq!(dec!(1500), salary.amount());
+        assert_eq!(dec!(-1200.50), rows[1].as_ref().unwrap().amount());
+        assert!(matches!(
+            rows[2],
+            Err(CsvImportError::MissingValue { line: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn profiles_report_missing_columns_and_invalid_values() {
+        let profile = profile(
+            r#"
+columns:
+  date: date
+  title: title
+  amount: amount
+"#,
+        );
+
+        assert!(matches!(
+            profile.read(b"date,name,amount\n"),
+            Err(CsvImportError::MissingColumn { .. })
+        ));
+
+        let rows = profile
+            .read(b"date,title,amount\n15/01/2026,a,1\n2026-01-15,b,abc\n")
+            .unwrap();
+        assert!(matches!(rows[0], Err(CsvImportError::InvalidDate { .. })));
+        assert!(matches!(rows[1], Err(CsvImportError::InvalidAmount { .. })));
+    }
+}
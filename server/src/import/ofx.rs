@@ -0,0 +1,544 @@
+//! A parser for OFX bank and credit card statements, also known as QFX when exported for
+//! Quicken.
+//!
+//! OFX 1.x files are SGML, where elements holding a value usually have no closing tag, while
+//! OFX 2.x files are XML. Both are read the same way: elements are closed implicitly when
+//! another one starts right after their value.
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, TimeZone as _, Utc};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+
+use crate::models::transaction::{PostingDateParseError, local_to_utc};
+
+/// Specifies errors that may arise from parsing an OFX file
+#[derive(Debug, thiserror::Error)]
+pub enum OfxParseError {
+    #[error("the file has no <OFX> element")]
+    MissingOfxElement,
+    #[error("tag <{tag}> is never closed")]
+    UnclosedTag { tag: String },
+    #[error("closing tag </{tag}> does not match any open tag")]
+    UnexpectedClosingTag { tag: String },
+    #[error("<{aggregate}> has no <{field}>")]
+    MissingField { aggregate: String, field: String },
+    #[error("\"{raw}\" is not a valid OFX date")]
+    InvalidDate { raw: String },
+    #[error("\"{raw}\" is not a valid amount")]
+    InvalidAmount { raw: String },
+    #[error(transparent)]
+    PostingDate(#[from] PostingDateParseError),
+}
+
+/// The transactions of one account in an OFX file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OfxStatement {
+    /// The `ACCTID` of the account at its institution
+    account_id: String,
+    /// The `CURDEF` of the statement, like `BRL`
+    currency: Option<String>,
+    transactions: Vec<OfxTransaction>,
+}
+
+impl OfxStatement {
+    pub fn account_id(&self) -> &str {
+        &self.account_id
+    }
+
+    pub fn currency(&self) -> Option<&str> {
+        self.currency.as_deref()
+    }
+
+    pub fn transactions(&self) -> &[OfxTransaction] {
+        &self.transactions
+    }
+
+    /// The import fingerprint of a transaction of this statement, made of the account's id and
+    /// the transaction's FITID, which the bank keeps the same across statements.
+    pub fn fingerprint(&self, transaction: &OfxTransaction) -> String {
+        format!("ofx:{}:{}", self.account_id, transaction.fitid)
+    }
+}
+
+/// A `STMTTRN` of an [OfxStatement]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OfxTransaction {
+    /// The id the institution gave the transaction, unique within the account
+    fitid: String,
+    posting_date: DateTime<Utc>,
+    /// Positive amounts are money coming into the account, negative ones are money leaving it
+    amount: Decimal,
+    /// The payee, or the memo if there is no payee
+    name: String,
+    memo: Option<String>,
+}
+
+impl OfxTransaction {
+    pub fn fitid(&self) -> &str {
+        &self.fitid
+    }
+
+    pub fn posting_date(&self) -> DateTime<Utc> {
+        self.posting_date
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn memo(&self) -> Option<&str> {
+        self.memo.as_deref()
+    }
+}
+
+/// Decode the contents of an OFX file. OFX 1.x files are often encoded in Latin-1 or
+/// Windows-1252 rather than UTF-8, so bytes that are not valid UTF-8 are read as Latin-1.
+pub fn decode(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.iter().map(|&byte| byte as char).collect(),
+    }
+}
+
+/// Parse the bank and credit card statements of an OFX file.
+///
+/// Transactions appearing more than once in a statement, with the same FITID, are only kept
+/// once. Dates without a UTC offset are interpreted as local times in `timezone`.
+pub fn parse(input: &str, timezone: Tz) -> Result<Vec<OfxStatement>, OfxParseError> {
+    let root = parse_elements(input)?;
+    let ofx = root
+        .children
+        .iter()
+        .find(|element| element.name == "OFX")
+        .ok_or(OfxParseError::MissingOfxElement)?;
+
+    let mut statements = Vec::new();
+    ofx.descendants(&["STMTRS", "CCSTMTRS"], &mut statements);
+
+    statements
+        .into_iter()
+        .map(|statement| parse_statement(statement, timezone))
+        .collect()
+}
+
+fn parse_statement(statement: &Element, timezone: Tz) -> Result<OfxStatement, OfxParseError> {
+    let account_id = statement
+        .child("BANKACCTFROM")
+        .or_else(|| statement.child("CCACCTFROM"))
+        .ok_or_else(|| missing(&statement.name, "BANKACCTFROM"))?
+        .required_text("ACCTID")?
+        .to_string();
+
+    let mut fitids = HashSet::new();
+    let mut transactions = Vec::new();
+    for element in statement
+        .child("BANKTRANLIST")
+        .map(|list| list.children.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter(|element| element.name == "STMTTRN")
+    {
+        let transaction = parse_transaction(element, timezone)?;
+        if fitids.insert(transaction.fitid.clone()) {
+            transactions.push(transaction);
+        }
+    }
+
+    Ok(OfxStatement {
+        account_id,
+        currency: statement.text("CURDEF").map(str::to_string),
+        transactions,
+    })
+}
+
+fn parse_transaction(element: &Element, timezone: Tz) -> Result<OfxTransaction, OfxParseError> {
+    let raw_amount = element.required_text("TRNAMT")?;
+    // Some institutions use a decimal comma
+    let amount = if raw_amount.contains('.') {
+        raw_amount.to_string()
+    } else {
+        raw_amount.replace(',', ".")
+    }
+    .parse::<Decimal>()
+    .map_err(|_| OfxParseError::InvalidAmount {
+        raw: raw_amount.to_string(),
+    })?;
+    let memo = element.text("MEMO").map(str::to_string);
+    let name = element
+        .text("NAME")
+        .or_else(|| element.child("PAYEE").and_then(|payee| payee.text("NAME")))
+        .map(str::to_string)
+        .or_else(|| memo.clone())
+        .ok_or_else(|| missing(&element.name, "NAME"))?;
+
+    Ok(OfxTransaction {
+        fitid: element.required_text("FITID")?.to_string(),
+        posting_date: parse_date(element.required_text("DTPOSTED")?, timezone)?,
+        amount,
+        name,
+        memo,
+    })
+}
+
+/// Parse an OFX date, like `20260115`, `20260115103000` or `20260115103000.000[-3:BRT]`. The
+/// bracketed part is the UTC offset in hours and an optional timezone name.
+fn parse_date(raw: &str, timezone: Tz) -> Result<DateTime<Utc>, OfxParseError> {
+    let invalid = || OfxParseError::InvalidDate {
+        raw: raw.to_string(),
+    };
+    let (value, offset) = match raw.split_once('[') {
+        Some((value, offset)) => (value, Some(offset.trim_end_matches(']'))),
+        None => (raw, None),
+    };
+    // Milliseconds are dropped
+    let digits = value.split('.').next().unwrap_or_default().trim();
+    if digits.len() < 8 || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+
+    let date = NaiveDate::parse_from_str(&digits[..8], "%Y%m%d").map_err(|_| invalid())?;
+    let time = match digits.len() {
+        8 => NaiveTime::MIN,
+        12 => NaiveTime::parse_from_str(&digits[8..], "%H%M").map_err(|_| invalid())?,
+        14 => NaiveTime::parse_from_str(&digits[8..], "%H%M%S").map_err(|_| invalid())?,
+        _ => return Err(invalid()),
+    };
+    let naive = date.and_time(time);
+
+    match offset {
+        Some(offset) => {
+            let hours = offset
+                .split(':')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .parse::<Decimal>()
+                .map_err(|_| invalid())?;
+            let seconds = (hours * Decimal::from(3600))
+                .trunc()
+                .try_into()
+                .map_err(|_| invalid())?;
+
+            FixedOffset::east_opt(seconds)
+                .and_then(|offset| offset.from_local_datetime(&naive).single())
+                .map(|date| date.to_utc())
+                .ok_or_else(invalid)
+        }
+        None => Ok(local_to_utc(naive, timezone)?),
+    }
+}
+
+fn missing(aggregate: &str, field: &str) -> OfxParseError {
+    OfxParseError::MissingField {
+        aggregate: aggregate.to_string(),
+        field: field.to_string(),
+    }
+}
+
+/// An OFX element, which either holds a value or other elements
+#[derive(Debug, Default)]
+struct Element {
+    name: String,
+    text: Option<String>,
+    children: Vec<Element>,
+}
+
+impl Element {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_ascii_uppercase(),
+            ..Default::default()
+        }
+    }
+
+    fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// The value of the child with the given name
+    fn text(&self, name: &str) -> Option<&str> {
+        self.child(name)
+            .and_then(|child| child.text.as_deref())
+            .filter(|text| !text.is_empty())
+    }
+
+    fn required_text(&self, name: &str) -> Result<&str, OfxParseError> {
+        self.text(name).ok_or_else(|| missing(&self.name, name))
+    }
+
+    /// Collect the descendants of this element with one of the given names, without looking
+    /// inside of them
+    fn descendants<'a>(&'a self, names: &[&str], found: &mut Vec<&'a Element>) {
+        for child in &self.children {
+            if names.contains(&child.name.as_str()) {
+                found.push(child);
+            } else {
+                child.descendants(names, found);
+            }
+        }
+    }
+}
+
+/// Parse the elements of an OFX file into a tree, under a nameless root. Headers, processing
+/// instructions and comments are skipped.
+fn parse_elements(input: &str) -> Result<Element, OfxParseError> {
+    let mut stack = vec![Element::default()];
+    let mut rest = input;
+
+    // Close the innermost element if it holds a value, since values are never followed by
+    // other elements in the same element
+    fn close_value(stack: &mut Vec<Element>) {
+        if stack.len() > 1 && stack.last().is_some_and(|element| element.text.is_some()) {
+            let element = stack.pop().expect("the stack is not empty");
+            stack
+                .last_mut()
+                .expect("the root is never popped")
+                .children
+                .push(element);
+        }
+    }
+
+    while let Some(start) = rest.find('<') {
+        let text = rest[..start].trim();
+        if !text.is_empty() && stack.len() > 1 {
+            let element = stack.last_mut().expect("the stack is not empty");
+            if element.children.is_empty() && element.text.is_none() {
+                element.text = Some(unescape(text));
+            }
+        }
+
+        rest = &rest[start..];
+        let end = rest.find('>').ok_or_else(|| OfxParseError::UnclosedTag {
+            tag: rest
+                .chars()
+                .skip(1)
+                .take_while(|c| c.is_alphanumeric())
+                .collect(),
+        })?;
+        let tag = rest[1..end].trim();
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_uppercase();
+            if !stack.iter().skip(1).any(|element| element.name == name) {
+                return Err(OfxParseError::UnexpectedClosingTag { tag: name });
+            }
+            // Elements holding a value may have no closing tag, so close them along the way
+            loop {
+                let element = stack.pop().expect("the matching element is in the stack");
+                let done = element.name == name;
+                stack
+                    .last_mut()
+                    .expect("the root is never popped")
+                    .children
+                    .push(element);
+                if done {
+                    break;
+                }
+            }
+        } else if let Some(name) = tag.strip_suffix('/') {
+            close_value(&mut stack);
+            stack
+                .last_mut()
+                .expect("the root is never popped")
+                .children
+                .push(Element::new(name.trim()));
+        } else {
+            close_value(&mut stack);
+            stack.push(Element::new(tag));
+        }
+    }
+
+    // Close whatever is left open at the end of the file
+    while stack.len() > 1 {
+        let element = stack.pop().expect("the stack has more than one element");
+        stack
+            .last_mut()
+            .expect("the root is never popped")
+            .children
+            .push(element);
+    }
+
+    Ok(stack.pop().expect("the root is never popped"))
+}
+
+/// Replace the character entities that OFX values may contain
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone as _, Utc};
+    use chrono_tz::Tz;
+    use rust_decimal_macros::dec;
+
+    use super::{OfxParseError, decode, parse, parse_date};
+
+    const SGML: &str = "OFXHEADER:100
+DATA:OFXSGML
+VERSION:102
+ENCODING:USASCII
+CHARSET:1252
+
+<OFX>
+<SIGNONMSGSRSV1><SONRS><STATUS><CODE>0<SEVERITY>INFO</STATUS><DTSERVER>20260201</SONRS></SIGNONMSGSRSV1>
+<BANKMSGSRSV1>
+<STMTTRNRS>
+<TRNUID>1
+<STMTRS>
+<CURDEF>BRL
+<BANKACCTFROM>
+<BANKID>0341
+<ACCTID>12345-6
+<ACCTTYPE>CHECKING
+</BANKACCTFROM>
+<BANKTRANLIST>
+<DTSTART>20260101
+<DTEND>20260131
+<STMTTRN>
+<TRNTYPE>DEBIT
+<DTPOSTED>20260115120000[-3:BRT]
+<TRNAMT>-42,50
+<FITID>2026011501
+<MEMO>Padaria &amp; Cia
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20260120
+<TRNAMT>1000.00
+<FITID>2026012001
+<NAME>Salary
+</STMTTRN>
+<STMTTRN>
+<TRNTYPE>CREDIT
+<DTPOSTED>20260120
+<TRNAMT>1000.00
+<FITID>2026012001
+<NAME>Salary
+</STMTTRN>
+</BANKTRANLIST>
+<LEDGERBAL><BALAMT>957.50<DTASOF>20260131</LEDGERBAL>
+</STMTRS>
+</STMTTRNRS>
+</BANKMSGSRSV1>
+</OFX>
+";
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<?OFX OFXHEADER="200" VERSION="220" SECURITY="NONE" OLDFILEUID="NONE" NEWFILEUID="NONE"?>
+<OFX>
+  <CREDITCARDMSGSRSV1>
+    <CCSTMTTRNRS>
+      <TRNUID>1</TRNUID>
+      <CCSTMTRS>
+        <CURDEF>USD</CURDEF>
+        <CCACCTFROM><ACCTID>4111</ACCTID></CCACCTFROM>
+        <BANKTRANLIST>
+          <STMTTRN>
+            <TRNTYPE>DEBIT</TRNTYPE>
+            <DTPOSTED>20260203083000.000</DTPOSTED>
+            <TRNAMT>-12.99</TRNAMT>
+            <FITID>abc</FITID>
+            <PAYEE><NAME>Bookstore</NAME></PAYEE>
+            <MEMO/>
+          </STMTTRN>
+        </BANKTRANLIST>
+      </CCSTMTRS>
+    </CCSTMTTRNRS>
+  </CREDITCARDMSGSRSV1>
+</OFX>
+"#;
+
+    #[test]
+    fn sgml_statements_are_parsed_and_deduplicated_by_fitid() {
+        let statements = parse(SGML, Tz::UTC).unwrap();
+
+        assert_eq!(1, statements.len());
+        let statement = &statements[0];
+        assert_eq!("12345-6", statement.account_id());
+        assert_eq!(Some("BRL"), statement.currency());
+        assert_eq!(2, statement.transactions().len());
+
+        let bakery = &statement.transactions()[0];
+        assert_eq!("Padaria & Cia", bakery.name());
+        assert_eq!(dec!(-42.50), bakery.amount());
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 1, 15, 15, 0, 0).unwrap(),
+            bakery.posting_date()
+        );
+        assert_eq!("ofx:12345-6:2026011501", statement.fingerprint(bakery));
+    }
+
+    #[test]
+    fn xml_statements_are_parsed() {
+        let timezone: Tz = "America/Sao_Paulo".parse().unwrap();
+        let statements = parse(XML, timezone).unwrap();
+
+        assert_eq!(1, statements.len());
+        let statement = &statements[0];
+        assert_eq!("4111", statement.account_id());
+        let transaction = &statement.transactions()[0];
+        assert_eq!("Bookstore", transaction.name());
+        assert_eq!(None, transaction.memo());
+        // Dates without an offset are local times
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 2, 3, 11, 30, 0).unwrap(),
+            transaction.posting_date()
+        );
+    }
+
+    #[test]
+    fn ofx_dates_support_offsets_and_reject_garbage() {
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 1, 15, 4, 30, 0).unwrap(),
+            parse_date("20260115100000[5.5:IST]", Tz::UTC).unwrap()
+        );
+        assert_eq!(
+            Utc.with_ymd_and_hms(2026, 1, 15, 10, 0, 0).unwrap(),
+            parse_date("202601151000", Tz::UTC).unwrap()
+        );
+        for raw in ["2026-01-15", "20261315", "2026011510", "20260115[x]"] {
+            assert!(
+                matches!(
+                    parse_date(raw, Tz::UTC),
+                    Err(OfxParseError::InvalidDate { .. })
+                ),
+                "{raw}"
+            );
+        }
+    }
+
+    #[test]
+    fn files_without_an_ofx_element_or_with_stray_closing_tags_are_rejected() {
+        assert!(matches!(
+            parse("<HTML></HTML>", Tz::UTC),
+            Err(OfxParseError::MissingOfxElement)
+        ));
+        assert!(matches!(
+            parse("<OFX></STMTRS></OFX>", Tz::UTC),
+            Err(OfxParseError::UnexpectedClosingTag { .. })
+        ));
+    }
+
+    #[test]
+    fn latin1_files_are_decoded() {
+        assert_eq!("Descrição", decode(b"Descri\xe7\xe3o"));
+        assert_eq!("Descrição", decode("Descrição".as_bytes()));
+    }
+}
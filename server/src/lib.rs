@@ -5,6 +5,7 @@ use server::Server;
 pub mod cli;
 pub mod configuration;
 pub mod handlers;
+pub mod import;
 pub mod models;
 pub mod server;
 pub mod service;
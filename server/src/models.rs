@@ -1,2 +1,14 @@
 pub mod account;
+pub mod budget;
+pub mod category;
+pub mod credit_card;
+pub mod currency;
+pub mod forecast;
+pub mod import_batch;
+pub mod installment;
+pub mod payee;
+pub mod price;
+pub mod report;
+pub mod rule;
+pub mod schedule;
 pub mod transaction;
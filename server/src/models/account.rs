@@ -1,28 +1,80 @@
 pub mod errors;
 
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
 use axum::Form;
 use derive_more::derive::Display;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::currency::Currency;
 use crate::utils::NonemptyStringVisitor;
 
 pub use errors::*;
 
 /// An account here is akin to an account in the double entry bookkeeping model.
 /// It may represent your bank account, an "expenses" account, and so on. Its
-/// balance is represented in cents.
+/// balance is represented in cents, following the sign convention of its [AccountType].
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Account {
     id: Uuid,
     name: AccountName,
+    #[serde(rename = "type")]
+    account_type: AccountType,
+    /// The account one level above this one in the hierarchy, e.g. `assets:bank` is the parent
+    /// of `assets:bank:nubank`.
+    parent_id: Option<Uuid>,
+    /// What the balance and the legs of the account are counted in
+    #[serde(default)]
+    currency: Currency,
     balance: Decimal,
 }
 
 impl Account {
-    pub fn new(id: Uuid, name: AccountName, balance: Decimal) -> Self {
-        Self { id, name, balance }
+    /// Create a new [Account].
+    ///
+    /// The `balance` must already follow the account type's sign convention. See
+    /// [Account::from_ledger_balance] to build an account from the sum of its legs.
+    pub fn new(
+        id: Uuid,
+        name: AccountName,
+        account_type: AccountType,
+        parent_id: Option<Uuid>,
+        balance: Decimal,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            account_type,
+            parent_id,
+            currency: Currency::default(),
+            balance,
+        }
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    /// Create a new [Account] from its ledger balance, i.e. the sum of its debits minus the sum
+    /// of its credits.
+    pub fn from_ledger_balance(
+        id: Uuid,
+        name: AccountName,
+        account_type: AccountType,
+        parent_id: Option<Uuid>,
+        ledger_balance: Decimal,
+    ) -> Self {
+        Self::new(
+            id,
+            name,
+            account_type,
+            parent_id,
+            account_type.balance(ledger_balance),
+        )
     }
 
     pub fn id(&self) -> Uuid {
@@ -33,23 +85,202 @@ impl Account {
         &self.name
     }
 
+    pub fn account_type(&self) -> AccountType {
+        self.account_type
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn parent_id(&self) -> Option<Uuid> {
+        self.parent_id
+    }
+
     pub fn balance(&self) -> Decimal {
         self.balance
     }
 }
 
+/// A node of the account tree: an [Account] along with its sub-accounts and the rolled-up
+/// balance of the whole subtree.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AccountTreeNode {
+    #[serde(flatten)]
+    account: Account,
+    /// The balance of this account plus the balances of all of its descendants, following this
+    /// account's sign convention.
+    subtotal: Decimal,
+    children: Vec<AccountTreeNode>,
+}
+
+impl AccountTreeNode {
+    /// Arrange a flat list of [Account]s into trees, returning their roots sorted by name.
+    ///
+    /// Accounts whose parent is not in the list are treated as roots.
+    pub fn build(accounts: Vec<Account>) -> Vec<AccountTreeNode> {
+        let ids: HashSet<Uuid> = accounts.iter().map(Account::id).collect();
+        let mut children: HashMap<Option<Uuid>, Vec<Account>> = HashMap::new();
+        for account in accounts {
+            let parent_id = account.parent_id().filter(|id| ids.contains(id));
+            children.entry(parent_id).or_default().push(account);
+        }
+
+        Self::build_level(None, &mut children)
+    }
+
+    fn build_level(
+        parent_id: Option<Uuid>,
+        children: &mut HashMap<Option<Uuid>, Vec<Account>>,
+    ) -> Vec<AccountTreeNode> {
+        let mut accounts = children.remove(&parent_id).unwrap_or_default();
+        accounts.sort_by(|a, b| a.name().cmp(b.name()));
+
+        accounts
+            .into_iter()
+            .map(|account| {
+                let children = Self::build_level(Some(account.id()), children);
+                // Sub-accounts may follow a different sign convention, so roll up ledger balances
+                let ledger_subtotal = account.account_type().balance(account.balance())
+                    + children
+                        .iter()
+                        .map(|c| c.account.account_type().balance(c.subtotal))
+                        .sum::<Decimal>();
+                let subtotal = account.account_type().balance(ledger_subtotal);
+
+                AccountTreeNode {
+                    account,
+                    subtotal,
+                    children,
+                }
+            })
+            .collect()
+    }
+
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    pub fn subtotal(&self) -> Decimal {
+        self.subtotal
+    }
+
+    pub fn children(&self) -> &[AccountTreeNode] {
+        &self.children
+    }
+}
+
+/// The type of an [Account], which determines the sign convention of its balance.
+///
+/// Asset and expense accounts are debit-normal: debits increase their balances. Liability,
+/// income and equity accounts are credit-normal: credits increase their balances.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum AccountType {
+    #[default]
+    Asset,
+    Liability,
+    Income,
+    Expense,
+    Equity,
+}
+
+impl AccountType {
+    pub const ALL: [AccountType; 5] = [
+        AccountType::Asset,
+        AccountType::Liability,
+        AccountType::Income,
+        AccountType::Expense,
+        AccountType::Equity,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountType::Asset => "asset",
+            AccountType::Liability => "liability",
+            AccountType::Income => "income",
+            AccountType::Expense => "expense",
+            AccountType::Equity => "equity",
+        }
+    }
+
+    /// Whether debits increase the balance of accounts of this type.
+    pub fn is_debit_normal(&self) -> bool {
+        matches!(self, AccountType::Asset | AccountType::Expense)
+    }
+
+    /// Convert a ledger balance (debits minus credits) into a balance that follows this type's
+    /// sign convention. The conversion is its own inverse.
+    pub fn balance(&self, ledger_balance: Decimal) -> Decimal {
+        if self.is_debit_normal() || ledger_balance.is_zero() {
+            ledger_balance
+        } else {
+            -ledger_balance
+        }
+    }
+}
+
+impl std::fmt::Display for AccountType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AccountType {
+    type Err = AccountTypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AccountType::ALL
+            .into_iter()
+            .find(|t| t.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| AccountTypeParseError { raw: s.to_string() })
+    }
+}
+
 /// A valid account name. An account name will always be stored as a lowercase string.
+///
+/// Names are hierarchical, with colon-separated segments going from the most general to the most
+/// specific account, like `expenses:food:restaurants`.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display, Serialize)]
 pub struct AccountName(String);
 
 impl AccountName {
+    /// Separates the segments of a hierarchical account name
+    pub const SEPARATOR: char = ':';
+
     pub fn new(raw: &str) -> Result<Self, AccountNameEmptyError> {
-        let trimmed = raw.trim();
-        if trimmed.is_empty() {
+        let segments: Vec<&str> = raw.split(Self::SEPARATOR).map(str::trim).collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
             Err(AccountNameEmptyError)
         } else {
-            Ok(Self(raw.to_lowercase()))
+            Ok(Self(segments.join(":").to_lowercase()))
+        }
+    }
+
+    /// The colon-separated segments of this name, from the root to the leaf
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.0.split(Self::SEPARATOR)
+    }
+
+    /// The name of the parent account, or [None] if this is a root account
+    pub fn parent(&self) -> Option<AccountName> {
+        self.0
+            .rsplit_once(Self::SEPARATOR)
+            .map(|(parent, _)| Self(parent.to_string()))
+    }
+
+    /// The names of all the accounts above this one, starting from the root
+    pub fn ancestors(&self) -> Vec<AccountName> {
+        let mut ancestors = Vec::new();
+        let mut current = self.parent();
+        while let Some(name) = current {
+            current = name.parent();
+            ancestors.push(name);
         }
+        ancestors.reverse();
+        ancestors
     }
 
     pub fn into_url_encoding(self) -> String {
@@ -73,16 +304,161 @@ impl<'de> Deserialize<'de> for AccountName {
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CreateAccountRequest {
     name: AccountName,
+    account_type: AccountType,
+    currency: Currency,
 }
 
 impl CreateAccountRequest {
     /// Create a new [CreateAccountRequest]. Note that a balance is not present,
     /// because a new account will always have a balance of 0.
-    pub fn new(name: AccountName) -> Self {
-        Self { name }
+    pub fn new(name: AccountName, account_type: AccountType) -> Self {
+        Self {
+            name,
+            account_type,
+            currency: Currency::default(),
+        }
+    }
+
+    /// Set the currency of the account, [Currency::BRL] by default
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
     }
 
     pub fn name(&self) -> &AccountName {
         &self.name
     }
+
+    pub fn account_type(&self) -> AccountType {
+        self.account_type
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    use super::{Account, AccountName, AccountTreeNode, AccountType};
+
+    #[test]
+    fn account_names_are_split_into_segments() {
+        let name = AccountName::new("Assets : Bank:Nubank").unwrap();
+
+        assert_eq!("assets:bank:nubank", name.to_string());
+        assert_eq!(
+            vec!["assets", "bank", "nubank"],
+            name.segments().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                AccountName::new("assets").unwrap(),
+                AccountName::new("assets:bank").unwrap()
+            ],
+            name.ancestors()
+        );
+        assert_eq!(None, AccountName::new("assets").unwrap().parent());
+    }
+
+    #[test]
+    fn account_names_must_not_have_empty_segments() {
+        assert!(AccountName::new("assets::nubank").is_err());
+        assert!(AccountName::new("assets:").is_err());
+        assert!(AccountName::new(" ").is_err());
+    }
+
+    #[test]
+    fn account_tree_rolls_up_subtotals() {
+        let assets = Account::new(
+            Uuid::new_v4(),
+            AccountName::new("assets").unwrap(),
+            AccountType::Asset,
+            None,
+            dec!(0),
+        );
+        let bank = Account::new(
+            Uuid::new_v4(),
+            AccountName::new("assets:bank").unwrap(),
+            AccountType::Asset,
+            Some(assets.id()),
+            dec!(10),
+        );
+        let nubank = Account::new(
+            Uuid::new_v4(),
+            AccountName::new("assets:bank:nubank").unwrap(),
+            AccountType::Asset,
+            Some(bank.id()),
+            dec!(32),
+        );
+        let card = Account::new(
+            Uuid::new_v4(),
+            AccountName::new("liabilities").unwrap(),
+            AccountType::Liability,
+            None,
+            dec!(5),
+        );
+
+        let roots = AccountTreeNode::build(vec![nubank, card, bank, assets]);
+
+        assert_eq!(2, roots.len());
+        assert_eq!(dec!(42), roots[0].subtotal());
+        assert_eq!(dec!(42), roots[0].children()[0].subtotal());
+        assert_eq!(dec!(32), roots[0].children()[0].children()[0].subtotal());
+        assert_eq!(dec!(5), roots[1].subtotal());
+    }
+
+    #[test]
+    fn account_types_round_trip_through_strings() {
+        for account_type in AccountType::ALL {
+            assert_eq!(Ok(account_type), account_type.as_str().parse());
+        }
+        assert_eq!(Ok(AccountType::Expense), " Expense ".parse());
+        assert!("savings".parse::<AccountType>().is_err());
+    }
+
+    #[test]
+    fn credit_normal_accounts_have_their_ledger_balance_negated() {
+        let name = AccountName::new("test").unwrap();
+        let asset = Account::from_ledger_balance(
+            Uuid::new_v4(),
+            name.clone(),
+            AccountType::Asset,
+            None,
+            dec!(42),
+        );
+        let liability = Account::from_ledger_balance(
+            Uuid::new_v4(),
+            name,
+            AccountType::Liability,
+            None,
+            dec!(-42),
+        );
+
+        assert_eq!(dec!(42), asset.balance());
+        assert_eq!(dec!(42), liability.balance());
+    }
+
+    #[test]
+    fn zero_balances_are_never_negative() {
+        assert_eq!("0", AccountType::Income.balance(dec!(0)).to_string());
+    }
+
+    #[test]
+    fn account_type_is_serialized_as_type() {
+        let account = Account::new(
+            Uuid::new_v4(),
+            AccountName::new("groceries").unwrap(),
+            AccountType::Expense,
+            None,
+            dec!(0),
+        );
+
+        let json = serde_json::to_value(&account).unwrap();
+
+        assert_eq!("expense", json["type"]);
+    }
 }
@@ -3,9 +3,15 @@ use uuid::Uuid;
 use super::AccountName;
 
 #[derive(Clone, Debug, thiserror::Error)]
-#[error("Account name must not be empty")]
+#[error("Account name and its segments must not be empty")]
 pub struct AccountNameEmptyError;
 
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("\"{raw}\" is not a valid account type")]
+pub struct AccountTypeParseError {
+    pub raw: String,
+}
+
 /// Specifies errors that may arise from interacting with [Account]s
 #[derive(Debug, thiserror::Error)]
 pub enum AccountError {
@@ -75,6 +81,8 @@ pub enum UpdateAccountError {
     NotFound { id: Uuid },
     #[error("account with name \"{name}\" already exists")]
     Duplicate { name: String },
+    #[error("account \"{name}\" cannot be moved under itself")]
+    MovedUnderItself { name: String },
     #[error(transparent)]
     Unknown(#[from] anyhow::Error),
 }
@@ -104,6 +112,8 @@ pub enum RenameAccountError {
 pub enum DeleteAccountError {
     #[error("account with id {id} not found")]
     NotFound { id: Uuid },
+    #[error("account with id {id} still has sub-accounts, transactions, rules or schedules")]
+    InUse { id: Uuid },
     #[error(transparent)]
     Unknown(#[from] anyhow::Error),
 }
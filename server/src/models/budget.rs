@@ -0,0 +1,428 @@
+pub mod errors;
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use chrono::{Datelike as _, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+/// A calendar month, like `2026-03`. Which moments belong to a month depends on the timezone
+/// transactions are looked at in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Month(NaiveDate);
+
+impl Month {
+    pub fn new(year: i32, month: u32) -> Option<Self> {
+        NaiveDate::from_ymd_opt(year, month, 1).map(Self)
+    }
+
+    /// The month the given date falls in
+    pub fn of(date: NaiveDate) -> Self {
+        Self(date.with_day(1).expect("every month has a first day"))
+    }
+
+    pub fn first_day(&self) -> NaiveDate {
+        self.0
+    }
+
+    pub fn next(&self) -> Self {
+        Self(self.0 + Months::new(1))
+    }
+
+    pub fn previous(&self) -> Self {
+        Self(self.0 - Months::new(1))
+    }
+
+    /// How many months there are from this one to `last`, both included, or 0 if `last` is
+    /// earlier
+    pub fn count_until(&self, last: Month) -> u64 {
+        let index = |month: &Month| i64::from(month.0.year()) * 12 + i64::from(month.0.month0());
+        u64::try_from(index(&last) - index(self) + 1).unwrap_or(0)
+    }
+
+    /// The day of the month with the given number, or its last day for numbers past its end,
+    /// like the 28th of February for 31
+    pub fn day(&self, day: u32) -> NaiveDate {
+        // Only the December of the last representable year has no next month
+        let last_day = self
+            .0
+            .checked_add_months(Months::new(1))
+            .map_or(31, |next| (next - chrono::Days::new(1)).day());
+        self.0
+            .with_day(day.clamp(1, last_day))
+            .expect("the day is within the month")
+    }
+}
+
+impl std::fmt::Display for Month {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.format("%Y-%m"))
+    }
+}
+
+impl FromStr for Month {
+    type Err = MonthParseError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        NaiveDate::parse_from_str(&format!("{}-01", raw.trim()), "%Y-%m-%d")
+            .map(Self)
+            .map_err(|_| MonthParseError {
+                raw: raw.to_string(),
+            })
+    }
+}
+
+impl Serialize for Month {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Month {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Month::from_str(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
+/// What a [Budget] limits spending on: either a category, including its subcategories, or an
+/// expense account, including its sub-accounts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum BudgetTarget {
+    #[serde(rename = "category_id")]
+    Category(Uuid),
+    #[serde(rename = "account_id")]
+    Account(Uuid),
+}
+
+/// An amount planned to be spent on a [BudgetTarget] in a given month.
+///
+/// With `rollover`, whatever is left of the month's envelope, be it unspent or overspent, is
+/// carried forward into the next month. Months without a budget of their own keep the
+/// `rollover` setting of the latest budget before them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Budget {
+    id: Uuid,
+    month: Month,
+    #[serde(flatten)]
+    target: BudgetTarget,
+    amount: Decimal,
+    rollover: bool,
+}
+
+impl Budget {
+    pub fn new(
+        id: Uuid,
+        month: Month,
+        target: BudgetTarget,
+        amount: Decimal,
+        rollover: bool,
+    ) -> Self {
+        Self {
+            id,
+            month,
+            target,
+            amount,
+            rollover,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    pub fn target(&self) -> BudgetTarget {
+        self.target
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn rollover(&self) -> bool {
+        self.rollover
+    }
+}
+
+/// The state of the envelope of a [BudgetTarget] in a given month
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetLine {
+    month: Month,
+    #[serde(flatten)]
+    target: BudgetTarget,
+    /// The budget set for this month, if any
+    budget_id: Option<Uuid>,
+    budgeted: Decimal,
+    /// Whether what remains at the end of this month is carried into the next one
+    rollover: bool,
+    /// What remained from the previous month, negative if it was overspent
+    carried_over: Decimal,
+    spent: Decimal,
+    /// `carried_over + budgeted - spent`
+    remaining: Decimal,
+}
+
+impl BudgetLine {
+    /// Compute the envelopes of the given month from all the [Budget]s up to that month and the
+    /// spending per target and month.
+    ///
+    /// A target gets a line if it has a budget in that month, or if its envelope rolls over into
+    /// it from an earlier month.
+    pub fn for_month(
+        month: Month,
+        budgets: &[Budget],
+        spending: &HashMap<(BudgetTarget, Month), Decimal>,
+    ) -> Vec<BudgetLine> {
+        let mut by_target: HashMap<BudgetTarget, HashMap<Month, &Budget>> = HashMap::new();
+        for budget in budgets.iter().filter(|b| b.month() <= month) {
+            by_target
+                .entry(budget.target())
+                .or_default()
+                .insert(budget.month(), budget);
+        }
+
+        let mut lines: Vec<BudgetLine> = by_target
+            .into_iter()
+            .filter_map(|(target, budgets)| {
+                let mut current = *budgets.keys().min()?;
+                let mut carried_over = Decimal::ZERO;
+                let mut rollover = false;
+                loop {
+                    let budget = budgets.get(&current);
+                    if let Some(budget) = budget {
+                        rollover = budget.rollover();
+                    }
+                    let budgeted = budget.map(|b| b.amount()).unwrap_or_default();
+                    let spent = spending
+                        .get(&(target, current))
+                        .copied()
+                        .unwrap_or_default();
+                    let remaining = carried_over + budgeted - spent;
+
+                    if current == month {
+                        return (budget.is_some() || rollover).then_some(BudgetLine {
+                            month,
+                            target,
+                            budget_id: budget.map(|b| b.id()),
+                            budgeted,
+                            rollover,
+                            carried_over,
+                            spent,
+                            remaining,
+                        });
+                    }
+
+                    carried_over = if rollover { remaining } else { Decimal::ZERO };
+                    current = current.next();
+                }
+            })
+            .collect();
+        lines.sort_by_key(|line| line.target);
+
+        lines
+    }
+
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    pub fn target(&self) -> BudgetTarget {
+        self.target
+    }
+
+    pub fn budget_id(&self) -> Option<Uuid> {
+        self.budget_id
+    }
+
+    pub fn budgeted(&self) -> Decimal {
+        self.budgeted
+    }
+
+    pub fn rollover(&self) -> bool {
+        self.rollover
+    }
+
+    pub fn carried_over(&self) -> Decimal {
+        self.carried_over
+    }
+
+    pub fn spent(&self) -> Decimal {
+        self.spent
+    }
+
+    pub fn remaining(&self) -> Decimal {
+        self.remaining
+    }
+}
+
+/// The fields required to create a [Budget]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateBudgetRequest {
+    month: Month,
+    target: BudgetTarget,
+    amount: Decimal,
+    rollover: bool,
+}
+
+impl CreateBudgetRequest {
+    pub fn new(month: Month, target: BudgetTarget, amount: Decimal, rollover: bool) -> Self {
+        Self {
+            month,
+            target,
+            amount,
+            rollover,
+        }
+    }
+
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    pub fn target(&self) -> BudgetTarget {
+        self.target
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn rollover(&self) -> bool {
+        self.rollover
+    }
+}
+
+/// The fields of a [Budget] to change. Fields set to [None] are left as they are.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UpdateBudgetRequest {
+    amount: Option<Decimal>,
+    rollover: Option<bool>,
+}
+
+impl UpdateBudgetRequest {
+    pub fn new(amount: Option<Decimal>, rollover: Option<bool>) -> Self {
+        Self { amount, rollover }
+    }
+
+    pub fn amount(&self) -> Option<Decimal> {
+        self.amount
+    }
+
+    pub fn rollover(&self) -> Option<bool> {
+        self.rollover
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    use super::{Budget, BudgetLine, BudgetTarget, Month};
+
+    fn month(raw: &str) -> Month {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn months_are_parsed_and_displayed_as_year_and_month() {
+        assert_eq!("2026-03", month("2026-03").to_string());
+        assert_eq!(month("2027-01"), month("2026-12").next());
+        assert!("2026-13".parse::<Month>().is_err());
+        assert!("march".parse::<Month>().is_err());
+    }
+
+    #[test]
+    fn months_are_counted_up_to_the_last_one() {
+        assert_eq!(1, month("2026-03").count_until(month("2026-03")));
+        assert_eq!(13, month("2025-12").count_until(month("2026-12")));
+        assert_eq!(0, month("2026-03").count_until(month("2026-02")));
+    }
+
+    #[test]
+    fn budgets_serialize_their_target_as_an_id_field() {
+        let id = Uuid::nil();
+        let budget = Budget::new(
+            id,
+            month("2026-03"),
+            BudgetTarget::Category(id),
+            dec!(10),
+            true,
+        );
+
+        let json = serde_json::to_value(&budget).unwrap();
+
+        assert_eq!("2026-03", json["month"]);
+        assert_eq!(id.to_string(), json["category_id"]);
+        assert_eq!(budget, serde_json::from_value(json).unwrap());
+    }
+
+    #[test]
+    fn envelopes_without_rollover_start_over_every_month() {
+        let target = BudgetTarget::Account(Uuid::new_v4());
+        let budgets = vec![
+            Budget::new(Uuid::new_v4(), month("2026-01"), target, dec!(100), false),
+            Budget::new(Uuid::new_v4(), month("2026-02"), target, dec!(100), false),
+        ];
+        let spending = HashMap::from([((target, month("2026-01")), dec!(30))]);
+
+        let lines = BudgetLine::for_month(month("2026-02"), &budgets, &spending);
+
+        assert_eq!(1, lines.len());
+        assert_eq!(dec!(0), lines[0].carried_over());
+        assert_eq!(dec!(100), lines[0].remaining());
+    }
+
+    #[test]
+    fn envelopes_with_rollover_carry_unspent_and_overspent_amounts() {
+        let target = BudgetTarget::Category(Uuid::new_v4());
+        let budgets = vec![
+            Budget::new(Uuid::new_v4(), month("2026-01"), target, dec!(100), true),
+            Budget::new(Uuid::new_v4(), month("2026-03"), target, dec!(50), true),
+        ];
+        let spending = HashMap::from([
+            ((target, month("2026-01")), dec!(30)),
+            ((target, month("2026-02")), dec!(90)),
+            ((target, month("2026-03")), dec!(10)),
+        ]);
+
+        // February has no budget of its own, but January's rollover carries into it
+        let february = BudgetLine::for_month(month("2026-02"), &budgets, &spending);
+        assert_eq!(None, february[0].budget_id());
+        assert_eq!(dec!(70), february[0].carried_over());
+        assert_eq!(dec!(-20), february[0].remaining());
+
+        let march = BudgetLine::for_month(month("2026-03"), &budgets, &spending);
+        assert_eq!(dec!(-20), march[0].carried_over());
+        assert_eq!(dec!(20), march[0].remaining());
+    }
+
+    #[test]
+    fn budgets_after_the_month_are_ignored() {
+        let target = BudgetTarget::Category(Uuid::new_v4());
+        let budgets = vec![Budget::new(
+            Uuid::new_v4(),
+            month("2026-05"),
+            target,
+            dec!(100),
+            true,
+        )];
+
+        assert!(BudgetLine::for_month(month("2026-04"), &budgets, &HashMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,53 @@
+use uuid::Uuid;
+
+use super::Month;
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("\"{raw}\" is not a valid month, expected a year and a month like 2026-03")]
+pub struct MonthParseError {
+    pub raw: String,
+}
+
+/// Specifies errors that may arise from creating a [Budget]
+#[derive(Debug, thiserror::Error)]
+pub enum CreateBudgetError {
+    #[error("there already is a budget for the same target in {month}")]
+    Duplicate { month: Month },
+    #[error("category with id {id} was not found")]
+    CategoryNotFound { id: Uuid },
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error("account with id {id} is not an expense account")]
+    NotAnExpenseAccount { id: Uuid },
+    #[error("budgeted amounts must not be negative")]
+    NegativeAmount,
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from updating a [Budget]
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateBudgetError {
+    #[error("budget with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error("budgeted amounts must not be negative")]
+    NegativeAmount,
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from deleting a [Budget]
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteBudgetError {
+    #[error("budget with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from computing the [BudgetLine]s of a month
+#[derive(Debug, thiserror::Error)]
+pub enum GetBudgetReportError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
@@ -0,0 +1,121 @@
+pub mod errors;
+
+use axum::Form;
+use derive_more::derive::Display;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::utils::NonemptyStringVisitor;
+
+pub use errors::*;
+
+/// A category classifies [Transaction](crate::models::transaction::Transaction)s, e.g. to report
+/// how much was spent on food. Categories form a hierarchy through their names, just like
+/// accounts do.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Category {
+    id: Uuid,
+    name: CategoryName,
+    /// The category one level above this one in the hierarchy, e.g. `food` is the parent of
+    /// `food:restaurants`.
+    parent_id: Option<Uuid>,
+}
+
+impl Category {
+    pub fn new(id: Uuid, name: CategoryName, parent_id: Option<Uuid>) -> Self {
+        Self {
+            id,
+            name,
+            parent_id,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &CategoryName {
+        &self.name
+    }
+
+    pub fn parent_id(&self) -> Option<Uuid> {
+        self.parent_id
+    }
+}
+
+/// A valid category name. A category name will always be stored as a lowercase string.
+///
+/// Names are hierarchical, with colon-separated segments going from the most general to the most
+/// specific category, like `food:restaurants`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display, Serialize)]
+pub struct CategoryName(String);
+
+impl CategoryName {
+    /// Separates the segments of a hierarchical category name
+    pub const SEPARATOR: char = ':';
+
+    pub fn new(raw: &str) -> Result<Self, CategoryNameEmptyError> {
+        let segments: Vec<&str> = raw.split(Self::SEPARATOR).map(str::trim).collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            Err(CategoryNameEmptyError)
+        } else {
+            Ok(Self(segments.join(":").to_lowercase()))
+        }
+    }
+
+    /// The name of the parent category, or [None] if this is a root category
+    pub fn parent(&self) -> Option<CategoryName> {
+        self.0
+            .rsplit_once(Self::SEPARATOR)
+            .map(|(parent, _)| Self(parent.to_string()))
+    }
+
+    /// The names of all the categories above this one, starting from the root
+    pub fn ancestors(&self) -> Vec<CategoryName> {
+        let mut ancestors = Vec::new();
+        let mut current = self.parent();
+        while let Some(name) = current {
+            current = name.parent();
+            ancestors.push(name);
+        }
+        ancestors.reverse();
+
+        ancestors
+    }
+
+    pub fn into_url_encoding(self) -> String {
+        Form(self).to_string()
+    }
+}
+
+impl<'de> Deserialize<'de> for CategoryName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = deserializer.deserialize_str(NonemptyStringVisitor)?;
+        CategoryName::new(&raw)
+            .map_err(|_| serde::de::Error::custom("category name must be a nonempty string"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CategoryName;
+
+    #[test]
+    fn category_names_are_normalized() {
+        let name = CategoryName::new(" Food : Restaurants").unwrap();
+
+        assert_eq!("food:restaurants", name.to_string());
+        assert_eq!(Some(CategoryName::new("food").unwrap()), name.parent());
+        assert_eq!(vec![CategoryName::new("food").unwrap()], name.ancestors());
+    }
+
+    #[test]
+    fn category_names_must_not_have_empty_segments() {
+        assert!(CategoryName::new("food::restaurants").is_err());
+        assert!(CategoryName::new(":food").is_err());
+        assert!(CategoryName::new("").is_err());
+    }
+}
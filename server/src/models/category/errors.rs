@@ -0,0 +1,56 @@
+use uuid::Uuid;
+
+use super::CategoryName;
+
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("Category name and its segments must not be empty")]
+pub struct CategoryNameEmptyError;
+
+/// Specifies errors that may arise from creating a [Category]
+#[derive(Debug, thiserror::Error)]
+pub enum CreateCategoryError {
+    #[error("category name \"{name}\" is already taken")]
+    Duplicate { name: CategoryName },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from getting a [Category]
+#[derive(Debug, thiserror::Error)]
+pub enum GetCategoryError {
+    #[error("category with id {id} not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from listing [Category]s
+#[derive(Debug, thiserror::Error)]
+pub enum ListCategoriesError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise when renaming a [Category]
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateCategoryError {
+    #[error("category with id {id} not found")]
+    NotFound { id: Uuid },
+    #[error("category with name \"{name}\" already exists")]
+    Duplicate { name: CategoryName },
+    #[error("category \"{name}\" cannot be moved under itself")]
+    MovedUnderItself { name: String },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise when deleting a [Category]
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteCategoryError {
+    #[error("category with id {id} not found")]
+    NotFound { id: Uuid },
+    #[error("category with id {id} still has subcategories, transactions, rules or schedules")]
+    InUse { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
@@ -0,0 +1,399 @@
+pub mod errors;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+use crate::models::budget::Month;
+
+/// The billing cycle and limit of a liability account that is a credit card.
+///
+/// Each statement is named after the month it closes in. It covers the days after the previous
+/// statement closed up to its closing day, and is due on the next due day after that. Days past
+/// the end of a short month fall on its last day.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreditCard {
+    account_id: Uuid,
+    /// The last day of each statement
+    closing_day: u32,
+    /// When each statement must be paid
+    due_day: u32,
+    credit_limit: Decimal,
+    /// The asset account statements are paid from, if known
+    payment_account_id: Option<Uuid>,
+}
+
+impl CreditCard {
+    pub fn new(account_id: Uuid, closing_day: u32, due_day: u32, credit_limit: Decimal) -> Self {
+        Self {
+            account_id,
+            closing_day,
+            due_day,
+            credit_limit,
+            payment_account_id: None,
+        }
+    }
+
+    pub fn with_payment_account(mut self, payment_account_id: Option<Uuid>) -> Self {
+        self.payment_account_id = payment_account_id;
+        self
+    }
+
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn closing_day(&self) -> u32 {
+        self.closing_day
+    }
+
+    pub fn due_day(&self) -> u32 {
+        self.due_day
+    }
+
+    pub fn credit_limit(&self) -> Decimal {
+        self.credit_limit
+    }
+
+    pub fn payment_account_id(&self) -> Option<Uuid> {
+        self.payment_account_id
+    }
+
+    /// The last day of the statement of `month`
+    pub fn closing_date(&self, month: Month) -> NaiveDate {
+        month.day(self.closing_day)
+    }
+
+    /// The first and last days of the statement of `month`
+    pub fn period(&self, month: Month) -> (NaiveDate, NaiveDate) {
+        let previous_closing_date = self.closing_date(month.previous());
+        (
+            previous_closing_date + chrono::Days::new(1),
+            self.closing_date(month),
+        )
+    }
+
+    /// When the statement of `month` must be paid: the first due day after it closes
+    pub fn due_date(&self, month: Month) -> NaiveDate {
+        let due_date = month.day(self.due_day);
+        if due_date > self.closing_date(month) {
+            due_date
+        } else {
+            month.next().day(self.due_day)
+        }
+    }
+
+    /// The statement a posting on `date` is billed in
+    pub fn statement_month(&self, date: NaiveDate) -> Month {
+        let month = Month::of(date);
+        if date <= self.closing_date(month) {
+            month
+        } else {
+            month.next()
+        }
+    }
+}
+
+/// The fields of a [CreditCard], set as a whole
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetCreditCardRequest {
+    closing_day: u32,
+    due_day: u32,
+    credit_limit: Decimal,
+    payment_account_id: Option<Uuid>,
+}
+
+impl SetCreditCardRequest {
+    pub fn new(closing_day: u32, due_day: u32, credit_limit: Decimal) -> Self {
+        Self {
+            closing_day,
+            due_day,
+            credit_limit,
+            payment_account_id: None,
+        }
+    }
+
+    /// Set the asset account statements are paid from
+    pub fn with_payment_account(mut self, payment_account_id: Option<Uuid>) -> Self {
+        self.payment_account_id = payment_account_id;
+        self
+    }
+
+    pub fn closing_day(&self) -> u32 {
+        self.closing_day
+    }
+
+    pub fn due_day(&self) -> u32 {
+        self.due_day
+    }
+
+    pub fn credit_limit(&self) -> Decimal {
+        self.credit_limit
+    }
+
+    pub fn payment_account_id(&self) -> Option<Uuid> {
+        self.payment_account_id
+    }
+}
+
+/// Where a [Statement] stands on a given day
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatementStatus {
+    /// Its period is over and it can be paid
+    Closed,
+    /// Its period includes the day, so it still takes new charges
+    Open,
+    /// Its period has not started, but it may already have charges, like later installments
+    Upcoming,
+}
+
+/// What was charged to a credit card in one billing cycle and what is owed at its end. Amounts
+/// follow the sign convention of liabilities: charges are positive, payments and refunds are
+/// credits.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Statement {
+    month: Month,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    due_date: NaiveDate,
+    status: StatementStatus,
+    /// What was owed when the period started
+    opening_balance: Decimal,
+    charges: Decimal,
+    credits: Decimal,
+    /// `opening_balance + charges - credits`, the amount owed for this statement
+    closing_balance: Decimal,
+}
+
+impl Statement {
+    /// Compute the statement of `month` from the postings of the card, as local posting dates
+    /// and amounts following the sign convention of liabilities.
+    pub fn new(
+        card: &CreditCard,
+        month: Month,
+        postings: &[(NaiveDate, Decimal)],
+        today: NaiveDate,
+    ) -> Self {
+        let (period_start, period_end) = card.period(month);
+
+        let mut opening_balance = Decimal::ZERO;
+        let mut charges = Decimal::ZERO;
+        let mut credits = Decimal::ZERO;
+        for (date, amount) in postings {
+            if *date < period_start {
+                opening_balance += amount;
+            } else if *date <= period_end {
+                if amount.is_sign_positive() {
+                    charges += amount;
+                } else {
+                    credits -= amount;
+                }
+            }
+        }
+
+        let status = if today > period_end {
+            StatementStatus::Closed
+        } else if today >= period_start {
+            StatementStatus::Open
+        } else {
+            StatementStatus::Upcoming
+        };
+
+        Self {
+            month,
+            period_start,
+            period_end,
+            due_date: card.due_date(month),
+            status,
+            opening_balance,
+            charges,
+            credits,
+            closing_balance: opening_balance + charges - credits,
+        }
+    }
+
+    pub fn month(&self) -> Month {
+        self.month
+    }
+
+    pub fn period_start(&self) -> NaiveDate {
+        self.period_start
+    }
+
+    pub fn period_end(&self) -> NaiveDate {
+        self.period_end
+    }
+
+    pub fn due_date(&self) -> NaiveDate {
+        self.due_date
+    }
+
+    pub fn status(&self) -> StatementStatus {
+        self.status
+    }
+
+    pub fn opening_balance(&self) -> Decimal {
+        self.opening_balance
+    }
+
+    pub fn charges(&self) -> Decimal {
+        self.charges
+    }
+
+    pub fn credits(&self) -> Decimal {
+        self.credits
+    }
+
+    pub fn closing_balance(&self) -> Decimal {
+        self.closing_balance
+    }
+
+    /// What is left to pay of the statement on `on`: its closing balance, minus the payments
+    /// and refunds posted after it closed, and never less than zero
+    pub fn amount_due(&self, postings: &[(NaiveDate, Decimal)], on: NaiveDate) -> Decimal {
+        let paid: Decimal = postings
+            .iter()
+            .filter(|(date, amount)| {
+                *date > self.period_end && *date <= on && amount.is_sign_negative()
+            })
+            .map(|(_, amount)| -amount)
+            .sum();
+
+        (self.closing_balance - paid).max(Decimal::ZERO)
+    }
+}
+
+/// The statements of a credit card over a range of months, along with what it still allows to
+/// be spent
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CardStatements {
+    card: CreditCard,
+    /// What is owed on the card, including charges of upcoming statements
+    balance: Decimal,
+    /// `credit_limit - balance`, negative when the card is over its limit
+    available_credit: Decimal,
+    /// The earliest statement first
+    statements: Vec<Statement>,
+}
+
+impl CardStatements {
+    /// The largest number of statements that can be asked for at once
+    pub const MAX_STATEMENTS: usize = 120;
+
+    pub fn new(card: CreditCard, balance: Decimal, statements: Vec<Statement>) -> Self {
+        Self {
+            available_credit: card.credit_limit() - balance,
+            card,
+            balance,
+            statements,
+        }
+    }
+
+    pub fn card(&self) -> &CreditCard {
+        &self.card
+    }
+
+    pub fn balance(&self) -> Decimal {
+        self.balance
+    }
+
+    pub fn available_credit(&self) -> Decimal {
+        self.available_credit
+    }
+
+    pub fn statements(&self) -> &[Statement] {
+        &self.statements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn statements_close_on_the_closing_day_and_are_due_after_it() {
+        let card = CreditCard::new(Uuid::new_v4(), 3, 10, dec!(1000));
+        let march = Month::new(2026, 3).unwrap();
+
+        assert_eq!((date(2026, 2, 4), date(2026, 3, 3)), card.period(march));
+        assert_eq!(date(2026, 3, 10), card.due_date(march));
+        assert_eq!(march, card.statement_month(date(2026, 3, 3)));
+        assert_eq!(march.next(), card.statement_month(date(2026, 3, 4)));
+
+        let card = CreditCard::new(Uuid::new_v4(), 25, 5, dec!(1000));
+        assert_eq!(date(2026, 4, 5), card.due_date(march));
+    }
+
+    #[test]
+    fn days_past_the_end_of_the_month_fall_on_its_last_day() {
+        let card = CreditCard::new(Uuid::new_v4(), 31, 31, dec!(1000));
+        let march = Month::new(2026, 3).unwrap();
+
+        assert_eq!((date(2026, 3, 1), date(2026, 3, 31)), card.period(march));
+        assert_eq!(date(2026, 4, 30), card.due_date(march));
+        assert_eq!(date(2026, 2, 28), card.closing_date(march.previous()));
+    }
+
+    #[test]
+    fn statements_sum_the_postings_of_their_period() {
+        let card = CreditCard::new(Uuid::new_v4(), 3, 10, dec!(1000));
+        let postings = [
+            (date(2026, 1, 20), dec!(100)),
+            (date(2026, 2, 4), dec!(50)),
+            (date(2026, 2, 10), dec!(-100)),
+            (date(2026, 3, 3), dec!(25)),
+            (date(2026, 3, 4), dec!(999)),
+        ];
+
+        let statement = Statement::new(
+            &card,
+            Month::new(2026, 3).unwrap(),
+            &postings,
+            date(2026, 3, 4),
+        );
+
+        assert_eq!(StatementStatus::Closed, statement.status());
+        assert_eq!(dec!(100), statement.opening_balance());
+        assert_eq!(dec!(75), statement.charges());
+        assert_eq!(dec!(100), statement.credits());
+        assert_eq!(dec!(75), statement.closing_balance());
+
+        let next = Statement::new(
+            &card,
+            Month::new(2026, 4).unwrap(),
+            &postings,
+            date(2026, 3, 4),
+        );
+        assert_eq!(StatementStatus::Open, next.status());
+        assert_eq!(dec!(1074), next.closing_balance());
+    }
+
+    #[test]
+    fn payments_after_the_closing_date_reduce_the_amount_due() {
+        let card = CreditCard::new(Uuid::new_v4(), 3, 10, dec!(1000));
+        let postings = [
+            (date(2026, 3, 1), dec!(100)),
+            (date(2026, 3, 5), dec!(-40)),
+            (date(2026, 3, 6), dec!(30)),
+        ];
+        let statement = Statement::new(
+            &card,
+            Month::new(2026, 3).unwrap(),
+            &postings,
+            date(2026, 3, 6),
+        );
+
+        assert_eq!(dec!(60), statement.amount_due(&postings, date(2026, 3, 6)));
+        assert_eq!(dec!(100), statement.amount_due(&postings, date(2026, 3, 4)));
+    }
+}
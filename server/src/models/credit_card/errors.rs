@@ -0,0 +1,37 @@
+use uuid::Uuid;
+
+use crate::models::budget::Month;
+
+/// Specifies errors that may arise from setting the [CreditCard](super::CreditCard) details of
+/// an account
+#[derive(Debug, thiserror::Error)]
+pub enum SetCreditCardError {
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error("account with id {id} is not a liability account")]
+    NotALiabilityAccount { id: Uuid },
+    #[error("account with id {id} is not an asset account")]
+    NotAnAssetAccount { id: Uuid },
+    #[error("{day} is not a day of the month, expected a number from 1 to 31")]
+    InvalidDay { day: u32 },
+    #[error("credit limits must not be negative")]
+    NegativeLimit,
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from computing the [Statement](super::Statement)s of a
+/// credit card
+#[derive(Debug, thiserror::Error)]
+pub enum GetStatementsError {
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error("account with id {id} is not a credit card")]
+    NotACreditCard { id: Uuid },
+    #[error("the range from {from} to {to} is empty")]
+    InvalidRange { from: Month, to: Month },
+    #[error("the range would have {count} statements, but it can have at most {max}")]
+    TooManyStatements { count: u64, max: usize },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
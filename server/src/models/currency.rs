@@ -0,0 +1,397 @@
+pub mod errors;
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+pub use errors::*;
+
+/// What the amounts of an account are counted in: an ISO 4217 currency code, like `BRL` or
+/// `USD`, or the symbol of another commodity, like the ticker `PETR4` of a stock. Symbols are
+/// always stored in uppercase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Currency {
+    /// The symbol, padded with zeros
+    code: [u8; Currency::MAX_LEN],
+    len: u8,
+}
+
+impl Currency {
+    /// The currency of accounts created without one, and of reports by default
+    pub const BRL: Currency = Currency::from_ascii(*b"BRL");
+
+    /// The longest a symbol can be
+    pub const MAX_LEN: usize = 10;
+
+    const fn from_ascii<const N: usize>(symbol: [u8; N]) -> Self {
+        let mut code = [0; Self::MAX_LEN];
+        let mut i = 0;
+        while i < N {
+            code[i] = symbol[i];
+            i += 1;
+        }
+        Self { code, len: N as u8 }
+    }
+
+    /// Parse a currency code or commodity symbol: an uppercase ASCII letter followed by 1 to 9
+    /// uppercase ASCII letters or digits. Lowercase letters are uppercased.
+    pub fn new(raw: &str) -> Result<Self, CurrencyParseError> {
+        let symbol = raw.trim().to_ascii_uppercase();
+        let bytes = symbol.as_bytes();
+        let valid = (2..=Self::MAX_LEN).contains(&bytes.len())
+            && bytes[0].is_ascii_uppercase()
+            && bytes
+                .iter()
+                .all(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit());
+        if !valid {
+            return Err(CurrencyParseError {
+                raw: raw.to_string(),
+            });
+        }
+
+        let mut code = [0; Self::MAX_LEN];
+        code[..bytes.len()].copy_from_slice(bytes);
+        Ok(Self {
+            code,
+            len: bytes.len() as u8,
+        })
+    }
+
+    pub fn code(&self) -> &str {
+        std::str::from_utf8(&self.code[..self.len as usize])
+            .expect("currency codes are ASCII letters and digits")
+    }
+
+    /// How amounts in this currency are usually written, like `R$` for BRL, or its code for
+    /// currencies without a well-known symbol
+    pub fn symbol(&self) -> &str {
+        match self.code() {
+            "BRL" => "R$",
+            "USD" => "US$",
+            "EUR" => "€",
+            "GBP" => "£",
+            "JPY" => "¥",
+            code => code,
+        }
+    }
+
+    /// Write an amount of this currency, like `R$ 12.50`
+    pub fn format(&self, amount: Decimal) -> String {
+        format!("{} {}", self.symbol(), amount.round_dp(2))
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Self::BRL
+    }
+}
+
+impl std::fmt::Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = CurrencyParseError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        Self::new(raw)
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Currency::new(&raw).map_err(serde::de::Error::custom)
+    }
+}
+
+/// How much one unit of `base` is worth in `quote`, like 5.40 BRL per USD
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    base: Currency,
+    quote: Currency,
+    rate: Decimal,
+}
+
+impl ExchangeRate {
+    pub fn new(
+        base: Currency,
+        quote: Currency,
+        rate: Decimal,
+    ) -> Result<Self, InvalidExchangeRateError> {
+        if base == quote {
+            return Err(InvalidExchangeRateError::SameCurrency { currency: base });
+        }
+        if rate <= Decimal::ZERO {
+            return Err(InvalidExchangeRateError::NotPositive { rate });
+        }
+
+        Ok(Self { base, quote, rate })
+    }
+
+    pub fn base(&self) -> Currency {
+        self.base
+    }
+
+    pub fn quote(&self) -> Currency {
+        self.quote
+    }
+
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+
+    /// Whether this rate converts between `a` and `b`, in either direction
+    pub fn converts(&self, a: Currency, b: Currency) -> bool {
+        (self.base, self.quote) == (a, b) || (self.base, self.quote) == (b, a)
+    }
+
+    /// Convert an amount in `from` into the other currency of this rate, or [None] if `from` is
+    /// neither of them
+    pub fn convert(&self, amount: Decimal, from: Currency) -> Option<Decimal> {
+        if from == self.base {
+            Some(amount * self.rate)
+        } else if from == self.quote {
+            Some(amount / self.rate)
+        } else {
+            None
+        }
+    }
+}
+
+/// The [ExchangeRate]s known on given days, to convert amounts between currencies as of a date
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExchangeRates {
+    /// How much one unit of the first currency is worth in the second one, by day
+    rates: HashMap<(Currency, Currency), BTreeMap<NaiveDate, Decimal>>,
+}
+
+impl ExchangeRates {
+    /// Record a rate known on `date`, in both directions. A rate recorded later for the same
+    /// day replaces the earlier one.
+    pub fn insert(&mut self, date: NaiveDate, rate: &ExchangeRate) {
+        self.rates
+            .entry((rate.base(), rate.quote()))
+            .or_default()
+            .insert(date, rate.rate());
+        self.rates
+            .entry((rate.quote(), rate.base()))
+            .or_default()
+            .insert(date, Decimal::ONE / rate.rate());
+    }
+
+    /// How much one unit of `from` is worth in `to` on `date`, going by the latest rate known
+    /// on or before it
+    pub fn rate(&self, from: Currency, to: Currency, date: NaiveDate) -> Option<Decimal> {
+        if from == to {
+            return Some(Decimal::ONE);
+        }
+
+        self.rates
+            .get(&(from, to))?
+            .range(..=date)
+            .next_back()
+            .map(|(_, rate)| *rate)
+    }
+
+    /// The rates to go from `from` to `to` through a third currency on `date`, when there is
+    /// none between them directly, like from PETR4 to BRL and then from BRL to USD. When there
+    /// are several such currencies, the first one in alphabetical order is used.
+    fn rates_through_another_currency(
+        &self,
+        from: Currency,
+        to: Currency,
+        date: NaiveDate,
+    ) -> Option<[Decimal; 2]> {
+        let mut others: Vec<Currency> = self
+            .rates
+            .keys()
+            .filter(|(base, quote)| *base == from && *quote != to)
+            .map(|(_, quote)| *quote)
+            .collect();
+        others.sort();
+
+        others
+            .into_iter()
+            .find_map(|other| Some([self.rate(from, other, date)?, self.rate(other, to, date)?]))
+    }
+
+    /// Convert an amount in `from` into `to` as of `date`, rounded to cents. Zero amounts need
+    /// no rate.
+    ///
+    /// Without a rate between the two currencies, the amount is converted through a third one,
+    /// but never through more than one.
+    ///
+    /// # Errors
+    ///
+    /// - [ConversionError::MissingExchangeRate] if there is no rate between the currencies,
+    ///   either directly or through a third one
+    /// - [ConversionError::Overflow] if the converted amount is too large
+    pub fn convert(
+        &self,
+        amount: Decimal,
+        from: Currency,
+        to: Currency,
+        date: NaiveDate,
+    ) -> Result<Decimal, ConversionError> {
+        if from == to || amount.is_zero() {
+            return Ok(amount);
+        }
+
+        let rates = match self.rate(from, to, date) {
+            Some(rate) => vec![rate],
+            None => self
+                .rates_through_another_currency(from, to, date)
+                .ok_or(ConversionError::MissingExchangeRate { from, to, date })?
+                .to_vec(),
+        };
+
+        rates
+            .into_iter()
+            .try_fold(amount, |amount, rate| amount.checked_mul(rate))
+            .map(|converted| converted.round_dp(2))
+            .ok_or(ConversionError::Overflow { amount, from, to })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn currency(code: &str) -> Currency {
+        code.parse().unwrap()
+    }
+
+    fn date(raw: &str) -> NaiveDate {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn currencies_are_codes_or_commodity_symbols() {
+        assert_eq!("USD", currency(" usd ").to_string());
+        assert_eq!(Currency::BRL, currency("BRL"));
+        assert_eq!("R$ 12.35", Currency::BRL.format(dec!(12.346)));
+        assert_eq!("CHF 1", currency("CHF").format(dec!(1)));
+        assert_eq!("PETR4", currency("petr4").code());
+        assert!(Currency::new("U").is_err());
+        assert!(Currency::new("4USD").is_err());
+        assert!(Currency::new("US$").is_err());
+        assert!(Currency::new("ABCDEFGHIJK").is_err());
+    }
+
+    #[test]
+    fn exchange_rates_convert_both_ways() {
+        let rate = ExchangeRate::new(currency("USD"), Currency::BRL, dec!(5)).unwrap();
+
+        assert_eq!(Some(dec!(50)), rate.convert(dec!(10), currency("USD")));
+        assert_eq!(Some(dec!(2)), rate.convert(dec!(10), Currency::BRL));
+        assert_eq!(None, rate.convert(dec!(10), currency("EUR")));
+        assert!(ExchangeRate::new(Currency::BRL, Currency::BRL, dec!(1)).is_err());
+        assert!(ExchangeRate::new(currency("USD"), Currency::BRL, dec!(0)).is_err());
+    }
+
+    #[test]
+    fn conversions_use_the_latest_rate_on_or_before_the_date() {
+        let usd = currency("USD");
+        let mut rates = ExchangeRates::default();
+        rates.insert(
+            date("2026-01-10"),
+            &ExchangeRate::new(usd, Currency::BRL, dec!(5)).unwrap(),
+        );
+        rates.insert(
+            date("2026-02-10"),
+            &ExchangeRate::new(Currency::BRL, usd, dec!(0.25)).unwrap(),
+        );
+
+        assert_eq!(
+            Ok(dec!(50)),
+            rates.convert(dec!(10), usd, Currency::BRL, date("2026-02-09"))
+        );
+        assert_eq!(
+            Ok(dec!(40)),
+            rates.convert(dec!(10), usd, Currency::BRL, date("2026-03-01"))
+        );
+        assert_eq!(
+            Ok(dec!(0)),
+            rates.convert(dec!(0), usd, Currency::BRL, date("2026-01-01"))
+        );
+        assert_eq!(
+            Err(ConversionError::MissingExchangeRate {
+                from: usd,
+                to: Currency::BRL,
+                date: date("2026-01-01"),
+            }),
+            rates.convert(dec!(10), usd, Currency::BRL, date("2026-01-01"))
+        );
+    }
+
+    #[test]
+    fn conversions_go_through_another_currency_when_there_is_no_direct_rate() {
+        let (usd, petr4) = (currency("USD"), currency("PETR4"));
+        let mut rates = ExchangeRates::default();
+        rates.insert(
+            date("2026-01-10"),
+            &ExchangeRate::new(petr4, Currency::BRL, dec!(40)).unwrap(),
+        );
+        rates.insert(
+            date("2026-01-10"),
+            &ExchangeRate::new(usd, Currency::BRL, dec!(5)).unwrap(),
+        );
+
+        assert_eq!(
+            Ok(dec!(80)),
+            rates.convert(dec!(10), petr4, usd, date("2026-01-10"))
+        );
+        assert_eq!(
+            Ok(dec!(2)),
+            rates.convert(dec!(16), usd, petr4, date("2026-01-10"))
+        );
+        assert_eq!(
+            Err(ConversionError::MissingExchangeRate {
+                from: petr4,
+                to: currency("EUR"),
+                date: date("2026-01-10"),
+            }),
+            rates.convert(dec!(10), petr4, currency("EUR"), date("2026-01-10"))
+        );
+    }
+
+    #[test]
+    fn conversions_that_overflow_are_errors() {
+        let usd = currency("USD");
+        let mut rates = ExchangeRates::default();
+        rates.insert(
+            date("2026-01-10"),
+            &ExchangeRate::new(usd, Currency::BRL, dec!(1000)).unwrap(),
+        );
+
+        assert_eq!(
+            Err(ConversionError::Overflow {
+                amount: Decimal::MAX,
+                from: usd,
+                to: Currency::BRL,
+            }),
+            rates.convert(Decimal::MAX, usd, Currency::BRL, date("2026-01-10"))
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use super::Currency;
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("\"{raw}\" is not a currency, expected a code like BRL or USD, or a symbol like PETR4")]
+pub struct CurrencyParseError {
+    pub raw: String,
+}
+
+/// Specifies why an [ExchangeRate](super::ExchangeRate) is not valid
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidExchangeRateError {
+    #[error(
+        "an exchange rate must convert between two different currencies, not {currency} to itself"
+    )]
+    SameCurrency { currency: Currency },
+    #[error("exchange rates must be positive, not {rate}")]
+    NotPositive { rate: Decimal },
+}
+
+/// Specifies why an amount can't be converted between currencies by
+/// [ExchangeRates::convert](super::ExchangeRates::convert)
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ConversionError {
+    #[error(
+        "there is no exchange rate from {from} to {to} on or before {date}, either directly or through another currency"
+    )]
+    MissingExchangeRate {
+        from: Currency,
+        to: Currency,
+        date: NaiveDate,
+    },
+    #[error("converting {amount} {from} into {to} overflows")]
+    Overflow {
+        amount: Decimal,
+        from: Currency,
+        to: Currency,
+    },
+}
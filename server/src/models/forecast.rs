@@ -0,0 +1,287 @@
+pub mod errors;
+
+use chrono::{Days, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+use crate::models::account::AccountName;
+use crate::models::budget::Month;
+
+/// The most days a forecast can look ahead
+pub const MAX_FORECAST_DAYS: u32 = 366;
+
+/// Where a change of a forecast balance comes from
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum ForecastSource {
+    /// A transaction already posted with a future date, like a later installment
+    Transaction { transaction_id: Uuid },
+    /// An occurrence of a schedule that is not posted yet
+    Schedule { schedule_id: Uuid, date: NaiveDate },
+    /// The payment of a credit card statement on its due date
+    CardStatement { account_id: Uuid, month: Month },
+}
+
+/// Money expected to come into or leave an account on a given day
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForecastChange {
+    date: NaiveDate,
+    #[serde(flatten)]
+    source: ForecastSource,
+    title: String,
+    /// Positive when money comes into the account
+    amount: Decimal,
+}
+
+impl ForecastChange {
+    pub fn new(
+        date: NaiveDate,
+        source: ForecastSource,
+        title: impl Into<String>,
+        amount: Decimal,
+    ) -> Self {
+        Self {
+            date,
+            source,
+            title: title.into(),
+            amount,
+        }
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn source(&self) -> &ForecastSource {
+        &self.source
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+}
+
+/// The projected balance of an account at the end of a day, and what changes it that day
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForecastDay {
+    date: NaiveDate,
+    balance: Decimal,
+    changes: Vec<ForecastChange>,
+}
+
+impl ForecastDay {
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn balance(&self) -> Decimal {
+        self.balance
+    }
+
+    pub fn changes(&self) -> &[ForecastChange] {
+        &self.changes
+    }
+}
+
+/// The balance of an asset account projected day by day
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountForecast {
+    account_id: Uuid,
+    account_name: AccountName,
+    /// The balance at the end of today, leaving out transactions posted with later dates
+    opening_balance: Decimal,
+    days: Vec<ForecastDay>,
+    lowest_balance: Decimal,
+    /// The first day the balance ends below the threshold, if any
+    below_threshold_on: Option<NaiveDate>,
+}
+
+impl AccountForecast {
+    /// Project `opening_balance` over the days from `from` to `until`, both included, applying
+    /// each change on its day. Changes before `from`, like late occurrences of schedules, are
+    /// applied on `from`.
+    pub fn new(
+        account_id: Uuid,
+        account_name: AccountName,
+        opening_balance: Decimal,
+        from: NaiveDate,
+        until: NaiveDate,
+        mut changes: Vec<ForecastChange>,
+        threshold: Decimal,
+    ) -> Self {
+        changes.sort_by_key(ForecastChange::date);
+        let mut changes = changes.into_iter().peekable();
+
+        let mut balance = opening_balance;
+        let mut lowest_balance = opening_balance;
+        let mut below_threshold_on = None;
+        let mut days = Vec::new();
+        let mut date = from;
+        while date <= until {
+            let mut day_changes = Vec::new();
+            while let Some(change) = changes.next_if(|change| change.date() <= date) {
+                balance += change.amount();
+                day_changes.push(change);
+            }
+
+            lowest_balance = lowest_balance.min(balance);
+            if balance < threshold && below_threshold_on.is_none() {
+                below_threshold_on = Some(date);
+            }
+            days.push(ForecastDay {
+                date,
+                balance,
+                changes: day_changes,
+            });
+            date = date + Days::new(1);
+        }
+
+        Self {
+            account_id,
+            account_name,
+            opening_balance,
+            days,
+            lowest_balance,
+            below_threshold_on,
+        }
+    }
+
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn account_name(&self) -> &AccountName {
+        &self.account_name
+    }
+
+    pub fn opening_balance(&self) -> Decimal {
+        self.opening_balance
+    }
+
+    pub fn days(&self) -> &[ForecastDay] {
+        &self.days
+    }
+
+    pub fn lowest_balance(&self) -> Decimal {
+        self.lowest_balance
+    }
+
+    pub fn below_threshold_on(&self) -> Option<NaiveDate> {
+        self.below_threshold_on
+    }
+}
+
+/// The projected balances of asset accounts over the coming days, from transactions posted with
+/// future dates, upcoming occurrences of schedules and credit card statements coming due
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CashFlowForecast {
+    /// The first day of the forecast, tomorrow
+    from: NaiveDate,
+    until: NaiveDate,
+    /// Balances below this are flagged
+    threshold: Decimal,
+    accounts: Vec<AccountForecast>,
+}
+
+impl CashFlowForecast {
+    pub fn new(
+        from: NaiveDate,
+        until: NaiveDate,
+        threshold: Decimal,
+        accounts: Vec<AccountForecast>,
+    ) -> Self {
+        Self {
+            from,
+            until,
+            threshold,
+            accounts,
+        }
+    }
+
+    pub fn from(&self) -> NaiveDate {
+        self.from
+    }
+
+    pub fn until(&self) -> NaiveDate {
+        self.until
+    }
+
+    pub fn threshold(&self) -> Decimal {
+        self.threshold
+    }
+
+    pub fn accounts(&self) -> &[AccountForecast] {
+        &self.accounts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, day).unwrap()
+    }
+
+    fn change(day: u32, amount: Decimal) -> ForecastChange {
+        ForecastChange::new(
+            date(day),
+            ForecastSource::Transaction {
+                transaction_id: Uuid::new_v4(),
+            },
+            "Change",
+            amount,
+        )
+    }
+
+    #[test]
+    fn forecasts_flag_the_first_day_below_the_threshold() {
+        let forecast = AccountForecast::new(
+            Uuid::new_v4(),
+            AccountName::new("assets:checking").unwrap(),
+            dec!(100),
+            date(2),
+            date(6),
+            vec![
+                change(5, dec!(1000)),
+                change(3, dec!(-150)),
+                change(1, dec!(-20)),
+            ],
+            Decimal::ZERO,
+        );
+
+        let balances: Vec<_> = forecast.days().iter().map(ForecastDay::balance).collect();
+        assert_eq!(
+            vec![dec!(80), dec!(-70), dec!(-70), dec!(930), dec!(930)],
+            balances
+        );
+        assert_eq!(1, forecast.days()[0].changes().len());
+        assert_eq!(dec!(-70), forecast.lowest_balance());
+        assert_eq!(Some(date(3)), forecast.below_threshold_on());
+    }
+
+    #[test]
+    fn forecasts_staying_above_the_threshold_are_not_flagged() {
+        let forecast = AccountForecast::new(
+            Uuid::new_v4(),
+            AccountName::new("assets:checking").unwrap(),
+            dec!(100),
+            date(2),
+            date(4),
+            vec![change(3, dec!(-50))],
+            dec!(10),
+        );
+
+        assert_eq!(dec!(50), forecast.lowest_balance());
+        assert_eq!(None, forecast.below_threshold_on());
+    }
+}
@@ -0,0 +1,14 @@
+use uuid::Uuid;
+
+/// Specifies errors that may arise from building a [CashFlowForecast](super::CashFlowForecast)
+#[derive(Debug, thiserror::Error)]
+pub enum GetCashFlowForecastError {
+    #[error("a forecast must look between 1 and {max} days ahead, not {days}")]
+    InvalidDays { days: u32, max: u32 },
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error("account with id {id} is not an asset account")]
+    NotAnAssetAccount { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
@@ -0,0 +1,359 @@
+pub mod errors;
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+use crate::configuration::ImportSettings;
+use crate::import::{ImportRow, csv::CsvProfile};
+use crate::models::account::{AccountType, CreateAccountRequest};
+
+/// The format of an imported file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportFormat {
+    /// A CSV export, read according to an import profile
+    Csv,
+    /// An OFX or QFX bank or credit card statement
+    Ofx,
+}
+
+impl ImportFormat {
+    pub const ALL: [ImportFormat; 2] = [ImportFormat::Csv, ImportFormat::Ofx];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFormat::Csv => "csv",
+            ImportFormat::Ofx => "ofx",
+        }
+    }
+}
+
+impl std::fmt::Display for ImportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = ImportFormatParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ImportFormat::ALL
+            .into_iter()
+            .find(|f| f.as_str().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| ImportFormatParseError { raw: s.to_string() })
+    }
+}
+
+/// One import of a file. The transactions it created are tagged with its id, so that they can
+/// be rolled back together.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportBatch {
+    id: Uuid,
+    /// The name of the imported file
+    source: String,
+    format: ImportFormat,
+    /// The import profile CSV files were read with
+    profile: Option<String>,
+    /// The account the file's transactions were imported into
+    account_id: Uuid,
+    /// How many rows the file had, whether they were imported or not
+    row_count: i64,
+    /// How many transactions of the batch still exist
+    transaction_count: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl ImportBatch {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Uuid,
+        source: String,
+        format: ImportFormat,
+        profile: Option<String>,
+        account_id: Uuid,
+        row_count: i64,
+        transaction_count: i64,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            source,
+            format,
+            profile,
+            account_id,
+            row_count,
+            transaction_count,
+            created_at,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn format(&self) -> ImportFormat {
+        self.format
+    }
+
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn row_count(&self) -> i64 {
+        self.row_count
+    }
+
+    pub fn transaction_count(&self) -> i64 {
+        self.transaction_count
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+}
+
+/// The fields required to import a file
+#[derive(Clone, Debug)]
+pub struct ImportRequest {
+    /// The name of the file
+    source: String,
+    format: ImportFormat,
+    /// The name of the import profile and the profile itself, needed for CSV files
+    profile: Option<(String, CsvProfile)>,
+    /// The account the transactions are imported into, created if it does not exist yet
+    account: CreateAccountRequest,
+    contents: Vec<u8>,
+    dry_run: bool,
+    /// Where money going out of and coming into the account is booked
+    settings: ImportSettings,
+}
+
+impl ImportRequest {
+    pub fn new(
+        source: impl Into<String>,
+        format: ImportFormat,
+        account: CreateAccountRequest,
+        contents: Vec<u8>,
+    ) -> Self {
+        Self {
+            source: source.into(),
+            format,
+            profile: None,
+            account,
+            contents,
+            dry_run: false,
+            settings: ImportSettings::default(),
+        }
+    }
+
+    /// Set the accounts on the other side of the imported transactions
+    pub fn with_settings(mut self, settings: ImportSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Set the import profile CSV files are read with
+    pub fn with_profile(mut self, name: impl Into<String>, profile: CsvProfile) -> Self {
+        self.profile = Some((name.into(), profile));
+        self
+    }
+
+    /// Only report what importing the file would do, without writing anything
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn format(&self) -> ImportFormat {
+        self.format
+    }
+
+    pub fn profile_name(&self) -> Option<&str> {
+        self.profile.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    pub fn profile(&self) -> Option<&CsvProfile> {
+        self.profile.as_ref().map(|(_, profile)| profile)
+    }
+
+    pub fn account(&self) -> &CreateAccountRequest {
+        &self.account
+    }
+
+    pub fn contents(&self) -> &[u8] {
+        &self.contents
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// The account on the other side of an imported row: the default expense account for money
+    /// leaving the imported account, and the default income account otherwise
+    pub fn counterpart(&self, row: &ImportRow) -> CreateAccountRequest {
+        match row.counterpart_type() {
+            AccountType::Expense => CreateAccountRequest::new(
+                self.settings.default_expense_account.clone(),
+                AccountType::Expense,
+            ),
+            account_type => CreateAccountRequest::new(
+                self.settings.default_income_account.clone(),
+                account_type,
+            ),
+        }
+    }
+}
+
+/// What happened to a row of an imported file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ImportRowStatus {
+    /// The row became a new transaction, or would have on a dry run
+    Created { transaction_id: Option<Uuid> },
+    /// The row had already been imported
+    Duplicate { fingerprint: String },
+    /// The row could not be read or saved
+    Failed { error: String },
+}
+
+/// The outcome of importing a row of a file, along with what could be read of it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportRowResult {
+    /// The line of the file the row is on, for formats that have lines
+    line: Option<u64>,
+    posting_date: Option<DateTime<Utc>>,
+    title: Option<String>,
+    /// Positive amounts are money coming into the account, negative ones are money leaving it
+    amount: Option<Decimal>,
+    #[serde(flatten)]
+    status: ImportRowStatus,
+}
+
+impl ImportRowResult {
+    pub fn new(
+        line: Option<u64>,
+        posting_date: Option<DateTime<Utc>>,
+        title: Option<String>,
+        amount: Option<Decimal>,
+        status: ImportRowStatus,
+    ) -> Self {
+        Self {
+            line,
+            posting_date,
+            title,
+            amount,
+            status,
+        }
+    }
+
+    pub fn line(&self) -> Option<u64> {
+        self.line
+    }
+
+    pub fn posting_date(&self) -> Option<DateTime<Utc>> {
+        self.posting_date
+    }
+
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    pub fn amount(&self) -> Option<Decimal> {
+        self.amount
+    }
+
+    pub fn status(&self) -> &ImportRowStatus {
+        &self.status
+    }
+}
+
+/// Counts of what happened to the rows of an imported file
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSummary {
+    /// Rows turned into new transactions
+    pub created: usize,
+    /// Rows skipped because they had already been imported
+    pub duplicates: usize,
+    /// Rows that could not be read or saved
+    pub failed: usize,
+}
+
+impl ImportSummary {
+    pub fn count(rows: &[ImportRowResult]) -> Self {
+        let mut summary = Self::default();
+        for row in rows {
+            match row.status {
+                ImportRowStatus::Created { .. } => summary.created += 1,
+                ImportRowStatus::Duplicate { .. } => summary.duplicates += 1,
+                ImportRowStatus::Failed { .. } => summary.failed += 1,
+            }
+        }
+        summary
+    }
+}
+
+impl std::fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} new, {} duplicate, {} failed",
+            self.created, self.duplicates, self.failed
+        )
+    }
+}
+
+/// The outcome of importing a file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportReport {
+    /// The batch the created transactions belong to, which dry runs do not record
+    batch: Option<ImportBatch>,
+    dry_run: bool,
+    summary: ImportSummary,
+    rows: Vec<ImportRowResult>,
+}
+
+impl ImportReport {
+    pub fn new(batch: Option<ImportBatch>, dry_run: bool, rows: Vec<ImportRowResult>) -> Self {
+        Self {
+            batch,
+            dry_run,
+            summary: ImportSummary::count(&rows),
+            rows,
+        }
+    }
+
+    pub fn batch(&self) -> Option<&ImportBatch> {
+        self.batch.as_ref()
+    }
+
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    pub fn summary(&self) -> ImportSummary {
+        self.summary
+    }
+
+    pub fn rows(&self) -> &[ImportRowResult] {
+        &self.rows
+    }
+}
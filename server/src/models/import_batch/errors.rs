@@ -0,0 +1,38 @@
+use uuid::Uuid;
+
+use crate::import::{csv::CsvImportError, ofx::OfxParseError};
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("\"{raw}\" is not a valid import format, expected csv or ofx")]
+pub struct ImportFormatParseError {
+    pub raw: String,
+}
+
+/// Specifies errors that may arise from importing a file
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("CSV files need an import profile")]
+    MissingProfile,
+    #[error(transparent)]
+    Csv(#[from] CsvImportError),
+    #[error(transparent)]
+    Ofx(#[from] OfxParseError),
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from listing [ImportBatch](super::ImportBatch)es
+#[derive(Debug, thiserror::Error)]
+pub enum ListImportBatchesError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from rolling back an [ImportBatch](super::ImportBatch)
+#[derive(Debug, thiserror::Error)]
+pub enum RollbackImportBatchError {
+    #[error("import batch with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
@@ -0,0 +1,323 @@
+pub mod errors;
+
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Months, Utc};
+use regex::Regex;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+use crate::models::transaction::TransactionTitle;
+
+/// The most installments a purchase can be split into
+pub const MAX_INSTALLMENTS: u32 = 120;
+
+/// A purchase paid in monthly installments, each of them a [Transaction] of its own, like the
+/// "parcela 3/10" lines of a credit card statement.
+///
+/// [Transaction]: crate::models::transaction::Transaction
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct InstallmentPlan {
+    id: Uuid,
+    title: TransactionTitle,
+    /// The account paying the installments, like a credit card
+    account_id: Uuid,
+    /// The price of the whole purchase
+    total_amount: Decimal,
+    installment_count: u32,
+    /// When the first installment is posted, the others following one month apart
+    first_posting_date: DateTime<Utc>,
+}
+
+impl InstallmentPlan {
+    pub fn new(
+        id: Uuid,
+        title: TransactionTitle,
+        account_id: Uuid,
+        total_amount: Decimal,
+        installment_count: u32,
+        first_posting_date: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            title,
+            account_id,
+            total_amount,
+            installment_count,
+            first_posting_date,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn title(&self) -> &TransactionTitle {
+        &self.title
+    }
+
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn total_amount(&self) -> Decimal {
+        self.total_amount
+    }
+
+    pub fn installment_count(&self) -> u32 {
+        self.installment_count
+    }
+
+    pub fn first_posting_date(&self) -> DateTime<Utc> {
+        self.first_posting_date
+    }
+
+    /// The amount of the installment with the given number, starting at 1, or [None] if the plan
+    /// has no such installment. See [split].
+    pub fn installment_amount(&self, number: u32) -> Option<Decimal> {
+        let index = number.checked_sub(1)?;
+        split(self.total_amount, self.installment_count)
+            .get(index as usize)
+            .copied()
+    }
+
+    /// When the installment with the given number, starting at 1, is posted
+    pub fn posting_date(&self, number: u32) -> DateTime<Utc> {
+        self.first_posting_date + Months::new(number.max(1) - 1)
+    }
+}
+
+/// Split `amount` into `count` installments of whole cents. The first installment takes what
+/// is left after rounding, as card issuers do, so that the installments add up to `amount`.
+pub fn split(amount: Decimal, count: u32) -> Vec<Decimal> {
+    let count = count.max(1);
+    let installment = (amount / Decimal::from(count)).round_dp(2);
+    let first = amount - installment * Decimal::from(count - 1);
+
+    std::iter::once(first)
+        .chain(std::iter::repeat_n(installment, count as usize - 1))
+        .collect()
+}
+
+/// Which installment of an [InstallmentPlan] a transaction is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Installment {
+    pub plan_id: Uuid,
+    /// Starts at 1
+    pub number: u32,
+}
+
+/// Installment markers at the end of a title or after the word "parcela", like
+/// "LOJA X PARCELA 3/10", "LOJA X - Parc 03 de 10" or "LOJA X 03/10"
+static MARKER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(\bparcela|\bparc\.?)\s*(\d{1,3})\s*(/|de)\s*(\d{1,3})\b|\s(\d{1,3})/(\d{1,3})\s*$",
+    )
+    .unwrap()
+});
+
+/// An installment marker read from the title of an imported row
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstallmentMarker {
+    /// Starts at 1
+    pub number: u32,
+    pub count: u32,
+    /// The title without the marker, which the installments of a purchase share
+    pub title: String,
+}
+
+impl InstallmentMarker {
+    /// Find the installment marker of a title. Markers numbering an installment beyond the
+    /// count, or with a single installment, are not markers, but likely dates.
+    pub fn parse(title: &str) -> Option<Self> {
+        let captures = MARKER.captures(title)?;
+        let (number, count) = match (captures.get(2), captures.get(4)) {
+            (Some(number), Some(count)) => (number, count),
+            _ => (captures.get(5)?, captures.get(6)?),
+        };
+        let number: u32 = number.as_str().parse().ok()?;
+        let count: u32 = count.as_str().parse().ok()?;
+        if !(2..=MAX_INSTALLMENTS).contains(&count) || number == 0 || number > count {
+            return None;
+        }
+
+        let marker = captures.get(0)?;
+        let title = format!("{}{}", &title[..marker.start()], &title[marker.end()..]);
+        let title = title
+            .trim_matches(|c: char| c == '-' || c.is_whitespace())
+            .to_string();
+
+        Some(Self {
+            number,
+            count,
+            title,
+        })
+    }
+}
+
+/// What is left to pay of an [InstallmentPlan]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallmentCommitment {
+    plan: InstallmentPlan,
+    /// How many installments are posted after the moment the commitment was computed at
+    remaining_count: u32,
+    remaining_amount: Decimal,
+    next_posting_date: Option<DateTime<Utc>>,
+}
+
+impl InstallmentCommitment {
+    /// What is left to pay of `plan` after `now`
+    pub fn new(plan: InstallmentPlan, now: DateTime<Utc>) -> Self {
+        let remaining: Vec<u32> = (1..=plan.installment_count())
+            .filter(|number| plan.posting_date(*number) > now)
+            .collect();
+
+        Self {
+            remaining_count: remaining.len() as u32,
+            remaining_amount: remaining
+                .iter()
+                .filter_map(|number| plan.installment_amount(*number))
+                .sum(),
+            next_posting_date: remaining.first().map(|number| plan.posting_date(*number)),
+            plan,
+        }
+    }
+
+    pub fn plan(&self) -> &InstallmentPlan {
+        &self.plan
+    }
+
+    pub fn remaining_count(&self) -> u32 {
+        self.remaining_count
+    }
+
+    pub fn remaining_amount(&self) -> Decimal {
+        self.remaining_amount
+    }
+
+    pub fn next_posting_date(&self) -> Option<DateTime<Utc>> {
+        self.next_posting_date
+    }
+}
+
+/// The installments an account is still committed to pay
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallmentCommitments {
+    account_id: Uuid,
+    /// What is left to pay across all plans
+    remaining_amount: Decimal,
+    /// The plans with installments left, the ones ending first first
+    plans: Vec<InstallmentCommitment>,
+}
+
+impl InstallmentCommitments {
+    pub fn new(account_id: Uuid, plans: Vec<InstallmentCommitment>) -> Self {
+        Self {
+            account_id,
+            remaining_amount: plans
+                .iter()
+                .map(InstallmentCommitment::remaining_amount)
+                .sum(),
+            plans,
+        }
+    }
+
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn remaining_amount(&self) -> Decimal {
+        self.remaining_amount
+    }
+
+    pub fn plans(&self) -> &[InstallmentCommitment] {
+        &self.plans
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn split_puts_the_rounding_on_the_first_installment() {
+        assert_eq!(
+            vec![dec!(33.34), dec!(33.33), dec!(33.33)],
+            split(dec!(100), 3)
+        );
+        assert_eq!(vec![dec!(50), dec!(50)], split(dec!(100), 2));
+        assert_eq!(vec![dec!(7)], split(dec!(7), 1));
+    }
+
+    #[test]
+    fn plans_only_have_amounts_for_their_installments() {
+        let plan = InstallmentPlan::new(
+            Uuid::new_v4(),
+            TransactionTitle::new("Loja X").unwrap(),
+            Uuid::new_v4(),
+            dec!(100),
+            3,
+            Utc.with_ymd_and_hms(2026, 1, 10, 3, 0, 0).unwrap(),
+        );
+
+        assert_eq!(Some(dec!(33.34)), plan.installment_amount(1));
+        assert_eq!(Some(dec!(33.33)), plan.installment_amount(3));
+        assert_eq!(None, plan.installment_amount(0));
+        assert_eq!(None, plan.installment_amount(4));
+    }
+
+    #[test]
+    fn parse_reads_installment_markers() {
+        let marker = |number, count, title: &str| {
+            Some(InstallmentMarker {
+                number,
+                count,
+                title: title.to_string(),
+            })
+        };
+
+        assert_eq!(
+            marker(3, 10, "LOJA X"),
+            InstallmentMarker::parse("LOJA X PARCELA 3/10")
+        );
+        assert_eq!(
+            marker(2, 12, "Loja X"),
+            InstallmentMarker::parse("Loja X - Parc 02 de 12")
+        );
+        assert_eq!(
+            marker(1, 6, "LOJA X"),
+            InstallmentMarker::parse("LOJA X 01/06")
+        );
+        assert_eq!(None, InstallmentMarker::parse("LOJA X 10/04"));
+        assert_eq!(None, InstallmentMarker::parse("UBER *TRIP"));
+    }
+
+    #[test]
+    fn commitments_count_the_installments_left() {
+        let first_posting_date = Utc.with_ymd_and_hms(2026, 1, 10, 3, 0, 0).unwrap();
+        let plan = InstallmentPlan::new(
+            Uuid::new_v4(),
+            TransactionTitle::new("Loja X").unwrap(),
+            Uuid::new_v4(),
+            dec!(100),
+            3,
+            first_posting_date,
+        );
+
+        let commitment =
+            InstallmentCommitment::new(plan, Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+
+        assert_eq!(2, commitment.remaining_count());
+        assert_eq!(dec!(66.66), commitment.remaining_amount());
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 2, 10, 3, 0, 0).unwrap()),
+            commitment.next_posting_date()
+        );
+    }
+}
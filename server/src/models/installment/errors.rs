@@ -0,0 +1,11 @@
+use uuid::Uuid;
+
+/// Specifies errors that may arise from looking up what is left to pay of the
+/// [InstallmentPlan](super::InstallmentPlan)s of an account
+#[derive(Debug, thiserror::Error)]
+pub enum GetInstallmentCommitmentsError {
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
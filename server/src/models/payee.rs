@@ -0,0 +1,130 @@
+pub mod errors;
+
+use std::sync::LazyLock;
+
+use derive_more::derive::Display;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+/// Who a [Transaction](crate::models::transaction::Transaction) was paid to or received from,
+/// like a merchant. Imported transactions get the payee their title normalizes to, so that
+/// "UBER *TRIP 1234" and "UBER *TRIP 5678" share the payee "Uber Trip".
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Payee {
+    id: Uuid,
+    name: PayeeName,
+}
+
+impl Payee {
+    pub fn new(id: Uuid, name: PayeeName) -> Self {
+        Self { id, name }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &PayeeName {
+        &self.name
+    }
+}
+
+/// Installment markers, like "Parcela 2/10", "PARC 02/10" or a bare "2/10"
+static INSTALLMENT: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(parcela|parc\.?)?\s*\d{1,3}\s*(/|de)\s*\d{1,3}\b").unwrap()
+});
+
+/// Words at the end of a title holding digits, like order numbers or card terminal ids
+static TRAILING_IDS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\s+[^\s]*\d[^\s]*)+\s*$").unwrap());
+
+/// The normalized name of a [Payee]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Display, Serialize, Deserialize)]
+pub struct PayeeName(String);
+
+impl PayeeName {
+    /// Normalize the title of a transaction into the name of its payee: installment markers and
+    /// trailing ids are stripped, as well as the separators banks put between a payment
+    /// processor and a merchant, and every word is capitalized.
+    ///
+    /// Returns [None] for blank titles. A title that is nothing but ids is kept as is.
+    pub fn from_title(title: &str) -> Option<Self> {
+        let stripped = INSTALLMENT.replace_all(title, " ");
+        let stripped = TRAILING_IDS.replace(&stripped, "");
+        let name = title_case(&stripped.replace(['*', '#', '_'], " "));
+        let name = name.trim_matches(|c: char| c == '-' || c == '.' || c.is_whitespace());
+
+        if !name.is_empty() {
+            Some(Self(name.to_string()))
+        } else if !title.trim().is_empty() {
+            Some(Self(title_case(title)))
+        } else {
+            None
+        }
+    }
+
+    /// A name that was already normalized, like one read from the database
+    pub fn from_stored(name: String) -> Self {
+        Self(name)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Collapse whitespace and capitalize the first letter of every word, lowercasing the others
+fn title_case(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first
+                    .to_uppercase()
+                    .chain(chars.flat_map(char::to_lowercase))
+                    .collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PayeeName;
+
+    fn normalize(title: &str) -> Option<String> {
+        PayeeName::from_title(title).map(|name| name.as_str().to_string())
+    }
+
+    #[test]
+    fn trailing_ids_and_separators_are_stripped() {
+        assert_eq!(Some("Uber Trip".to_string()), normalize("UBER *TRIP 1234"));
+        assert_eq!(Some("Uber Trip".to_string()), normalize("uber *trip 5678"));
+        assert_eq!(
+            Some("Padaria Do Joao".to_string()),
+            normalize("PADARIA DO JOAO 01234 SP12")
+        );
+        assert_eq!(Some("Ifood".to_string()), normalize("IFOOD   #A1B2C3"));
+    }
+
+    #[test]
+    fn installment_markers_are_stripped() {
+        assert_eq!(
+            Some("Magazine Luiza".to_string()),
+            normalize("MAGAZINE LUIZA Parcela 2/10")
+        );
+        assert_eq!(Some("Loja X".to_string()), normalize("LOJA X PARC 02/10"));
+        assert_eq!(Some("Netshoes".to_string()), normalize("Netshoes 3 de 6"));
+    }
+
+    #[test]
+    fn titles_that_are_only_ids_are_kept() {
+        assert_eq!(Some("12345".to_string()), normalize("12345"));
+        assert_eq!(None, normalize("   "));
+    }
+}
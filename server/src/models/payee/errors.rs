@@ -0,0 +1,13 @@
+/// Specifies errors that may arise from getting or creating a [Payee](super::Payee)
+#[derive(Debug, thiserror::Error)]
+pub enum GetOrCreatePayeeError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from listing [Payee](super::Payee)s
+#[derive(Debug, thiserror::Error)]
+pub enum ListPayeesError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
@@ -0,0 +1,329 @@
+pub mod errors;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+use crate::models::currency::{Currency, ExchangeRate, InvalidExchangeRateError};
+
+/// What one unit of a `commodity`, like USD or the stock PETR4, was worth in `currency` on a
+/// given day. Prices value the balances of accounts held in other currencies.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Price {
+    id: Uuid,
+    commodity: Currency,
+    currency: Currency,
+    date: NaiveDate,
+    rate: Decimal,
+}
+
+impl Price {
+    pub fn new(
+        id: Uuid,
+        commodity: Currency,
+        currency: Currency,
+        date: NaiveDate,
+        rate: Decimal,
+    ) -> Self {
+        Self {
+            id,
+            commodity,
+            currency,
+            date,
+            rate,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn commodity(&self) -> Currency {
+        self.commodity
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+}
+
+/// The fields of a [Price], set as a whole
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreatePriceRequest {
+    commodity: Currency,
+    currency: Currency,
+    date: NaiveDate,
+    rate: Decimal,
+}
+
+impl CreatePriceRequest {
+    pub fn new(commodity: Currency, currency: Currency, date: NaiveDate, rate: Decimal) -> Self {
+        Self {
+            commodity,
+            currency,
+            date,
+            rate,
+        }
+    }
+
+    pub fn commodity(&self) -> Currency {
+        self.commodity
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+
+    /// The price as an exchange rate, which fails if the commodity is priced in itself or the
+    /// rate is not positive
+    pub fn exchange_rate(&self) -> Result<ExchangeRate, InvalidExchangeRateError> {
+        ExchangeRate::new(self.commodity, self.currency, self.rate)
+    }
+}
+
+/// The rate of a commodity in a currency on a day, taken from the latest [Price] on or before
+/// that day. When only the commodity is priced in the currency the other way around, the rate
+/// is the inverse of that price.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceQuote {
+    commodity: Currency,
+    currency: Currency,
+    /// The day the rate was asked for
+    date: NaiveDate,
+    rate: Decimal,
+    /// Where the rate comes from
+    price: Price,
+}
+
+impl PriceQuote {
+    /// Quote `commodity` in `currency` on `date` from `price`, which must be a price of one in
+    /// the other
+    pub fn new(commodity: Currency, currency: Currency, date: NaiveDate, price: Price) -> Self {
+        let rate = if price.commodity() == commodity {
+            price.rate()
+        } else {
+            Decimal::ONE / price.rate()
+        };
+
+        Self {
+            commodity,
+            currency,
+            date,
+            rate,
+            price,
+        }
+    }
+
+    pub fn commodity(&self) -> Currency {
+        self.commodity
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn rate(&self) -> Decimal {
+        self.rate
+    }
+
+    pub fn price(&self) -> &Price {
+        &self.price
+    }
+}
+
+/// A row of a price file that could not be read
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedPriceRow {
+    line: u64,
+    error: String,
+}
+
+impl FailedPriceRow {
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+}
+
+/// What importing a price file did
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriceImportReport {
+    /// How many prices were new
+    created: usize,
+    /// How many prices replaced the rate of an existing one of the same commodity, currency
+    /// and date
+    updated: usize,
+    failed: Vec<FailedPriceRow>,
+}
+
+impl PriceImportReport {
+    pub fn new(created: usize, updated: usize, failed: Vec<FailedPriceRow>) -> Self {
+        Self {
+            created,
+            updated,
+            failed,
+        }
+    }
+
+    pub fn created(&self) -> usize {
+        self.created
+    }
+
+    pub fn updated(&self) -> usize {
+        self.updated
+    }
+
+    pub fn failed(&self) -> &[FailedPriceRow] {
+        &self.failed
+    }
+}
+
+/// Read a CSV file of prices, with a header naming the `commodity`, `currency`, `date` and
+/// `rate` columns, in any order. Dates are like `2026-01-31` and rates like `5.4321`.
+///
+/// The file as a whole fails to be read if it is malformed or misses a column. Otherwise, each
+/// row is read on its own, so that one invalid row does not prevent importing the others.
+pub fn read_prices(
+    contents: &[u8],
+) -> Result<Vec<Result<CreatePriceRequest, FailedPriceRow>>, ImportPricesError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(contents);
+    let headers = reader.headers()?.clone();
+    let column = |name: &'static str| {
+        headers
+            .iter()
+            .position(|header| header.eq_ignore_ascii_case(name))
+            .ok_or(ImportPricesError::MissingColumn { name })
+    };
+    let columns = [
+        column("commodity")?,
+        column("currency")?,
+        column("date")?,
+        column("rate")?,
+    ];
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |position| position.line());
+        let [commodity, currency, date, rate] =
+            columns.map(|column| record.get(column).unwrap_or_default());
+        let row = read_price(commodity, currency, date, rate)
+            .map_err(|error| FailedPriceRow { line, error });
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+/// Read the fields of a row of a price file, or describe what is wrong with them
+fn read_price(
+    commodity: &str,
+    currency: &str,
+    date: &str,
+    rate: &str,
+) -> Result<CreatePriceRequest, String> {
+    let commodity = Currency::new(commodity).map_err(|e| e.to_string())?;
+    let currency = Currency::new(currency).map_err(|e| e.to_string())?;
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| format!("\"{date}\" is not a date, expected one like 2026-01-31"))?;
+    let rate = rate
+        .parse::<Decimal>()
+        .map_err(|_| format!("\"{rate}\" is not a valid rate"))?;
+
+    let req = CreatePriceRequest::new(commodity, currency, date, rate);
+    req.exchange_rate().map_err(|e| e.to_string())?;
+
+    Ok(req)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn currency(raw: &str) -> Currency {
+        Currency::new(raw).unwrap()
+    }
+
+    #[test]
+    fn price_files_are_read_row_by_row() {
+        let file = "\
+date,commodity,currency,rate
+2026-01-02,usd,BRL,5.4321
+2026-01-03,PETR4,BRL,-1
+2026/01/04,USD,BRL,5.5
+";
+
+        let rows = read_prices(file.as_bytes()).unwrap();
+
+        assert_eq!(3, rows.len());
+        assert_eq!(
+            Ok(CreatePriceRequest::new(
+                currency("USD"),
+                Currency::BRL,
+                NaiveDate::from_ymd_opt(2026, 1, 2).unwrap(),
+                dec!(5.4321),
+            )),
+            rows[0]
+        );
+        assert_eq!(3, rows[1].as_ref().unwrap_err().line());
+        assert!(rows[2].as_ref().unwrap_err().error().contains("2026/01/04"));
+    }
+
+    #[test]
+    fn price_files_must_name_every_column() {
+        let file = "commodity,currency,rate\nUSD,BRL,5\n";
+
+        assert!(matches!(
+            read_prices(file.as_bytes()),
+            Err(ImportPricesError::MissingColumn { name: "date" })
+        ));
+    }
+
+    #[test]
+    fn quotes_invert_prices_of_the_other_way_around() {
+        let date = NaiveDate::from_ymd_opt(2026, 1, 2).unwrap();
+        let price = Price::new(
+            Uuid::new_v4(),
+            currency("USD"),
+            Currency::BRL,
+            date,
+            dec!(4),
+        );
+
+        let quote = PriceQuote::new(Currency::BRL, currency("USD"), date, price.clone());
+        assert_eq!(dec!(0.25), quote.rate());
+
+        let quote = PriceQuote::new(currency("USD"), Currency::BRL, date, price);
+        assert_eq!(dec!(4), quote.rate());
+    }
+}
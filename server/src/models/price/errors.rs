@@ -0,0 +1,86 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::currency::{Currency, InvalidExchangeRateError};
+
+/// Specifies errors that may arise from creating a [Price](super::Price)
+#[derive(Debug, thiserror::Error)]
+pub enum CreatePriceError {
+    #[error(transparent)]
+    Invalid(#[from] InvalidExchangeRateError),
+    #[error("{commodity} already has a price in {currency} on {date}")]
+    Duplicate {
+        commodity: Currency,
+        currency: Currency,
+        date: NaiveDate,
+    },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from replacing a [Price](super::Price)
+#[derive(Debug, thiserror::Error)]
+pub enum UpdatePriceError {
+    #[error("price with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Invalid(#[from] InvalidExchangeRateError),
+    #[error("{commodity} already has a price in {currency} on {date}")]
+    Duplicate {
+        commodity: Currency,
+        currency: Currency,
+        date: NaiveDate,
+    },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from getting a [Price](super::Price)
+#[derive(Debug, thiserror::Error)]
+pub enum GetPriceError {
+    #[error("price with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from listing [Price](super::Price)s
+#[derive(Debug, thiserror::Error)]
+pub enum ListPricesError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from deleting a [Price](super::Price)
+#[derive(Debug, thiserror::Error)]
+pub enum DeletePriceError {
+    #[error("price with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from importing a file of [Price](super::Price)s
+#[derive(Debug, thiserror::Error)]
+pub enum ImportPricesError {
+    #[error("the file is not valid CSV: {0}")]
+    Malformed(#[from] csv::Error),
+    #[error("the file has no \"{name}\" column")]
+    MissingColumn { name: &'static str },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from looking up the [PriceQuote](super::PriceQuote) of a
+/// commodity
+#[derive(Debug, thiserror::Error)]
+pub enum LookupPriceError {
+    #[error("there is no price of {commodity} in {currency} on or before {date}")]
+    NotFound {
+        commodity: Currency,
+        currency: Currency,
+        date: NaiveDate,
+    },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
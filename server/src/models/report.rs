@@ -0,0 +1,828 @@
+pub mod errors;
+
+use std::collections::HashMap;
+
+use chrono::{Datelike, Days, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::account::{Account, AccountName, AccountType};
+use crate::models::budget::Month;
+use crate::models::currency::{ConversionError, Currency, ExchangeRates};
+
+pub use errors::*;
+
+/// The months from the one `from` falls in to the one the day before `to` falls in
+pub fn months_between(from: NaiveDate, to: NaiveDate) -> Vec<Month> {
+    let last = Month::of(to.pred_opt().unwrap_or(to));
+    let mut months = Vec::new();
+    let mut current = Month::of(from);
+    while current <= last {
+        months.push(current);
+        current = current.next();
+    }
+
+    months
+}
+
+/// The last day of the period that starts on `start` and ends before `next`, cut short by the
+/// end of a report, `to` excluded
+fn last_day(next: NaiveDate, to: NaiveDate) -> NaiveDate {
+    next.min(to).pred_opt().unwrap_or(to)
+}
+
+/// Converts the ledger amounts of accounts into the currency of a report, with the exchange
+/// rates known at the end of each period
+#[derive(Clone, Copy, Debug)]
+pub struct Valuation<'a> {
+    currency: Currency,
+    rates: &'a ExchangeRates,
+}
+
+impl<'a> Valuation<'a> {
+    pub fn new(currency: Currency, rates: &'a ExchangeRates) -> Self {
+        Self { currency, rates }
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Value an amount of `account` on `date`
+    fn value(
+        &self,
+        account: &Account,
+        amount: Decimal,
+        date: NaiveDate,
+    ) -> Result<Decimal, ConversionError> {
+        self.rates
+            .convert(amount, account.currency(), self.currency, date)
+    }
+}
+
+/// The amounts of an [Account] in a report, one per month of the report, following the account
+/// type's sign convention and counted in the currency of the report
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportLine {
+    account_id: Uuid,
+    account_name: AccountName,
+    amounts: Vec<Decimal>,
+    /// The sum of the amounts for an income statement, or the closing balance for a balance
+    /// sheet
+    total: Decimal,
+}
+
+impl ReportLine {
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn account_name(&self) -> &AccountName {
+        &self.account_name
+    }
+
+    pub fn amounts(&self) -> &[Decimal] {
+        &self.amounts
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.total
+    }
+}
+
+/// The [ReportLine]s of all the accounts of one [AccountType], along with their sums
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportSection {
+    #[serde(rename = "type")]
+    account_type: AccountType,
+    lines: Vec<ReportLine>,
+    /// The sums of the lines' amounts, one per month
+    totals: Vec<Decimal>,
+    total: Decimal,
+}
+
+impl ReportSection {
+    /// Build the section of the accounts of the given type. Each account's ledger amounts per
+    /// month, in the currency of the report, are looked up in `amounts`, and accounts whose
+    /// amounts are all zero are left out.
+    fn build(
+        account_type: AccountType,
+        accounts: &[Account],
+        months: &[Month],
+        amounts: impl Fn(&Account, Month) -> Result<Decimal, ConversionError>,
+        total: impl Fn(&[Decimal]) -> Decimal,
+    ) -> Result<Self, ConversionError> {
+        let mut lines = Vec::new();
+        for account in accounts
+            .iter()
+            .filter(|account| account.account_type() == account_type)
+        {
+            let amounts = months
+                .iter()
+                .map(|month| Ok(account_type.balance(amounts(account, *month)?)))
+                .collect::<Result<Vec<Decimal>, _>>()?;
+            if amounts.iter().all(Decimal::is_zero) {
+                continue;
+            }
+            lines.push(ReportLine {
+                account_id: account.id(),
+                account_name: account.name().clone(),
+                total: total(&amounts),
+                amounts,
+            });
+        }
+        lines.sort_by(|a, b| a.account_name.cmp(&b.account_name));
+
+        let totals: Vec<Decimal> = (0..months.len())
+            .map(|i| lines.iter().map(|line| line.amounts[i]).sum())
+            .collect();
+
+        Ok(Self {
+            account_type,
+            total: lines.iter().map(|line| line.total).sum(),
+            lines,
+            totals,
+        })
+    }
+
+    pub fn account_type(&self) -> AccountType {
+        self.account_type
+    }
+
+    pub fn lines(&self) -> &[ReportLine] {
+        &self.lines
+    }
+
+    pub fn totals(&self) -> &[Decimal] {
+        &self.totals
+    }
+
+    pub fn total(&self) -> Decimal {
+        self.total
+    }
+}
+
+/// How much was earned and spent per income and expense account over a period, month by month
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncomeStatement {
+    from: NaiveDate,
+    /// The day after the last day of the period
+    to: NaiveDate,
+    /// What the amounts are counted in
+    currency: Currency,
+    months: Vec<Month>,
+    income: ReportSection,
+    expenses: ReportSection,
+    /// Income minus expenses, one per month
+    net_income: Vec<Decimal>,
+    net_income_total: Decimal,
+}
+
+impl IncomeStatement {
+    /// Build an income statement from the `movements` of each account per month: the sum of the
+    /// amounts of its legs in that month, within the period. Each month's movements are valued
+    /// at the end of the month.
+    pub fn build(
+        from: NaiveDate,
+        to: NaiveDate,
+        accounts: &[Account],
+        movements: &HashMap<(Uuid, Month), Decimal>,
+        valuation: Valuation,
+    ) -> Result<Self, ConversionError> {
+        let months = months_between(from, to);
+        let section = |account_type| {
+            ReportSection::build(
+                account_type,
+                accounts,
+                &months,
+                |account, month| {
+                    let movement = movements
+                        .get(&(account.id(), month))
+                        .copied()
+                        .unwrap_or_default();
+                    valuation.value(account, movement, last_day(month.next().first_day(), to))
+                },
+                |amounts| amounts.iter().sum(),
+            )
+        };
+        let income = section(AccountType::Income)?;
+        let expenses = section(AccountType::Expense)?;
+        let net_income = income
+            .totals()
+            .iter()
+            .zip(expenses.totals())
+            .map(|(income, expenses)| income - expenses)
+            .collect();
+
+        Ok(Self {
+            from,
+            to,
+            currency: valuation.currency(),
+            months,
+            net_income_total: income.total() - expenses.total(),
+            income,
+            expenses,
+            net_income,
+        })
+    }
+
+    pub fn from(&self) -> NaiveDate {
+        self.from
+    }
+
+    pub fn to(&self) -> NaiveDate {
+        self.to
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn months(&self) -> &[Month] {
+        &self.months
+    }
+
+    pub fn income(&self) -> &ReportSection {
+        &self.income
+    }
+
+    pub fn expenses(&self) -> &ReportSection {
+        &self.expenses
+    }
+
+    pub fn net_income(&self) -> &[Decimal] {
+        &self.net_income
+    }
+
+    pub fn net_income_total(&self) -> Decimal {
+        self.net_income_total
+    }
+}
+
+/// The balances of the asset, liability and equity accounts at the end of each month of a
+/// period, or at its end for the last month.
+///
+/// Income and expenses are not closed into an equity account, so what they add up to is shown
+/// as `retained_earnings`, which keeps assets equal to liabilities plus equity plus retained
+/// earnings. Balances in other currencies are valued at the end of each month, so changes in
+/// exchange rates can break that equality.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceSheet {
+    from: NaiveDate,
+    /// The day after the last day of the period
+    to: NaiveDate,
+    /// What the balances are counted in
+    currency: Currency,
+    months: Vec<Month>,
+    assets: ReportSection,
+    liabilities: ReportSection,
+    equity: ReportSection,
+    /// Income minus expenses since the beginning of the ledger, one per month
+    retained_earnings: Vec<Decimal>,
+}
+
+impl BalanceSheet {
+    /// Build a balance sheet from the `opening` ledger balance of each account at the start of
+    /// the period, and its `movements` per month within the period. Each month's balances are
+    /// valued at the end of the month.
+    pub fn build(
+        from: NaiveDate,
+        to: NaiveDate,
+        accounts: &[Account],
+        opening: &HashMap<Uuid, Decimal>,
+        movements: &HashMap<(Uuid, Month), Decimal>,
+        valuation: Valuation,
+    ) -> Result<Self, ConversionError> {
+        let months = months_between(from, to);
+        let closing = |account: &Account, month: Month| {
+            let opening = opening.get(&account.id()).copied().unwrap_or_default();
+            let balance = months
+                .iter()
+                .take_while(|m| **m <= month)
+                .filter_map(|m| movements.get(&(account.id(), *m)))
+                .fold(opening, |balance, movement| balance + movement);
+            valuation.value(account, balance, last_day(month.next().first_day(), to))
+        };
+        let section = |account_type| {
+            ReportSection::build(account_type, accounts, &months, closing, |amounts| {
+                amounts.last().copied().unwrap_or_default()
+            })
+        };
+        let mut retained_earnings = Vec::with_capacity(months.len());
+        for month in &months {
+            let mut earnings = Decimal::ZERO;
+            for account in accounts
+                .iter()
+                .filter(|a| matches!(a.account_type(), AccountType::Income | AccountType::Expense))
+            {
+                earnings -= closing(account, *month)?;
+            }
+            retained_earnings.push(earnings);
+        }
+        let assets = section(AccountType::Asset)?;
+        let liabilities = section(AccountType::Liability)?;
+        let equity = section(AccountType::Equity)?;
+
+        Ok(Self {
+            from,
+            to,
+            currency: valuation.currency(),
+            months,
+            assets,
+            liabilities,
+            equity,
+            retained_earnings,
+        })
+    }
+
+    pub fn from(&self) -> NaiveDate {
+        self.from
+    }
+
+    pub fn to(&self) -> NaiveDate {
+        self.to
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn months(&self) -> &[Month] {
+        &self.months
+    }
+
+    pub fn assets(&self) -> &ReportSection {
+        &self.assets
+    }
+
+    pub fn liabilities(&self) -> &ReportSection {
+        &self.liabilities
+    }
+
+    pub fn equity(&self) -> &ReportSection {
+        &self.equity
+    }
+
+    pub fn retained_earnings(&self) -> &[Decimal] {
+        &self.retained_earnings
+    }
+}
+
+/// The length of the periods of a [BalanceHistory]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Interval {
+    Day,
+    /// Weeks start on Mondays
+    Week,
+    #[default]
+    Month,
+}
+
+impl Interval {
+    /// The unit of this interval in PostgreSQL's `date_trunc`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::Day => "day",
+            Interval::Week => "week",
+            Interval::Month => "month",
+        }
+    }
+
+    /// The first day of the period `date` falls in
+    pub fn truncate(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Interval::Day => date,
+            Interval::Week => date - Days::new(date.weekday().num_days_from_monday().into()),
+            Interval::Month => Month::of(date).first_day(),
+        }
+    }
+
+    /// The first day of the period that follows the one starting on `start`
+    fn next(&self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Interval::Day => start + Days::new(1),
+            Interval::Week => start + Days::new(7),
+            Interval::Month => Month::of(start).next().first_day(),
+        }
+    }
+
+    /// How many [periods](Self::periods) there are from `from` to `to`, counted without listing
+    /// them
+    pub fn count_periods(&self, from: NaiveDate, to: NaiveDate) -> u64 {
+        let start = self.truncate(from);
+        let Some(last) = to.pred_opt().filter(|last| *last >= start) else {
+            return 0;
+        };
+        // At least one, as `last` is not before `start`
+        let days = (last - start).num_days().unsigned_abs() + 1;
+
+        match self {
+            Interval::Day => days,
+            Interval::Week => days.div_ceil(7),
+            Interval::Month => {
+                let months =
+                    |date: NaiveDate| i64::from(date.year()) * 12 + i64::from(date.month0());
+                (months(last) - months(start)).unsigned_abs() + 1
+            }
+        }
+    }
+
+    /// The first day of every period from the one `from` falls in to the one the day before `to`
+    /// falls in
+    pub fn periods(&self, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+        let mut periods = Vec::new();
+        let mut current = self.truncate(from);
+        while current < to {
+            periods.push(current);
+            current = self.next(current);
+        }
+
+        periods
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The balances of an [Account] at the end of each period of a [BalanceHistory], following the
+/// account type's sign convention and counted in the currency of the history
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceSeries {
+    account_id: Uuid,
+    account_name: AccountName,
+    #[serde(rename = "type")]
+    account_type: AccountType,
+    balances: Vec<Decimal>,
+}
+
+impl BalanceSeries {
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn account_name(&self) -> &AccountName {
+        &self.account_name
+    }
+
+    pub fn account_type(&self) -> AccountType {
+        self.account_type
+    }
+
+    pub fn balances(&self) -> &[Decimal] {
+        &self.balances
+    }
+}
+
+/// The balances of a set of accounts at the end of each day, week or month of a period
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceHistory {
+    from: NaiveDate,
+    /// The day after the last day of the period
+    to: NaiveDate,
+    interval: Interval,
+    /// What the balances are counted in, valued at the end of each period
+    currency: Currency,
+    /// The first day of each period. The balances are the ones at the end of the period, or on
+    /// the day before `to` for the last one.
+    periods: Vec<NaiveDate>,
+    accounts: Vec<BalanceSeries>,
+    /// Assets minus liabilities among the accounts, one per period
+    net_worth: Vec<Decimal>,
+}
+
+impl BalanceHistory {
+    /// The largest number of periods a history can have
+    pub const MAX_PERIODS: usize = 1000;
+
+    /// Build a balance history from the `opening` ledger balance of each account at the start of
+    /// the period, and its `movements` per period within the period, keyed by the first day of
+    /// the period.
+    pub fn build(
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: Interval,
+        accounts: &[Account],
+        opening: &HashMap<Uuid, Decimal>,
+        movements: &HashMap<(Uuid, NaiveDate), Decimal>,
+        valuation: Valuation,
+    ) -> Result<Self, ConversionError> {
+        let periods = interval.periods(from, to);
+        let ledger_balances = |account: &Account| {
+            let opening = opening.get(&account.id()).copied().unwrap_or_default();
+            let mut balance = opening;
+            periods
+                .iter()
+                .map(|period| {
+                    balance += movements
+                        .get(&(account.id(), *period))
+                        .copied()
+                        .unwrap_or_default();
+                    valuation.value(account, balance, last_day(interval.next(*period), to))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let mut net_worth = vec![Decimal::ZERO; periods.len()];
+        let mut series = Vec::with_capacity(accounts.len());
+        for account in accounts {
+            let ledger_balances = ledger_balances(account)?;
+            // Ledger balances are debits minus credits, so adding up those of assets and
+            // liabilities subtracts the latter from the former
+            if matches!(
+                account.account_type(),
+                AccountType::Asset | AccountType::Liability
+            ) {
+                for (net_worth, balance) in net_worth.iter_mut().zip(&ledger_balances) {
+                    *net_worth += balance;
+                }
+            }
+
+            series.push(BalanceSeries {
+                account_id: account.id(),
+                account_name: account.name().clone(),
+                account_type: account.account_type(),
+                balances: ledger_balances
+                    .into_iter()
+                    .map(|balance| account.account_type().balance(balance))
+                    .collect(),
+            });
+        }
+        series.sort_by(|a, b| a.account_name.cmp(&b.account_name));
+
+        Ok(Self {
+            from,
+            to,
+            interval,
+            currency: valuation.currency(),
+            periods,
+            accounts: series,
+            net_worth,
+        })
+    }
+
+    pub fn from(&self) -> NaiveDate {
+        self.from
+    }
+
+    pub fn to(&self) -> NaiveDate {
+        self.to
+    }
+
+    pub fn interval(&self) -> Interval {
+        self.interval
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn periods(&self) -> &[NaiveDate] {
+        &self.periods
+    }
+
+    pub fn accounts(&self) -> &[BalanceSeries] {
+        &self.accounts
+    }
+
+    pub fn net_worth(&self) -> &[Decimal] {
+        &self.net_worth
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::NaiveDate;
+    use rust_decimal_macros::dec;
+    use uuid::Uuid;
+
+    use crate::models::account::{Account, AccountName, AccountType};
+    use crate::models::budget::Month;
+    use crate::models::currency::{Currency, ExchangeRate, ExchangeRates};
+
+    use super::{
+        BalanceHistory, BalanceSheet, IncomeStatement, Interval, Valuation, months_between,
+    };
+
+    fn date(raw: &str) -> NaiveDate {
+        raw.parse().unwrap()
+    }
+
+    fn month(raw: &str) -> Month {
+        raw.parse().unwrap()
+    }
+
+    fn account(name: &str, account_type: AccountType) -> Account {
+        Account::new(
+            Uuid::new_v4(),
+            AccountName::new(name).unwrap(),
+            account_type,
+            None,
+            dec!(0),
+        )
+    }
+
+    #[test]
+    fn months_between_covers_partial_months() {
+        assert_eq!(
+            vec![month("2026-01"), month("2026-02"), month("2026-03")],
+            months_between(date("2026-01-15"), date("2026-03-02"))
+        );
+        assert_eq!(
+            vec![month("2026-01")],
+            months_between(date("2026-01-01"), date("2026-02-01"))
+        );
+    }
+
+    #[test]
+    fn income_statements_follow_sign_conventions() {
+        let salary = account("income:salary", AccountType::Income);
+        let food = account("expenses:food", AccountType::Expense);
+        let bank = account("assets:bank", AccountType::Asset);
+        let movements = HashMap::from([
+            ((salary.id(), month("2026-01")), dec!(-1000)),
+            ((food.id(), month("2026-01")), dec!(300)),
+            ((food.id(), month("2026-02")), dec!(200)),
+            ((bank.id(), month("2026-01")), dec!(700)),
+        ]);
+
+        let statement = IncomeStatement::build(
+            date("2026-01-01"),
+            date("2026-03-01"),
+            &[salary, food, bank],
+            &movements,
+            Valuation::new(Currency::BRL, &ExchangeRates::default()),
+        )
+        .unwrap();
+
+        assert_eq!(&[dec!(1000), dec!(0)], statement.income().totals());
+        assert_eq!(dec!(500), statement.expenses().total());
+        assert_eq!(&[dec!(700), dec!(-200)], statement.net_income());
+        assert_eq!(dec!(500), statement.net_income_total());
+    }
+
+    #[test]
+    fn balance_sheets_accumulate_balances_and_balance_out() {
+        let bank = account("assets:bank", AccountType::Asset);
+        let card = account("liabilities:card", AccountType::Liability);
+        let salary = account("income:salary", AccountType::Income);
+        let food = account("expenses:food", AccountType::Expense);
+        let opening = HashMap::from([(bank.id(), dec!(100)), (salary.id(), dec!(-100))]);
+        let movements = HashMap::from([
+            ((card.id(), month("2026-01")), dec!(-40)),
+            ((food.id(), month("2026-01")), dec!(40)),
+            ((bank.id(), month("2026-02")), dec!(-40)),
+            ((card.id(), month("2026-02")), dec!(40)),
+        ]);
+
+        let sheet = BalanceSheet::build(
+            date("2026-01-01"),
+            date("2026-03-01"),
+            &[bank, card, salary, food],
+            &opening,
+            &movements,
+            Valuation::new(Currency::BRL, &ExchangeRates::default()),
+        )
+        .unwrap();
+
+        assert_eq!(&[dec!(100), dec!(60)], sheet.assets().totals());
+        assert_eq!(&[dec!(40), dec!(0)], sheet.liabilities().totals());
+        assert_eq!(dec!(60), sheet.assets().total());
+        assert_eq!(&[dec!(60), dec!(60)], sheet.retained_earnings());
+        assert!(sheet.equity().lines().is_empty());
+    }
+
+    #[test]
+    fn interval_periods_start_on_the_first_day_of_each_period() {
+        assert_eq!(
+            vec![date("2026-01-30"), date("2026-01-31")],
+            Interval::Day.periods(date("2026-01-30"), date("2026-02-01"))
+        );
+        // 2026-01-01 is a Thursday
+        assert_eq!(
+            vec![date("2025-12-29"), date("2026-01-05")],
+            Interval::Week.periods(date("2026-01-01"), date("2026-01-06"))
+        );
+        assert_eq!(
+            vec![date("2026-01-01"), date("2026-02-01")],
+            Interval::Month.periods(date("2026-01-15"), date("2026-02-02"))
+        );
+    }
+
+    #[test]
+    fn interval_periods_are_counted_without_listing_them() {
+        for (from, to) in [
+            ("2026-01-30", "2026-02-01"),
+            ("2026-01-01", "2026-01-06"),
+            ("2026-01-15", "2026-02-02"),
+            ("2025-12-31", "2027-03-01"),
+            ("2026-01-05", "2026-01-05"),
+        ] {
+            for interval in [Interval::Day, Interval::Week, Interval::Month] {
+                assert_eq!(
+                    interval.periods(date(from), date(to)).len() as u64,
+                    interval.count_periods(date(from), date(to)),
+                    "{interval} periods from {from} to {to}"
+                );
+            }
+        }
+
+        assert!(
+            Interval::Day.count_periods(NaiveDate::MIN, NaiveDate::MAX)
+                > BalanceHistory::MAX_PERIODS as u64
+        );
+    }
+
+    #[test]
+    fn balance_histories_accumulate_balances_per_period() {
+        let bank = account("assets:bank", AccountType::Asset);
+        let card = account("liabilities:card", AccountType::Liability);
+        let food = account("expenses:food", AccountType::Expense);
+        let opening = HashMap::from([(bank.id(), dec!(100))]);
+        let movements = HashMap::from([
+            ((card.id(), date("2026-01-05")), dec!(-40)),
+            ((food.id(), date("2026-01-05")), dec!(40)),
+            ((bank.id(), date("2026-01-19")), dec!(-40)),
+            ((card.id(), date("2026-01-19")), dec!(40)),
+        ]);
+
+        let history = BalanceHistory::build(
+            date("2026-01-05"),
+            date("2026-01-26"),
+            Interval::Week,
+            &[bank, card, food],
+            &opening,
+            &movements,
+            Valuation::new(Currency::BRL, &ExchangeRates::default()),
+        )
+        .unwrap();
+
+        assert_eq!(3, history.periods().len());
+        let balances: Vec<_> = history.accounts().iter().map(|s| s.balances()).collect();
+        assert_eq!(
+            vec![
+                &[dec!(100), dec!(100), dec!(60)][..],
+                &[dec!(40), dec!(40), dec!(40)][..],
+                &[dec!(40), dec!(40), dec!(0)][..],
+            ],
+            balances
+        );
+        assert_eq!(&[dec!(60), dec!(60), dec!(60)], history.net_worth());
+    }
+
+    #[test]
+    fn balance_sheets_value_balances_at_the_rate_of_each_month() {
+        let usd = Currency::new("USD").unwrap();
+        let brl = account("assets:bank", AccountType::Asset);
+        let dollars = account("assets:dollars", AccountType::Asset).with_currency(usd);
+        let opening = HashMap::from([(brl.id(), dec!(100)), (dollars.id(), dec!(10))]);
+        let mut rates = ExchangeRates::default();
+        rates.insert(
+            date("2026-01-10"),
+            &ExchangeRate::new(usd, Currency::BRL, dec!(5)).unwrap(),
+        );
+        rates.insert(
+            date("2026-02-10"),
+            &ExchangeRate::new(usd, Currency::BRL, dec!(6)).unwrap(),
+        );
+
+        let sheet = BalanceSheet::build(
+            date("2026-01-01"),
+            date("2026-03-01"),
+            &[brl.clone(), dollars.clone()],
+            &opening,
+            &HashMap::new(),
+            Valuation::new(Currency::BRL, &rates),
+        )
+        .unwrap();
+        assert_eq!(&[dec!(150), dec!(160)], sheet.assets().totals());
+
+        let in_dollars = BalanceSheet::build(
+            date("2026-01-01"),
+            date("2026-03-01"),
+            &[brl.clone(), dollars.clone()],
+            &opening,
+            &HashMap::new(),
+            Valuation::new(usd, &rates),
+        )
+        .unwrap();
+        assert_eq!(usd, in_dollars.currency());
+        assert_eq!(&[dec!(30), dec!(26.67)], in_dollars.assets().totals());
+
+        let missing = BalanceSheet::build(
+            date("2025-12-01"),
+            date("2026-01-01"),
+            &[brl, dollars],
+            &opening,
+            &HashMap::new(),
+            Valuation::new(Currency::BRL, &rates),
+        );
+        assert!(missing.is_err());
+    }
+}
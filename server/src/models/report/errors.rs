@@ -0,0 +1,48 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::account::ListAccountsError;
+use crate::models::currency::ConversionError;
+
+/// Specifies errors that may arise from building an
+/// [IncomeStatement](super::IncomeStatement) or a [BalanceSheet](super::BalanceSheet)
+#[derive(Debug, thiserror::Error)]
+pub enum GetReportError {
+    #[error("the report period must end after it starts, but it goes from {from} to {to}")]
+    InvalidPeriod { from: NaiveDate, to: NaiveDate },
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from building a [BalanceHistory](super::BalanceHistory)
+#[derive(Debug, thiserror::Error)]
+pub enum GetBalanceHistoryError {
+    #[error("the period must end after it starts, but it goes from {from} to {to}")]
+    InvalidPeriod { from: NaiveDate, to: NaiveDate },
+    #[error("the history would have {count} periods, but it can have at most {max}")]
+    TooManyPeriods { count: u64, max: usize },
+    #[error("there is no account with id {id}")]
+    AccountNotFound { id: Uuid },
+    #[error(transparent)]
+    Conversion(#[from] ConversionError),
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl From<ListAccountsError> for GetReportError {
+    fn from(value: ListAccountsError) -> Self {
+        match value {
+            ListAccountsError::Unknown(err) => GetReportError::Unknown(err),
+        }
+    }
+}
+
+impl From<ListAccountsError> for GetBalanceHistoryError {
+    fn from(value: ListAccountsError) -> Self {
+        match value {
+            ListAccountsError::Unknown(err) => GetBalanceHistoryError::Unknown(err),
+        }
+    }
+}
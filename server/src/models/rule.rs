@@ -0,0 +1,474 @@
+pub mod errors;
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+use crate::models::account::AccountType;
+use crate::models::category::CategoryName;
+use crate::models::transaction::Leg;
+
+/// A user-defined rule categorizing transactions. Rules are tried in order of priority, lowest
+/// first, and the first one whose conditions all hold for a transaction applies its actions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rule {
+    id: Uuid,
+    name: String,
+    priority: i32,
+    #[serde(flatten)]
+    conditions: RuleConditions,
+    #[serde(flatten)]
+    actions: RuleActions,
+}
+
+impl Rule {
+    pub fn new(
+        id: Uuid,
+        name: String,
+        priority: i32,
+        conditions: RuleConditions,
+        actions: RuleActions,
+    ) -> Self {
+        Self {
+            id,
+            name,
+            priority,
+            conditions,
+            actions,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    pub fn conditions(&self) -> &RuleConditions {
+        &self.conditions
+    }
+
+    pub fn actions(&self) -> &RuleActions {
+        &self.actions
+    }
+}
+
+/// What a transaction must look like for a [Rule] to apply. Conditions that are not set always
+/// hold.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleConditions {
+    /// A regular expression the title must match, like `(?i)^uber`
+    pub title_pattern: Option<String>,
+    /// The amount moved must be at least this much
+    pub min_amount: Option<Decimal>,
+    /// The amount moved must be at most this much
+    pub max_amount: Option<Decimal>,
+    /// The money must come from or go to this account
+    pub source_account_id: Option<Uuid>,
+    pub payee_id: Option<Uuid>,
+}
+
+/// What a [Rule] does to the transactions it matches
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleActions {
+    pub category: Option<CategoryName>,
+    /// The account the other side of the transaction is booked on, instead of the default
+    /// expense or income account
+    pub destination_account_id: Option<Uuid>,
+    /// Tags added to the transaction
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl RuleActions {
+    pub fn is_empty(&self) -> bool {
+        self.category.is_none() && self.destination_account_id.is_none() && self.tags.is_empty()
+    }
+}
+
+/// The fields required to create a [Rule], or to replace all fields of an existing one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateRuleRequest {
+    name: String,
+    priority: i32,
+    conditions: RuleConditions,
+    actions: RuleActions,
+}
+
+impl CreateRuleRequest {
+    pub fn new(
+        name: impl Into<String>,
+        priority: i32,
+        conditions: RuleConditions,
+        actions: RuleActions,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            priority,
+            conditions,
+            actions,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    pub fn conditions(&self) -> &RuleConditions {
+        &self.conditions
+    }
+
+    pub fn actions(&self) -> &RuleActions {
+        &self.actions
+    }
+
+    /// Check the fields that do not depend on what is stored: the name, the title pattern, the
+    /// amount range and that there is something to do
+    pub fn validate(&self) -> Result<(), RuleValidationError> {
+        if self.name.trim().is_empty() {
+            return Err(RuleValidationError::EmptyName);
+        }
+        if let Some(pattern) = &self.conditions.title_pattern {
+            Regex::new(pattern).map_err(|e| RuleValidationError::InvalidPattern {
+                pattern: pattern.clone(),
+                reason: e.to_string(),
+            })?;
+        }
+        if let (Some(min), Some(max)) = (self.conditions.min_amount, self.conditions.max_amount)
+            && min > max
+        {
+            return Err(RuleValidationError::InvalidAmountRange);
+        }
+        if self.actions.is_empty() {
+            return Err(RuleValidationError::NoActions);
+        }
+
+        Ok(())
+    }
+}
+
+/// What rules are matched against: a transaction, or an imported row about to become one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RuleSubject<'a> {
+    pub title: &'a str,
+    /// The amount moved, always positive
+    pub amount: Decimal,
+    /// The accounts on the source side of the transaction
+    pub source_account_ids: Vec<Uuid>,
+    pub payee_id: Option<Uuid>,
+}
+
+/// [Rule]s ready to be matched, ordered by priority and with their title patterns compiled
+#[derive(Clone, Debug, Default)]
+pub struct RuleSet {
+    rules: Vec<(Rule, Option<Regex>)>,
+}
+
+impl RuleSet {
+    /// Compile the title patterns of `rules`, which must already be ordered by priority
+    pub fn new(rules: Vec<Rule>) -> Result<Self, regex::Error> {
+        let rules = rules
+            .into_iter()
+            .map(|rule| {
+                let pattern = rule
+                    .conditions
+                    .title_pattern
+                    .as_deref()
+                    .map(Regex::new)
+                    .transpose()?;
+                Ok((rule, pattern))
+            })
+            .collect::<Result<_, regex::Error>>()?;
+
+        Ok(Self { rules })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The first rule whose conditions all hold for `subject`
+    pub fn first_match(&self, subject: &RuleSubject<'_>) -> Option<&Rule> {
+        self.rules
+            .iter()
+            .find(|(rule, pattern)| {
+                let conditions = &rule.conditions;
+                pattern.as_ref().is_none_or(|p| p.is_match(subject.title))
+                    && conditions
+                        .min_amount
+                        .is_none_or(|min| subject.amount >= min)
+                    && conditions
+                        .max_amount
+                        .is_none_or(|max| subject.amount <= max)
+                    && conditions
+                        .source_account_id
+                        .is_none_or(|id| subject.source_account_ids.contains(&id))
+                    && conditions
+                        .payee_id
+                        .is_none_or(|id| subject.payee_id == Some(id))
+            })
+            .map(|(rule, _)| rule)
+    }
+}
+
+/// The position of the leg booked on the other side of a transaction, the one a rule's
+/// destination account replaces: the only income or expense leg of a transaction between two
+/// accounts. Transactions with more legs, or with no single such leg, have none.
+pub fn counterpart_leg(legs: &[Leg], account_types: &HashMap<Uuid, AccountType>) -> Option<usize> {
+    if legs.len() != 2 {
+        return None;
+    }
+
+    let mut positions = legs.iter().enumerate().filter(|(_, leg)| {
+        matches!(
+            account_types.get(&leg.account_id()),
+            Some(AccountType::Income | AccountType::Expense)
+        )
+    });
+    match (positions.next(), positions.next()) {
+        (Some((position, _)), None) => Some(position),
+        _ => None,
+    }
+}
+
+/// A change to one side of a transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change<T> {
+    pub from: T,
+    pub to: T,
+}
+
+/// What applying a [Rule] to an existing transaction changes, or would change in a preview
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleApplication {
+    transaction_id: Uuid,
+    title: String,
+    rule_id: Uuid,
+    /// The category before and after, if it changes
+    category: Option<Change<Option<CategoryName>>>,
+    /// The account on the other side of the transaction before and after, if it changes
+    destination_account_id: Option<Change<Uuid>>,
+    /// The tags the transaction did not have yet
+    added_tags: Vec<String>,
+}
+
+impl RuleApplication {
+    pub fn new(
+        transaction_id: Uuid,
+        title: String,
+        rule_id: Uuid,
+        category: Option<Change<Option<CategoryName>>>,
+        destination_account_id: Option<Change<Uuid>>,
+        added_tags: Vec<String>,
+    ) -> Self {
+        Self {
+            transaction_id,
+            title,
+            rule_id,
+            category,
+            destination_account_id,
+            added_tags,
+        }
+    }
+
+    pub fn transaction_id(&self) -> Uuid {
+        self.transaction_id
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn rule_id(&self) -> Uuid {
+        self.rule_id
+    }
+
+    pub fn category(&self) -> Option<&Change<Option<CategoryName>>> {
+        self.category.as_ref()
+    }
+
+    pub fn destination_account_id(&self) -> Option<&Change<Uuid>> {
+        self.destination_account_id.as_ref()
+    }
+
+    pub fn added_tags(&self) -> &[String] {
+        &self.added_tags
+    }
+
+    /// Whether the rule leaves the transaction as it is
+    pub fn is_noop(&self) -> bool {
+        self.category.is_none()
+            && self.destination_account_id.is_none()
+            && self.added_tags.is_empty()
+    }
+}
+
+/// The outcome of re-applying the rules to the existing transactions
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApplyRulesReport {
+    /// Whether the changes were only computed, without writing anything
+    preview: bool,
+    changes: Vec<RuleApplication>,
+}
+
+impl ApplyRulesReport {
+    pub fn new(preview: bool, changes: Vec<RuleApplication>) -> Self {
+        Self { preview, changes }
+    }
+
+    pub fn preview(&self) -> bool {
+        self.preview
+    }
+
+    pub fn changes(&self) -> &[RuleApplication] {
+        &self.changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn rule(priority: i32, conditions: RuleConditions) -> Rule {
+        let actions = RuleActions {
+            tags: vec!["tag".to_string()],
+            ..Default::default()
+        };
+        Rule::new(
+            Uuid::new_v4(),
+            "rule".to_string(),
+            priority,
+            conditions,
+            actions,
+        )
+    }
+
+    fn subject(title: &str, amount: Decimal) -> RuleSubject<'_> {
+        RuleSubject {
+            title,
+            amount,
+            source_account_ids: vec![],
+            payee_id: None,
+        }
+    }
+
+    #[test]
+    fn first_match_tries_rules_in_order() {
+        let uber = rule(
+            0,
+            RuleConditions {
+                title_pattern: Some("(?i)^uber".to_string()),
+                max_amount: Some(dec!(50)),
+                ..Default::default()
+            },
+        );
+        let fallback = rule(1, RuleConditions::default());
+        let rules = RuleSet::new(vec![uber.clone(), fallback.clone()]).unwrap();
+
+        assert_eq!(
+            rules.first_match(&subject("UBER *TRIP", dec!(20))),
+            Some(&uber)
+        );
+        assert_eq!(
+            rules.first_match(&subject("UBER *TRIP", dec!(80))),
+            Some(&fallback)
+        );
+        assert_eq!(
+            rules.first_match(&subject("Bakery", dec!(20))),
+            Some(&fallback)
+        );
+    }
+
+    #[test]
+    fn first_match_checks_accounts_and_payees() {
+        let account_id = Uuid::new_v4();
+        let payee_id = Uuid::new_v4();
+        let rules = RuleSet::new(vec![rule(
+            0,
+            RuleConditions {
+                source_account_id: Some(account_id),
+                payee_id: Some(payee_id),
+                ..Default::default()
+            },
+        )])
+        .unwrap();
+
+        let mut subject = subject("Bakery", dec!(5));
+        assert_eq!(rules.first_match(&subject), None);
+
+        subject.source_account_ids = vec![account_id];
+        subject.payee_id = Some(payee_id);
+        assert!(rules.first_match(&subject).is_some());
+    }
+
+    #[test]
+    fn validate_rejects_invalid_rules() {
+        let actions = RuleActions {
+            tags: vec!["tag".to_string()],
+            ..Default::default()
+        };
+        let invalid_pattern = RuleConditions {
+            title_pattern: Some("(".to_string()),
+            ..Default::default()
+        };
+        let invalid_range = RuleConditions {
+            min_amount: Some(dec!(10)),
+            max_amount: Some(dec!(5)),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            CreateRuleRequest::new("rule", 0, invalid_pattern, actions.clone()).validate(),
+            Err(RuleValidationError::InvalidPattern { .. })
+        ));
+        assert!(matches!(
+            CreateRuleRequest::new("rule", 0, invalid_range, actions.clone()).validate(),
+            Err(RuleValidationError::InvalidAmountRange)
+        ));
+        assert!(matches!(
+            CreateRuleRequest::new("rule", 0, RuleConditions::default(), RuleActions::default())
+                .validate(),
+            Err(RuleValidationError::NoActions)
+        ));
+        assert!(
+            CreateRuleRequest::new("rule", 0, RuleConditions::default(), actions)
+                .validate()
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn counterpart_leg_is_the_only_income_or_expense_leg() {
+        let card = Uuid::new_v4();
+        let groceries = Uuid::new_v4();
+        let savings = Uuid::new_v4();
+        let account_types = HashMap::from([
+            (card, AccountType::Liability),
+            (groceries, AccountType::Expense),
+            (savings, AccountType::Asset),
+        ]);
+
+        let purchase = [Leg::new(groceries, dec!(5)), Leg::new(card, dec!(-5))];
+        assert_eq!(counterpart_leg(&purchase, &account_types), Some(0));
+
+        let transfer = [Leg::new(savings, dec!(5)), Leg::new(card, dec!(-5))];
+        assert_eq!(counterpart_leg(&transfer, &account_types), None);
+    }
+}
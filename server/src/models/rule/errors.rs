@@ -0,0 +1,92 @@
+use uuid::Uuid;
+
+use crate::models::category::CategoryName;
+use crate::models::currency::Currency;
+
+/// Specifies why the fields of a [Rule](super::Rule) are not valid
+#[derive(Debug, thiserror::Error)]
+pub enum RuleValidationError {
+    #[error("rule names must not be empty")]
+    EmptyName,
+    #[error("\"{pattern}\" is not a valid regular expression: {reason}")]
+    InvalidPattern { pattern: String, reason: String },
+    #[error("the minimum amount must not be greater than the maximum amount")]
+    InvalidAmountRange,
+    #[error("a rule needs a category, a destination account or tags to assign")]
+    NoActions,
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error("payee with id {id} was not found")]
+    PayeeNotFound { id: Uuid },
+    #[error("category \"{name}\" was not found")]
+    CategoryNotFound { name: CategoryName },
+    #[error(
+        "the destination account is in {destination_currency}, not in {source_currency} like the source account"
+    )]
+    CurrencyMismatch {
+        source_currency: Currency,
+        destination_currency: Currency,
+    },
+}
+
+/// Specifies errors that may arise from creating a [Rule](super::Rule)
+#[derive(Debug, thiserror::Error)]
+pub enum CreateRuleError {
+    #[error(transparent)]
+    Invalid(#[from] RuleValidationError),
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from replacing a [Rule](super::Rule)
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateRuleError {
+    #[error("rule with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Invalid(#[from] RuleValidationError),
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl From<CreateRuleError> for UpdateRuleError {
+    fn from(value: CreateRuleError) -> Self {
+        match value {
+            CreateRuleError::Invalid(e) => UpdateRuleError::Invalid(e),
+            CreateRuleError::Unknown(e) => UpdateRuleError::Unknown(e),
+        }
+    }
+}
+
+/// Specifies errors that may arise from getting a [Rule](super::Rule)
+#[derive(Debug, thiserror::Error)]
+pub enum GetRuleError {
+    #[error("rule with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from listing [Rule](super::Rule)s
+#[derive(Debug, thiserror::Error)]
+pub enum ListRulesError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from deleting a [Rule](super::Rule)
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteRuleError {
+    #[error("rule with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from applying the [Rule](super::Rule)s to existing
+/// transactions
+#[derive(Debug, thiserror::Error)]
+pub enum ApplyRulesError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
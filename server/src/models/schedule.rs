@@ -0,0 +1,467 @@
+pub mod errors;
+
+use chrono::{DateTime, Datelike as _, Days, Months, NaiveDate, NaiveTime, Utc, Weekday};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub use errors::*;
+
+use crate::models::budget::Month;
+use crate::models::category::CategoryName;
+use crate::models::transaction::{
+    CreateTransactionRequest, PostingDateParseError, TransactionTitle, local_to_utc,
+};
+
+/// A transaction that repeats, like rent, a salary or a subscription. Each occurrence is posted
+/// as a [Transaction](crate::models::transaction::Transaction) of its own once its day comes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Schedule {
+    id: Uuid,
+    #[serde(flatten)]
+    transfer: ScheduledTransfer,
+    #[serde(flatten)]
+    recurrence: Recurrence,
+    /// The first day occurrences may fall on
+    start_date: NaiveDate,
+    /// The last day occurrences may fall on, if the series ends
+    end_date: Option<NaiveDate>,
+}
+
+impl Schedule {
+    pub fn new(
+        id: Uuid,
+        transfer: ScheduledTransfer,
+        recurrence: Recurrence,
+        start_date: NaiveDate,
+        end_date: Option<NaiveDate>,
+    ) -> Self {
+        Self {
+            id,
+            transfer,
+            recurrence,
+            start_date,
+            end_date,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn transfer(&self) -> &ScheduledTransfer {
+        &self.transfer
+    }
+
+    pub fn recurrence(&self) -> Recurrence {
+        self.recurrence
+    }
+
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        self.end_date
+    }
+
+    /// The days of the occurrences after `after`, if given, up to `until`, both within the
+    /// series
+    pub fn occurrences(&self, after: Option<NaiveDate>, until: NaiveDate) -> Vec<NaiveDate> {
+        let until = self.end_date.map_or(until, |end_date| end_date.min(until));
+
+        (0..=u32::MAX)
+            .map_while(|n| self.recurrence.nth(self.start_date, n))
+            .skip_while(|date| *date < self.start_date)
+            .take_while(|date| *date <= until)
+            .filter(|date| after.is_none_or(|after| *date > after))
+            .collect()
+    }
+
+    /// The transaction posted for the occurrence on `date`, at the start of that day in
+    /// `timezone`
+    pub fn transaction_request(
+        &self,
+        date: NaiveDate,
+        timezone: Tz,
+    ) -> Result<CreateTransactionRequest, PostingDateParseError> {
+        let posting_date: DateTime<Utc> = local_to_utc(date.and_time(NaiveTime::MIN), timezone)?;
+
+        Ok(CreateTransactionRequest::transfer(
+            self.transfer.title.clone(),
+            self.transfer.amount,
+            self.transfer.source_account_id,
+            self.transfer.destination_account_id,
+            self.transfer.category.clone(),
+            Some(posting_date),
+        )
+        .with_occurrence(Occurrence {
+            schedule_id: self.id,
+            date,
+        }))
+    }
+}
+
+/// What each occurrence of a [Schedule] posts: `amount` moving from one account to another
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledTransfer {
+    pub title: TransactionTitle,
+    pub amount: Decimal,
+    pub source_account_id: Uuid,
+    pub destination_account_id: Uuid,
+    pub category: Option<CategoryName>,
+}
+
+/// How often a [Schedule] repeats, every `interval` weeks, months or years
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "frequency", rename_all = "snake_case")]
+pub enum Recurrence {
+    /// On the weekday of the start date
+    Weekly {
+        #[serde(default = "one")]
+        interval: u32,
+    },
+    /// On the given day, or on the last day of months too short for it
+    Monthly {
+        #[serde(default = "one")]
+        interval: u32,
+        day: u32,
+    },
+    /// On the last weekday of the month, like salaries paid on the last business day
+    LastBusinessDay {
+        #[serde(default = "one")]
+        interval: u32,
+    },
+    /// On the month and day of the start date
+    Yearly {
+        #[serde(default = "one")]
+        interval: u32,
+    },
+}
+
+fn one() -> u32 {
+    1
+}
+
+impl Recurrence {
+    /// The largest number of weeks, months or years between occurrences
+    pub const MAX_INTERVAL: u32 = 100;
+
+    /// Build a [Recurrence] from how it is stored: its frequency, its interval and, for monthly
+    /// ones, the day
+    pub fn from_parts(frequency: &str, interval: u32, day: Option<u32>) -> Option<Self> {
+        match frequency {
+            "weekly" => Some(Self::Weekly { interval }),
+            "monthly" => Some(Self::Monthly {
+                interval,
+                day: day?,
+            }),
+            "last_business_day" => Some(Self::LastBusinessDay { interval }),
+            "yearly" => Some(Self::Yearly { interval }),
+            _ => None,
+        }
+    }
+
+    pub fn frequency(&self) -> &'static str {
+        match self {
+            Self::Weekly { .. } => "weekly",
+            Self::Monthly { .. } => "monthly",
+            Self::LastBusinessDay { .. } => "last_business_day",
+            Self::Yearly { .. } => "yearly",
+        }
+    }
+
+    pub fn interval(&self) -> u32 {
+        match *self {
+            Self::Weekly { interval }
+            | Self::Monthly { interval, .. }
+            | Self::LastBusinessDay { interval }
+            | Self::Yearly { interval } => interval,
+        }
+    }
+
+    pub fn day(&self) -> Option<u32> {
+        match *self {
+            Self::Monthly { day, .. } => Some(day),
+            _ => None,
+        }
+    }
+
+    /// The `n`th candidate day of a series starting on `start`, counting from 0, or [None] if
+    /// it is past the last day that can be represented. The first candidates of monthly series
+    /// may fall before `start`.
+    fn nth(&self, start: NaiveDate, n: u32) -> Option<NaiveDate> {
+        let month = |count: Option<u32>| {
+            Month::of(start)
+                .first_day()
+                .checked_add_months(Months::new(count?))
+                .map(Month::of)
+        };
+        match *self {
+            Self::Weekly { interval } => {
+                start.checked_add_days(Days::new(7 * u64::from(interval.checked_mul(n)?)))
+            }
+            Self::Monthly { interval, day } => Some(month(interval.checked_mul(n))?.day(day)),
+            Self::LastBusinessDay { interval } => {
+                let mut date = month(interval.checked_mul(n))?.day(31);
+                while matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                    date = date.pred_opt()?;
+                }
+                Some(date)
+            }
+            Self::Yearly { interval } => {
+                let count = interval.checked_mul(n).and_then(|n| n.checked_mul(12));
+                Some(month(count)?.day(start.day()))
+            }
+        }
+    }
+}
+
+/// The fields required to create a [Schedule], or to replace all fields of an existing one
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CreateScheduleRequest {
+    transfer: ScheduledTransfer,
+    recurrence: Recurrence,
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+}
+
+impl CreateScheduleRequest {
+    pub fn new(
+        transfer: ScheduledTransfer,
+        recurrence: Recurrence,
+        start_date: NaiveDate,
+        end_date: Option<NaiveDate>,
+    ) -> Self {
+        Self {
+            transfer,
+            recurrence,
+            start_date,
+            end_date,
+        }
+    }
+
+    pub fn transfer(&self) -> &ScheduledTransfer {
+        &self.transfer
+    }
+
+    pub fn recurrence(&self) -> Recurrence {
+        self.recurrence
+    }
+
+    pub fn start_date(&self) -> NaiveDate {
+        self.start_date
+    }
+
+    pub fn end_date(&self) -> Option<NaiveDate> {
+        self.end_date
+    }
+
+    /// Check the fields that do not depend on what is stored: the amount, the accounts, the
+    /// recurrence and the range of dates
+    pub fn validate(&self) -> Result<(), ScheduleValidationError> {
+        if self.transfer.amount <= Decimal::ZERO {
+            return Err(ScheduleValidationError::NonPositiveAmount);
+        }
+        if self.transfer.source_account_id == self.transfer.destination_account_id {
+            return Err(ScheduleValidationError::SameAccounts);
+        }
+        let interval = self.recurrence.interval();
+        if !(1..=Recurrence::MAX_INTERVAL).contains(&interval) {
+            return Err(ScheduleValidationError::InvalidInterval {
+                interval,
+                max: Recurrence::MAX_INTERVAL,
+            });
+        }
+        if let Some(day) = self.recurrence.day()
+            && !(1..=31).contains(&day)
+        {
+            return Err(ScheduleValidationError::InvalidDay { day });
+        }
+        if self
+            .end_date
+            .is_some_and(|end_date| end_date < self.start_date)
+        {
+            return Err(ScheduleValidationError::InvalidDateRange);
+        }
+
+        Ok(())
+    }
+}
+
+/// Which occurrence of a [Schedule] a transaction is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Occurrence {
+    pub schedule_id: Uuid,
+    pub date: NaiveDate,
+}
+
+/// An occurrence of a [Schedule] that is not posted yet
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpcomingOccurrence {
+    schedule_id: Uuid,
+    date: NaiveDate,
+    #[serde(flatten)]
+    transfer: ScheduledTransfer,
+    /// Skipped occurrences are never posted
+    skipped: bool,
+}
+
+impl UpcomingOccurrence {
+    /// The furthest from today occurrences can be listed up to, in days
+    pub const MAX_DAYS_AHEAD: u64 = 3660;
+
+    pub fn new(schedule: &Schedule, date: NaiveDate, skipped: bool) -> Self {
+        Self {
+            schedule_id: schedule.id(),
+            date,
+            transfer: schedule.transfer().clone(),
+            skipped,
+        }
+    }
+
+    pub fn schedule_id(&self) -> Uuid {
+        self.schedule_id
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn transfer(&self) -> &ScheduledTransfer {
+        &self.transfer
+    }
+
+    pub fn skipped(&self) -> bool {
+        self.skipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn schedule(recurrence: Recurrence, start_date: NaiveDate) -> Schedule {
+        Schedule::new(
+            Uuid::new_v4(),
+            ScheduledTransfer {
+                title: TransactionTitle::new("Rent").unwrap(),
+                amount: dec!(1500),
+                source_account_id: Uuid::new_v4(),
+                destination_account_id: Uuid::new_v4(),
+                category: None,
+            },
+            recurrence,
+            start_date,
+            None,
+        )
+    }
+
+    #[test]
+    fn monthly_occurrences_fall_on_the_last_day_of_short_months() {
+        let schedule = schedule(
+            Recurrence::Monthly {
+                interval: 1,
+                day: 31,
+            },
+            date(2026, 1, 15),
+        );
+
+        assert_eq!(
+            vec![date(2026, 1, 31), date(2026, 2, 28), date(2026, 3, 31)],
+            schedule.occurrences(None, date(2026, 4, 29))
+        );
+        assert_eq!(
+            vec![date(2026, 3, 31)],
+            schedule.occurrences(Some(date(2026, 2, 28)), date(2026, 4, 29))
+        );
+    }
+
+    #[test]
+    fn monthly_occurrences_before_the_start_date_are_left_out() {
+        let schedule = schedule(
+            Recurrence::Monthly {
+                interval: 2,
+                day: 5,
+            },
+            date(2026, 1, 15),
+        );
+
+        assert_eq!(
+            vec![date(2026, 3, 5), date(2026, 5, 5)],
+            schedule.occurrences(None, date(2026, 6, 30))
+        );
+    }
+
+    #[test]
+    fn weekly_and_yearly_occurrences_follow_the_start_date() {
+        let weekly = schedule(Recurrence::Weekly { interval: 2 }, date(2026, 3, 2));
+        assert_eq!(
+            vec![date(2026, 3, 2), date(2026, 3, 16), date(2026, 3, 30)],
+            weekly.occurrences(None, date(2026, 4, 12))
+        );
+
+        let yearly = schedule(Recurrence::Yearly { interval: 1 }, date(2024, 2, 29));
+        assert_eq!(
+            vec![date(2024, 2, 29), date(2025, 2, 28), date(2026, 2, 28)],
+            yearly.occurrences(None, date(2026, 12, 31))
+        );
+    }
+
+    #[test]
+    fn last_business_day_occurrences_skip_weekends() {
+        let schedule = schedule(
+            Recurrence::LastBusinessDay { interval: 1 },
+            date(2026, 1, 1),
+        );
+
+        // January 31st 2026 is a Saturday and May 31st a Sunday
+        assert_eq!(
+            vec![
+                date(2026, 1, 30),
+                date(2026, 2, 27),
+                date(2026, 3, 31),
+                date(2026, 4, 30),
+                date(2026, 5, 29),
+            ],
+            schedule.occurrences(None, date(2026, 5, 31))
+        );
+    }
+
+    #[test]
+    fn occurrences_stop_at_the_last_representable_day() {
+        for recurrence in [
+            Recurrence::Weekly { interval: 100 },
+            Recurrence::Monthly {
+                interval: 100,
+                day: 31,
+            },
+            Recurrence::LastBusinessDay { interval: 100 },
+            Recurrence::Yearly { interval: 100 },
+        ] {
+            let schedule = schedule(recurrence, NaiveDate::MAX - Days::new(500));
+
+            let occurrences = schedule.occurrences(None, NaiveDate::MAX);
+
+            assert!(occurrences.len() <= 1, "{recurrence:?}");
+        }
+    }
+
+    #[test]
+    fn occurrences_stop_at_the_end_date() {
+        let mut schedule = schedule(Recurrence::Weekly { interval: 1 }, date(2026, 3, 2));
+        schedule.end_date = Some(date(2026, 3, 9));
+
+        assert_eq!(
+            vec![date(2026, 3, 2), date(2026, 3, 9)],
+            schedule.occurrences(None, date(2026, 12, 31))
+        );
+    }
+}
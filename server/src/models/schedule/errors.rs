@@ -0,0 +1,117 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+use crate::models::category::CategoryName;
+use crate::models::currency::Currency;
+
+/// Specifies why the fields of a [Schedule](super::Schedule) are not valid
+#[derive(Debug, thiserror::Error)]
+pub enum ScheduleValidationError {
+    #[error("scheduled amounts must be positive")]
+    NonPositiveAmount,
+    #[error("the source and destination accounts must be different")]
+    SameAccounts,
+    #[error("schedules must repeat every 1 to {max} weeks, months or years, not {interval}")]
+    InvalidInterval { interval: u32, max: u32 },
+    #[error("{day} is not a day of the month, expected a number from 1 to 31")]
+    InvalidDay { day: u32 },
+    #[error("the end date must not be before the start date")]
+    InvalidDateRange,
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error("category \"{name}\" was not found")]
+    CategoryNotFound { name: CategoryName },
+    #[error(
+        "the destination account is in {destination_currency}, not in {source_currency} like the source account"
+    )]
+    CurrencyMismatch {
+        source_currency: Currency,
+        destination_currency: Currency,
+    },
+}
+
+/// Specifies errors that may arise from creating a [Schedule](super::Schedule)
+#[derive(Debug, thiserror::Error)]
+pub enum CreateScheduleError {
+    #[error(transparent)]
+    Invalid(#[from] ScheduleValidationError),
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from replacing a [Schedule](super::Schedule)
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateScheduleError {
+    #[error("schedule with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Invalid(#[from] ScheduleValidationError),
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl From<CreateScheduleError> for UpdateScheduleError {
+    fn from(value: CreateScheduleError) -> Self {
+        match value {
+            CreateScheduleError::Invalid(e) => UpdateScheduleError::Invalid(e),
+            CreateScheduleError::Unknown(e) => UpdateScheduleError::Unknown(e),
+        }
+    }
+}
+
+/// Specifies errors that may arise from getting a [Schedule](super::Schedule)
+#[derive(Debug, thiserror::Error)]
+pub enum GetScheduleError {
+    #[error("schedule with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from listing [Schedule](super::Schedule)s
+#[derive(Debug, thiserror::Error)]
+pub enum ListSchedulesError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from deleting a [Schedule](super::Schedule)
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteScheduleError {
+    #[error("schedule with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from listing the
+/// [UpcomingOccurrence](super::UpcomingOccurrence)s of the schedules
+#[derive(Debug, thiserror::Error)]
+pub enum ListUpcomingOccurrencesError {
+    #[error("occurrences can be listed up to {max} days ahead, not until {until}")]
+    TooFarAhead { until: NaiveDate, max: u64 },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from skipping an occurrence of a
+/// [Schedule](super::Schedule)
+#[derive(Debug, thiserror::Error)]
+pub enum SkipOccurrenceError {
+    #[error("schedule with id {id} was not found")]
+    NotFound { id: Uuid },
+    #[error("schedule with id {id} has no occurrence on {date}")]
+    NotAnOccurrence { id: Uuid, date: NaiveDate },
+    #[error("the occurrence on {date} of schedule with id {id} was already posted")]
+    AlreadyPosted { id: Uuid, date: NaiveDate },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+/// Specifies errors that may arise from posting the occurrences of the
+/// [Schedule](super::Schedule)s that came due
+#[derive(Debug, thiserror::Error)]
+pub enum PostDueOccurrencesError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
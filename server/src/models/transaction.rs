@@ -1,51 +1,85 @@
 pub mod errors;
 
 use axum::Form;
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone as _, Utc};
+use chrono_tz::Tz;
 use derive_more::derive::Display;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::models::category::CategoryName;
+use crate::models::currency::{Currency, ExchangeRate};
+use crate::models::installment::{Installment, InstallmentPlan, split};
+use crate::models::schedule::Occurrence;
 use crate::utils::NonemptyStringVisitor;
 
 pub use errors::*;
 
-/// A uniquely identifiable monetary transaction between two [Account]s.
-/// All amounts are represented as cents.
+/// A uniquely identifiable journal entry, made of two or more [Leg]s whose amounts sum up to
+/// zero. All amounts are represented as cents.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct Transaction {
     id: Uuid,
     title: TransactionTitle,
-    amount: Decimal,
-    source_account_id: Uuid,
-    destination_account_id: Uuid,
-    category: Option<String>,
+    legs: Vec<Leg>,
+    category: Option<CategoryName>,
     /// The moment the transaction happened.
     posting_date: DateTime<Utc>,
+    /// Who the transaction was paid to or received from
+    #[serde(default)]
+    payee_id: Option<Uuid>,
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Which installment of a purchase the transaction is, if it was paid in installments
+    #[serde(default)]
+    installment: Option<Installment>,
+    /// The rate legs in two currencies were converted at
+    #[serde(default)]
+    exchange_rate: Option<ExchangeRate>,
 }
 
 impl Transaction {
     pub fn new(
         id: Uuid,
         title: TransactionTitle,
-        amount: Decimal,
-        source_account_id: Uuid,
-        destination_account_id: Uuid,
-        category: Option<String>,
+        legs: Vec<Leg>,
+        category: Option<CategoryName>,
         posting_date: DateTime<Utc>,
     ) -> Self {
         Self {
             id,
             title,
-            amount,
-            source_account_id,
-            destination_account_id,
+            legs,
             category,
             posting_date,
+            payee_id: None,
+            tags: Vec::new(),
+            installment: None,
+            exchange_rate: None,
         }
     }
 
+    pub fn with_payee(mut self, payee_id: Option<Uuid>) -> Self {
+        self.payee_id = payee_id;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    pub fn with_installment(mut self, installment: Option<Installment>) -> Self {
+        self.installment = installment;
+        self
+    }
+
+    pub fn with_exchange_rate(mut self, exchange_rate: Option<ExchangeRate>) -> Self {
+        self.exchange_rate = exchange_rate;
+        self
+    }
+
     pub fn id(&self) -> Uuid {
         self.id
     }
@@ -54,25 +88,155 @@ impl Transaction {
         &self.title
     }
 
+    pub fn legs(&self) -> &[Leg] {
+        &self.legs
+    }
+
+    /// The total amount moved by this transaction, i.e. the sum of all positive [Leg]s.
     pub fn amount(&self) -> Decimal {
-        self.amount
+        total_debits(&self.legs)
     }
 
+    /// The [Transaction::amount] written in the currency of the legs it adds up
     pub fn display_amount(&self) -> String {
-        format!("R$ {}", self.amount.round_dp(2))
+        let currency = self
+            .legs
+            .iter()
+            .find(|leg| leg.amount().is_sign_positive())
+            .map(Leg::currency)
+            .unwrap_or_default();
+
+        currency.format(self.amount())
+    }
+
+    pub fn category(&self) -> &Option<CategoryName> {
+        &self.category
+    }
+
+    pub fn posting_date(&self) -> DateTime<Utc> {
+        self.posting_date
+    }
+
+    pub fn payee_id(&self) -> Option<Uuid> {
+        self.payee_id
     }
 
-    pub fn from_account(&self) -> Uuid {
-        self.source_account_id
+    pub fn tags(&self) -> &[String] {
+        &self.tags
     }
 
-    pub fn to_account(&self) -> Uuid {
-        self.destination_account_id
+    pub fn installment(&self) -> Option<Installment> {
+        self.installment
     }
 
-    pub fn category(&self) -> &Option<String> {
+    pub fn exchange_rate(&self) -> Option<ExchangeRate> {
+        self.exchange_rate
+    }
+}
+
+/// One side of a [Transaction]: an amount that is added to an [Account]'s balance.
+///
+/// A positive amount increases the balance of the account, a negative one decreases it. The
+/// amount is counted in the currency of the account, see [denominate].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Leg {
+    account_id: Uuid,
+    amount: Decimal,
+    #[serde(default)]
+    currency: Currency,
+}
+
+impl Leg {
+    pub fn new(account_id: Uuid, amount: Decimal) -> Self {
+        Self {
+            account_id,
+            amount,
+            currency: Currency::default(),
+        }
+    }
+
+    pub fn with_currency(mut self, currency: Currency) -> Self {
+        self.currency = currency;
+        self
+    }
+
+    pub fn account_id(&self) -> Uuid {
+        self.account_id
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+}
+
+/// A [Transaction] seen from one of its accounts, like a line of a bank statement.
+///
+/// Both the amount and the balance follow the account type's sign convention.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountPosting {
+    transaction_id: Uuid,
+    title: TransactionTitle,
+    category: Option<CategoryName>,
+    posting_date: DateTime<Utc>,
+    /// What the transaction added to the account's balance
+    amount: Decimal,
+    /// The account's balance right after the transaction
+    balance: Decimal,
+}
+
+impl AccountPosting {
+    pub fn new(
+        transaction_id: Uuid,
+        title: TransactionTitle,
+        category: Option<CategoryName>,
+        posting_date: DateTime<Utc>,
+        amount: Decimal,
+        balance: Decimal,
+    ) -> Self {
+        Self {
+            transaction_id,
+            title,
+            category,
+            posting_date,
+            amount,
+            balance,
+        }
+    }
+
+    pub fn transaction_id(&self) -> Uuid {
+        self.transaction_id
+    }
+
+    pub fn title(&self) -> &TransactionTitle {
+        &self.title
+    }
+
+    pub fn category(&self) -> &Option<CategoryName> {
         &self.category
     }
+
+    pub fn posting_date(&self) -> DateTime<Utc> {
+        self.posting_date
+    }
+
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    pub fn balance(&self) -> Decimal {
+        self.balance
+    }
+}
+
+fn total_debits(legs: &[Leg]) -> Decimal {
+    legs.iter()
+        .map(Leg::amount)
+        .filter(|a| a.is_sign_positive())
+        .sum()
 }
 
 /// A valid transaction title
@@ -110,58 +274,607 @@ impl<'de> Deserialize<'de> for TransactionTitle {
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct CreateTransactionRequest {
     title: TransactionTitle,
-    amount: Decimal,
-    source_account_id: Uuid,
-    destination_account_id: Uuid,
-    category: Option<String>,
-    posting_date: Option<NaiveDateTime>,
+    legs: Vec<Leg>,
+    category: Option<CategoryName>,
+    posting_date: Option<DateTime<Utc>>,
+    /// Identifies the imported row the transaction comes from, see [Self::with_import_fingerprint]
+    import_fingerprint: Option<String>,
+    /// The import batch that created the transaction, if it was imported
+    import_batch_id: Option<Uuid>,
+    payee_id: Option<Uuid>,
+    tags: Vec<String>,
+    /// How many monthly installments the transaction is split into, see
+    /// [Self::with_installments]
+    installment_count: u32,
+    /// The installment the transaction is, when it belongs to an existing plan
+    installment: Option<Installment>,
+    /// The plan of [Self::installment] when it is new, saved along with the transaction
+    new_installment_plan: Option<InstallmentPlan>,
+    /// The occurrence of a schedule the transaction is posted for
+    occurrence: Option<Occurrence>,
+    /// The rate to convert legs in two currencies at, see [denominate]
+    exchange_rate: Option<ExchangeRate>,
 }
 
 impl CreateTransactionRequest {
     /// Create a new [CreateTransactionRequest].
     ///
-    /// If `date` is [None], it defaults to
+    /// If `posting_date` is [None], it defaults to the current time.
     pub fn new(
+        title: TransactionTitle,
+        legs: Vec<Leg>,
+        category: Option<CategoryName>,
+        posting_date: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            title,
+            legs,
+            category,
+            posting_date,
+            import_fingerprint: None,
+            import_batch_id: None,
+            payee_id: None,
+            tags: Vec::new(),
+            installment_count: 1,
+            installment: None,
+            new_installment_plan: None,
+            occurrence: None,
+            exchange_rate: None,
+        }
+    }
+
+    /// Set the fingerprint of the imported row the transaction comes from, like the id the bank
+    /// gave it or a hash of its fields. No two transactions can have the same fingerprint, so
+    /// that importing overlapping files does not duplicate transactions.
+    pub fn with_import_fingerprint(mut self, fingerprint: impl Into<String>) -> Self {
+        self.import_fingerprint = Some(fingerprint.into());
+        self
+    }
+
+    /// Tag the transaction with the import batch creating it, so that it is deleted when the
+    /// batch is rolled back
+    pub fn with_import_batch(mut self, import_batch_id: Uuid) -> Self {
+        self.import_batch_id = Some(import_batch_id);
+        self
+    }
+
+    /// Set who the transaction was paid to or received from
+    pub fn with_payee(mut self, payee_id: Uuid) -> Self {
+        self.payee_id = Some(payee_id);
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Split the transaction into `count` monthly installments, the first one posted at the
+    /// posting date. Each leg's amount is spread over the installments, which are all created
+    /// at once and linked to an [InstallmentPlan](crate::models::installment::InstallmentPlan).
+    pub fn with_installments(mut self, count: u32) -> Self {
+        self.installment_count = count;
+        self
+    }
+
+    /// Link the transaction to an existing installment plan, like an imported "parcela 3/10"
+    pub fn with_installment(mut self, installment: Installment) -> Self {
+        self.installment = Some(installment);
+        self
+    }
+
+    /// Link the transaction to a new installment plan, which is saved along with it, like for
+    /// the first imported installment of a purchase
+    pub fn with_new_installment_plan(mut self, plan: InstallmentPlan, number: u32) -> Self {
+        self.installment = Some(Installment {
+            plan_id: plan.id(),
+            number,
+        });
+        self.new_installment_plan = Some(plan);
+        self
+    }
+
+    /// Link the transaction to the occurrence of a schedule it is posted for. Each occurrence
+    /// is posted at most once.
+    pub fn with_occurrence(mut self, occurrence: Occurrence) -> Self {
+        self.occurrence = Some(occurrence);
+        self
+    }
+
+    /// Set the rate to convert legs in two currencies at, which is recorded along with the
+    /// transaction
+    pub fn with_exchange_rate(mut self, exchange_rate: ExchangeRate) -> Self {
+        self.exchange_rate = Some(exchange_rate);
+        self
+    }
+
+    /// Replace the legs, like with the ones counted in the currencies of their accounts
+    pub fn with_legs(mut self, legs: Vec<Leg>) -> Self {
+        self.legs = legs;
+        self
+    }
+
+    /// Create a [CreateTransactionRequest] that moves `amount` from one account to another.
+    pub fn transfer(
         title: TransactionTitle,
         amount: Decimal,
         source_account_id: Uuid,
         destination_account_id: Uuid,
-        category: Option<String>,
-        posting_date: Option<NaiveDateTime>,
+        category: Option<CategoryName>,
+        posting_date: Option<DateTime<Utc>>,
     ) -> Self {
-        Self {
+        Self::new(
             title,
-            amount,
-            source_account_id,
-            destination_account_id,
+            vec![
+                Leg::new(source_account_id, -amount),
+                Leg::new(destination_account_id, amount),
+            ],
             category,
             posting_date,
-        }
+        )
     }
 
     pub fn title(&self) -> &TransactionTitle {
         &self.title
     }
 
+    pub fn legs(&self) -> &[Leg] {
+        &self.legs
+    }
+
+    /// The total amount moved by this request, i.e. the sum of all positive [Leg]s.
     pub fn amount(&self) -> Decimal {
-        self.amount
+        total_debits(&self.legs)
     }
 
-    pub fn source_account_id(&self) -> Uuid {
-        self.source_account_id
+    /// The sum of all [Leg] amounts. A valid transaction always has an imbalance of zero.
+    pub fn imbalance(&self) -> Decimal {
+        imbalance(&self.legs)
     }
 
-    pub fn destination_account_id(&self) -> Uuid {
-        self.destination_account_id
+    pub fn category(&self) -> &Option<CategoryName> {
+        &self.category
+    }
+
+    pub fn posting_date(&self) -> Option<DateTime<Utc>> {
+        self.posting_date
     }
 
-    pub fn category(&self) -> &Option<String> {
+    pub fn import_fingerprint(&self) -> Option<&str> {
+        self.import_fingerprint.as_deref()
+    }
+
+    pub fn import_batch_id(&self) -> Option<Uuid> {
+        self.import_batch_id
+    }
+
+    pub fn payee_id(&self) -> Option<Uuid> {
+        self.payee_id
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn installment_count(&self) -> u32 {
+        self.installment_count
+    }
+
+    pub fn installment(&self) -> Option<Installment> {
+        self.installment
+    }
+
+    pub fn new_installment_plan(&self) -> Option<&InstallmentPlan> {
+        self.new_installment_plan.as_ref()
+    }
+
+    pub fn exchange_rate(&self) -> Option<ExchangeRate> {
+        self.exchange_rate
+    }
+
+    pub fn occurrence(&self) -> Option<Occurrence> {
+        self.occurrence
+    }
+
+    /// The account paying for the transaction, the one of its first credited leg
+    pub fn paying_account_id(&self) -> Option<Uuid> {
+        self.legs
+            .iter()
+            .find(|leg| leg.amount().is_sign_negative() && !leg.amount().is_zero())
+            .map(Leg::account_id)
+    }
+
+    /// The transactions of the installments of `plan`, each moving its share of every leg. The
+    /// last leg takes what is left after rounding the others, so that every installment is
+    /// balanced. Only the first installment keeps the import fingerprint.
+    pub fn split_into_installments(&self, plan: &InstallmentPlan) -> Vec<Self> {
+        let Some((last, others)) = self.legs.split_last() else {
+            return vec![];
+        };
+        let shares: Vec<Vec<Decimal>> = others
+            .iter()
+            .map(|leg| split(leg.amount(), plan.installment_count()))
+            .collect();
+        // Legs in another currency cannot make up for the rounding of the others
+        let last_shares = split(last.amount(), plan.installment_count());
+        let single_currency = others.iter().all(|leg| leg.currency() == last.currency());
+
+        (1..=plan.installment_count())
+            .map(|number| {
+                let mut legs: Vec<Leg> = others
+                    .iter()
+                    .zip(&shares)
+                    .map(|(leg, shares)| {
+                        Leg::new(leg.account_id(), shares[number as usize - 1])
+                            .with_currency(leg.currency())
+                    })
+                    .collect();
+                let last_amount = if single_currency {
+                    -imbalance(&legs)
+                } else {
+                    last_shares[number as usize - 1]
+                };
+                legs.push(Leg::new(last.account_id(), last_amount).with_currency(last.currency()));
+
+                Self {
+                    legs,
+                    posting_date: Some(plan.posting_date(number)),
+                    import_fingerprint: self.import_fingerprint.clone().filter(|_| number == 1),
+                    installment_count: 1,
+                    installment: Some(Installment {
+                        plan_id: plan.id(),
+                        number,
+                    }),
+                    ..self.clone()
+                }
+            })
+            .collect()
+    }
+}
+
+/// The fields that may be changed on an existing [Transaction]. Fields set to [None] are left
+/// as they are.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateTransactionRequest {
+    title: Option<TransactionTitle>,
+    legs: Option<LegsUpdate>,
+    category: Option<Option<CategoryName>>,
+    posting_date: Option<DateTime<Utc>>,
+    exchange_rate: Option<ExchangeRate>,
+}
+
+impl UpdateTransactionRequest {
+    /// Create a new [UpdateTransactionRequest].
+    ///
+    /// `category` is doubly optional: `Some(None)` removes the transaction's category.
+    pub fn new(
+        title: Option<TransactionTitle>,
+        legs: Option<LegsUpdate>,
+        category: Option<Option<CategoryName>>,
+        posting_date: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            title,
+            legs,
+            category,
+            posting_date,
+            exchange_rate: None,
+        }
+    }
+
+    /// Set the rate to convert the new legs at, in place of the one recorded with the
+    /// transaction
+    pub fn with_exchange_rate(mut self, exchange_rate: ExchangeRate) -> Self {
+        self.exchange_rate = Some(exchange_rate);
+        self
+    }
+
+    pub fn title(&self) -> &Option<TransactionTitle> {
+        &self.title
+    }
+
+    pub fn legs(&self) -> &Option<LegsUpdate> {
+        &self.legs
+    }
+
+    pub fn category(&self) -> &Option<Option<CategoryName>> {
         &self.category
     }
 
-    pub fn posting_date(&self) -> Option<NaiveDateTime> {
+    pub fn posting_date(&self) -> Option<DateTime<Utc>> {
         self.posting_date
     }
+
+    pub fn exchange_rate(&self) -> Option<ExchangeRate> {
+        self.exchange_rate
+    }
+}
+
+/// A change to the [Leg]s of a [Transaction]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LegsUpdate {
+    /// Replace all of the legs
+    Replace(Vec<Leg>),
+    /// Change the amount or the accounts of a transaction that moves money from one account to
+    /// another, leaving the fields set to [None] as they are
+    Transfer {
+        amount: Option<Decimal>,
+        source_account_id: Option<Uuid>,
+        destination_account_id: Option<Uuid>,
+    },
+}
+
+impl LegsUpdate {
+    /// Compute the new legs of a transaction from its current ones.
+    ///
+    /// Returns [None] when changing the transfer fields of a transaction that is not a transfer,
+    /// i.e. one that does not have exactly a negative and a positive leg.
+    pub fn apply(&self, current: &[Leg]) -> Option<Vec<Leg>> {
+        match self {
+            LegsUpdate::Replace(legs) => Some(legs.clone()),
+            LegsUpdate::Transfer {
+                amount,
+                source_account_id,
+                destination_account_id,
+            } => {
+                let [first, second] = current else {
+                    return None;
+                };
+                let (source, destination) = match (
+                    first.amount().is_sign_negative(),
+                    second.amount().is_sign_negative(),
+                ) {
+                    (true, false) => (first, second),
+                    (false, true) => (second, first),
+                    _ => return None,
+                };
+                // Amounts are counted in the currency of the source, see [denominate]
+                let amount = amount.unwrap_or(-source.amount());
+
+                Some(vec![
+                    Leg::new(source_account_id.unwrap_or(source.account_id()), -amount),
+                    Leg::new(
+                        destination_account_id.unwrap_or(destination.account_id()),
+                        amount,
+                    ),
+                ])
+            }
+        }
+    }
+}
+
+/// Criteria to select [Transaction]s when listing them. Criteria set to [None] match every
+/// transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionFilters {
+    /// Only transactions with a [Leg] on this account
+    pub account_id: Option<Uuid>,
+    /// Only transactions posted at or after this moment
+    pub from: Option<DateTime<Utc>>,
+    /// Only transactions posted before this moment
+    pub to: Option<DateTime<Utc>>,
+    /// Only transactions whose [Transaction::amount] is at least this much
+    pub min_amount: Option<Decimal>,
+    /// Only transactions whose [Transaction::amount] is at most this much
+    pub max_amount: Option<Decimal>,
+    /// Only transactions in this category or any of its subcategories
+    pub category: Option<CategoryName>,
+    /// Only transactions whose title contains this text, ignoring case
+    pub title: Option<String>,
+}
+
+/// How to order [Transaction]s when listing them
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransactionSort {
+    pub field: TransactionSortField,
+    pub direction: SortDirection,
+}
+
+/// The field [Transaction]s are ordered by. Ties are broken by the transaction id.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionSortField {
+    #[default]
+    PostingDate,
+    Amount,
+    Title,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// Points at a [Transaction] in a list ordered by posting date, so the next page can start right
+/// after it. Ties on the posting date are broken by the transaction id.
+///
+/// Its string form is opaque to clients: they should only pass it back as they received it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransactionCursor {
+    pub posting_date: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl TransactionCursor {
+    pub fn new(transaction: &Transaction) -> Self {
+        Self {
+            posting_date: transaction.posting_date(),
+            id: transaction.id(),
+        }
+    }
+}
+
+impl From<&AccountPosting> for TransactionCursor {
+    fn from(posting: &AccountPosting) -> Self {
+        Self {
+            posting_date: posting.posting_date(),
+            id: posting.transaction_id(),
+        }
+    }
+}
+
+impl std::fmt::Display for TransactionCursor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}_{}",
+            self.posting_date.timestamp_micros(),
+            self.id.simple()
+        )
+    }
+}
+
+impl std::str::FromStr for TransactionCursor {
+    type Err = TransactionCursorParseError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        let invalid = || TransactionCursorParseError {
+            raw: raw.to_string(),
+        };
+        let (micros, id) = raw.split_once('_').ok_or_else(invalid)?;
+        let micros = micros.parse::<i64>().map_err(|_| invalid())?;
+
+        Ok(Self {
+            posting_date: DateTime::from_timestamp_micros(micros).ok_or_else(invalid)?,
+            id: Uuid::try_parse(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// The sum of the amounts of the given [Leg]s. A valid transaction in a single currency always
+/// has an imbalance of zero.
+pub fn imbalance(legs: &[Leg]) -> Decimal {
+    legs.iter().map(Leg::amount).sum()
+}
+
+/// Count each of the `legs` in the [Currency] of its account, as given by `currency_of`.
+///
+/// Legs can be in at most two currencies, and legs in two currencies need an `exchange_rate`
+/// between them. When their amounts sum up to zero as they are, they are taken to be in the
+/// currency of the first leg, and the ones in the other currency are converted and rounded to
+/// cents. Otherwise each amount is taken to be in the currency of its leg already, and the legs
+/// must balance once converted, give or take half a cent.
+///
+/// # Errors
+///
+/// - [CreateTransactionError::Unbalanced] if the legs do not sum up to zero
+/// - [CreateTransactionError::TooManyCurrencies] if the legs are in more than two currencies
+/// - [CreateTransactionError::MissingExchangeRate] if the legs are in two currencies, but there
+///   is no exchange rate
+/// - [CreateTransactionError::ExchangeRateMismatch] if the exchange rate does not convert between
+///   the currencies of the legs
+pub fn denominate(
+    legs: &[Leg],
+    currency_of: impl Fn(Uuid) -> Currency,
+    exchange_rate: Option<&ExchangeRate>,
+) -> Result<Vec<Leg>, CreateTransactionError> {
+    let legs: Vec<Leg> = legs
+        .iter()
+        .map(|leg| leg.clone().with_currency(currency_of(leg.account_id())))
+        .collect();
+    let mut currencies: Vec<Currency> = Vec::new();
+    for leg in &legs {
+        if !currencies.contains(&leg.currency()) {
+            currencies.push(leg.currency());
+        }
+    }
+
+    let (first, other) = match (currencies.as_slice(), exchange_rate) {
+        ([], None) | ([_], None) => {
+            let imbalance = imbalance(&legs);
+            if !imbalance.is_zero() {
+                return Err(CreateTransactionError::Unbalanced { imbalance });
+            }
+
+            return Ok(legs);
+        }
+        (&[first, other], Some(rate)) if rate.converts(first, other) => (first, other),
+        (&[first, other], None) => {
+            return Err(CreateTransactionError::MissingExchangeRate {
+                from: first,
+                to: other,
+            });
+        }
+        (_, Some(rate)) if currencies.len() <= 2 => {
+            return Err(CreateTransactionError::ExchangeRateMismatch {
+                base: rate.base(),
+                quote: rate.quote(),
+            });
+        }
+        _ => return Err(CreateTransactionError::TooManyCurrencies { currencies }),
+    };
+    let rate = exchange_rate.expect("only legs in two currencies with a rate get here");
+
+    if imbalance(&legs).is_zero() {
+        return Ok(legs
+            .into_iter()
+            .map(|leg| {
+                if leg.currency() == other {
+                    let amount = rate
+                        .convert(leg.amount(), first)
+                        .expect("the rate converts the first currency");
+                    Leg::new(leg.account_id(), amount.round_dp(2)).with_currency(other)
+                } else {
+                    leg
+                }
+            })
+            .collect());
+    }
+
+    let imbalance: Decimal = legs
+        .iter()
+        .map(|leg| {
+            if leg.currency() == first {
+                rate.convert(leg.amount(), first)
+                    .expect("the rate converts the first currency")
+            } else {
+                leg.amount()
+            }
+        })
+        .sum();
+    if !imbalance.round_dp(2).is_zero() {
+        return Err(CreateTransactionError::Unbalanced { imbalance });
+    }
+
+    Ok(legs)
+}
+
+/// Parse the posting date of a [Transaction].
+///
+/// Dates with an explicit UTC offset, like `2025-01-06T14:56:00-03:00`, are kept as they are.
+/// Dates without one, like `2025-01-06T14:56:00` or `2025-01-06`, are interpreted as local times
+/// in the given `timezone`, with plain dates standing for midnight.
+pub fn parse_posting_date(raw: &str, timezone: Tz) -> Result<DateTime<Utc>, PostingDateParseError> {
+    let raw = raw.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(date.to_utc());
+    }
+
+    let naive = NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M:%S%.f")
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%d %H:%M:%S%.f"))
+        .or_else(|_| NaiveDate::parse_from_str(raw, "%Y-%m-%d").map(|d| d.and_time(NaiveTime::MIN)))
+        .map_err(|_| PostingDateParseError::Invalid {
+            raw: raw.to_string(),
+        })?;
+
+    local_to_utc(naive, timezone)
+}
+
+/// Interpret a date and time without an offset as a local time in the given `timezone`.
+///
+/// Times that happen twice, when clocks are turned back, resolve to the earliest one.
+pub fn local_to_utc(
+    naive: NaiveDateTime,
+    timezone: Tz,
+) -> Result<DateTime<Utc>, PostingDateParseError> {
+    timezone
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|date| date.to_utc())
+        .ok_or(PostingDateParseError::NonexistentLocalTime { naive, timezone })
 }
 
 #[cfg(test)]
@@ -170,7 +883,14 @@ mod tests {
     use rust_decimal_macros::dec;
     use uuid::Uuid;
 
-    use crate::models::transaction::TransactionTitle;
+    use chrono::{NaiveDate, TimeZone as _};
+    use chrono_tz::Tz;
+
+    use crate::models::installment::{Installment, InstallmentPlan};
+    use crate::models::transaction::{
+        CreateTransactionRequest, Leg, LegsUpdate, TransactionCursor, TransactionTitle,
+        parse_posting_date,
+    };
 
     use super::Transaction;
 
@@ -179,9 +899,10 @@ mod tests {
         let tx = Transaction::new(
             Uuid::new_v4(),
             TransactionTitle::new("test").unwrap(),
-            dec!(42),
-            Uuid::new_v4(),
-            Uuid::new_v4(),
+            vec![
+                Leg::new(Uuid::new_v4(), dec!(-42)),
+                Leg::new(Uuid::new_v4(), dec!(42)),
+            ],
             None,
             Utc::now(),
         );
@@ -195,9 +916,61 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn split_into_installments_balances_every_installment() {
+        let (card, groceries, fees) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let posting_date = Utc.with_ymd_and_hms(2026, 1, 31, 3, 0, 0).unwrap();
+        let req = CreateTransactionRequest::new(
+            TransactionTitle::new("Loja X").unwrap(),
+            vec![
+                Leg::new(groceries, dec!(90)),
+                Leg::new(fees, dec!(10)),
+                Leg::new(card, dec!(-100)),
+            ],
+            None,
+            Some(posting_date),
+        )
+        .with_import_fingerprint("fingerprint");
+        let plan = InstallmentPlan::new(
+            Uuid::new_v4(),
+            req.title().clone(),
+            req.paying_account_id().unwrap(),
+            req.amount(),
+            3,
+            posting_date,
+        );
+
+        let installments = req.split_into_installments(&plan);
+
+        assert_eq!(card, plan.account_id());
+        assert_eq!(3, installments.len());
+        assert_eq!(
+            vec![
+                Leg::new(groceries, dec!(30)),
+                Leg::new(fees, dec!(3.34)),
+                Leg::new(card, dec!(-33.34)),
+            ],
+            installments[0].legs()
+        );
+        assert!(installments.iter().all(|i| i.imbalance().is_zero()));
+        assert_eq!(Some("fingerprint"), installments[0].import_fingerprint());
+        assert_eq!(None, installments[1].import_fingerprint());
+        assert_eq!(
+            Some(Utc.with_ymd_and_hms(2026, 2, 28, 3, 0, 0).unwrap()),
+            installments[1].posting_date()
+        );
+        assert_eq!(
+            Some(Installment {
+                plan_id: plan.id(),
+                number: 3
+            }),
+            installments[2].installment()
+        );
+    }
+
     #[test]
     fn deserialize_json() {
-        let raw = "{\"id\":\"b309f1bc-c1b6-4dd4-8404-c8c8627025a9\",\"title\":\"test\",\"amount\":42,\"source_account_id\":\"ef342846-ecea-43e7-a69e-fea1686a2f7c\",\"destination_account_id\":\"0f08a046-0338-40b1-97c6-5feb925b6f84\",\"category\":null,\"posting_date\":\"2025-01-06T14:56:00.760515Z\"}";
+        let raw = "{\"id\":\"b309f1bc-c1b6-4dd4-8404-c8c8627025a9\",\"title\":\"test\",\"legs\":[{\"account_id\":\"ef342846-ecea-43e7-a69e-fea1686a2f7c\",\"amount\":-42},{\"account_id\":\"0f08a046-0338-40b1-97c6-5feb925b6f84\",\"amount\":42}],\"category\":null,\"posting_date\":\"2025-01-06T14:56:00.760515Z\"}";
 
         let result: Result<Transaction, serde_json::Error> = serde_json::from_str(raw);
 
@@ -206,9 +979,139 @@ mod tests {
 
     #[test]
     fn deserialize_invalid_json_no_title() {
-        let raw = "{\"id\":\"b309f1bc-c1b6-4dd4-8404-c8c8627025a9\",\"title\":\"\",\"amount\":42,\"source_account_id\":\"ef342846-ecea-43e7-a69e-fea1686a2f7c\",\"destination_account_id\":\"0f08a046-0338-40b1-97c6-5feb925b6f84\",\"category\":null,\"posting_date\":\"2025-01-06T14:56:00.760515Z\"}";
+        let raw = "{\"id\":\"b309f1bc-c1b6-4dd4-8404-c8c8627025a9\",\"title\":\"\",\"legs\":[{\"account_id\":\"ef342846-ecea-43e7-a69e-fea1686a2f7c\",\"amount\":-42},{\"account_id\":\"0f08a046-0338-40b1-97c6-5feb925b6f84\",\"amount\":42}],\"category\":null,\"posting_date\":\"2025-01-06T14:56:00.760515Z\"}";
         let result: Result<Transaction, serde_json::Error> = serde_json::from_str(raw);
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn transfer_creates_balanced_legs() {
+        let req = CreateTransactionRequest::transfer(
+            TransactionTitle::new("test").unwrap(),
+            dec!(42),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            None,
+            None,
+        );
+
+        assert_eq!(2, req.legs().len());
+        assert_eq!(dec!(0), req.imbalance());
+        assert_eq!(dec!(42), req.amount());
+    }
+
+    #[test]
+    fn split_legs_must_sum_to_zero() {
+        let title = TransactionTitle::new("paycheck").unwrap();
+        let balanced = CreateTransactionRequest::new(
+            title.clone(),
+            vec![
+                Leg::new(Uuid::new_v4(), dec!(-5000)),
+                Leg::new(Uuid::new_v4(), dec!(1100)),
+                Leg::new(Uuid::new_v4(), dec!(400)),
+                Leg::new(Uuid::new_v4(), dec!(3500)),
+            ],
+            None,
+            None,
+        );
+        assert_eq!(dec!(0), balanced.imbalance());
+        assert_eq!(dec!(5000), balanced.amount());
+
+        let unbalanced = CreateTransactionRequest::new(
+            title,
+            vec![
+                Leg::new(Uuid::new_v4(), dec!(-5000)),
+                Leg::new(Uuid::new_v4(), dec!(4999)),
+            ],
+            None,
+            None,
+        );
+        assert_eq!(dec!(-1), unbalanced.imbalance());
+    }
+
+    #[test]
+    fn posting_dates_with_offsets_are_kept() {
+        let date = parse_posting_date("2025-01-06T14:56:00-03:00", Tz::Asia__Tokyo).unwrap();
+
+        assert_eq!(Utc.with_ymd_and_hms(2025, 1, 6, 17, 56, 0).unwrap(), date);
+    }
+
+    #[test]
+    fn posting_dates_without_offsets_are_local_to_the_timezone() {
+        let date = parse_posting_date("2025-01-06T14:56:00", Tz::America__Sao_Paulo).unwrap();
+        assert_eq!(Utc.with_ymd_and_hms(2025, 1, 6, 17, 56, 0).unwrap(), date);
+
+        let date = parse_posting_date("2025-01-06", Tz::America__Sao_Paulo).unwrap();
+        assert_eq!(Utc.with_ymd_and_hms(2025, 1, 6, 3, 0, 0).unwrap(), date);
+
+        let date = parse_posting_date("2025-01-06", Tz::UTC).unwrap();
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2025, 1, 6).unwrap(),
+            date.date_naive()
+        );
+    }
+
+    #[test]
+    fn nonexistent_local_times_are_rejected() {
+        // Clocks jumped from 02:00 to 03:00 in New York on this day
+        assert!(parse_posting_date("2025-03-09T02:30:00", Tz::America__New_York).is_err());
+        assert!(parse_posting_date("yesterday", Tz::UTC).is_err());
+    }
+
+    #[test]
+    fn transfer_updates_keep_unchanged_fields() {
+        let (source, destination, other) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let current = vec![Leg::new(source, dec!(-42)), Leg::new(destination, dec!(42))];
+
+        let update = LegsUpdate::Transfer {
+            amount: Some(dec!(50)),
+            source_account_id: None,
+            destination_account_id: Some(other),
+        };
+
+        assert_eq!(
+            Some(vec![Leg::new(source, dec!(-50)), Leg::new(other, dec!(50))]),
+            update.apply(&current)
+        );
+    }
+
+    #[test]
+    fn transfer_updates_require_a_transfer() {
+        let current = vec![
+            Leg::new(Uuid::new_v4(), dec!(-42)),
+            Leg::new(Uuid::new_v4(), dec!(40)),
+            Leg::new(Uuid::new_v4(), dec!(2)),
+        ];
+        let update = LegsUpdate::Transfer {
+            amount: Some(dec!(50)),
+            source_account_id: None,
+            destination_account_id: None,
+        };
+
+        assert_eq!(None, update.apply(&current));
+    }
+
+    #[test]
+    fn transaction_cursors_round_trip() {
+        let cursor = TransactionCursor {
+            posting_date: Utc.with_ymd_and_hms(2026, 1, 5, 12, 30, 0).unwrap(),
+            id: Uuid::new_v4(),
+        };
+
+        assert_eq!(
+            Ok(cursor),
+            cursor
+                .to_string()
+                .parse::<TransactionCursor>()
+                .map_err(|_| ())
+        );
+    }
+
+    #[test]
+    fn malformed_transaction_cursors_are_rejected() {
+        for raw in ["", "123", "abc_def", &format!("x_{}", Uuid::new_v4())] {
+            assert!(raw.parse::<TransactionCursor>().is_err(), "{raw}");
+        }
+    }
 }
@@ -1,20 +1,114 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
+use crate::models::category::CategoryName;
+use crate::models::currency::Currency;
+
 #[derive(Clone, Debug, thiserror::Error)]
 #[error("transaction should have a nonempty title")]
 pub struct TransactionTitleEmptyError;
 
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("\"{raw}\" is not a valid transaction cursor")]
+pub struct TransactionCursorParseError {
+    pub raw: String,
+}
+
+/// Specifies errors that may arise from parsing the posting date of a [Transaction]
+#[derive(Clone, Debug, thiserror::Error)]
+pub enum PostingDateParseError {
+    #[error("\"{raw}\" is not a valid date")]
+    Invalid { raw: String },
+    #[error("{naive} does not exist in the {timezone} timezone")]
+    NonexistentLocalTime { naive: NaiveDateTime, timezone: Tz },
+}
+
 /// Specifies errors that may arise from creating a [Transaction]
 #[derive(Debug, thiserror::Error)]
 pub enum CreateTransactionError {
-    #[error("source account with id {id} was not found")]
-    SourceAccountNotFound { id: Uuid },
-    #[error("destination account with id {id} was not found")]
-    DestinationAccountNotFound { id: Uuid },
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error("category \"{name}\" was not found")]
+    CategoryNotFound { name: CategoryName },
+    #[error("transaction must have at least two legs")]
+    NotEnoughLegs,
+    #[error("transaction legs must sum to zero, but they sum to {imbalance}")]
+    Unbalanced { imbalance: Decimal },
+    #[error("a transaction with import fingerprint \"{fingerprint}\" already exists")]
+    Duplicate { fingerprint: String },
+    #[error("a transaction must be split into between 1 and {max} installments, not {count}")]
+    InvalidInstallmentCount { count: u32, max: u32 },
+    #[error("the occurrence on {date} of schedule with id {schedule_id} was already posted")]
+    OccurrencePosted { schedule_id: Uuid, date: NaiveDate },
+    #[error("transaction moves money between {from} and {to}, so it needs an exchange rate")]
+    MissingExchangeRate { from: Currency, to: Currency },
+    #[error("the exchange rate from {base} to {quote} does not match the currencies of the legs")]
+    ExchangeRateMismatch { base: Currency, quote: Currency },
+    #[error("transaction legs must be in at most two currencies, not {}", currencies.iter().map(Currency::code).collect::<Vec<_>>().join(", "))]
+    TooManyCurrencies { currencies: Vec<Currency> },
     #[error(transparent)]
     Unknown(#[from] anyhow::Error),
 }
 
+/// Specifies errors that may arise from updating a [Transaction]
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateTransactionError {
+    #[error("transaction with id {id} was not found")]
+    TransactionNotFound { id: Uuid },
+    #[error("account with id {id} was not found")]
+    AccountNotFound { id: Uuid },
+    #[error("category \"{name}\" was not found")]
+    CategoryNotFound { name: CategoryName },
+    #[error("transaction must have at least two legs")]
+    NotEnoughLegs,
+    #[error("transaction legs must sum to zero, but they sum to {imbalance}")]
+    Unbalanced { imbalance: Decimal },
+    #[error("transaction with id {id} does not move money between exactly two accounts")]
+    NotATransfer { id: Uuid },
+    #[error("transaction moves money between {from} and {to}, so it needs an exchange rate")]
+    MissingExchangeRate { from: Currency, to: Currency },
+    #[error("the exchange rate from {base} to {quote} does not match the currencies of the legs")]
+    ExchangeRateMismatch { base: Currency, quote: Currency },
+    #[error("transaction legs must be in at most two currencies, not {}", currencies.iter().map(Currency::code).collect::<Vec<_>>().join(", "))]
+    TooManyCurrencies { currencies: Vec<Currency> },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl From<CreateTransactionError> for UpdateTransactionError {
+    fn from(value: CreateTransactionError) -> Self {
+        match value {
+            CreateTransactionError::AccountNotFound { id } => {
+                UpdateTransactionError::AccountNotFound { id }
+            }
+            CreateTransactionError::CategoryNotFound { name } => {
+                UpdateTransactionError::CategoryNotFound { name }
+            }
+            CreateTransactionError::NotEnoughLegs => UpdateTransactionError::NotEnoughLegs,
+            CreateTransactionError::Unbalanced { imbalance } => {
+                UpdateTransactionError::Unbalanced { imbalance }
+            }
+            CreateTransactionError::MissingExchangeRate { from, to } => {
+                UpdateTransactionError::MissingExchangeRate { from, to }
+            }
+            CreateTransactionError::ExchangeRateMismatch { base, quote } => {
+                UpdateTransactionError::ExchangeRateMismatch { base, quote }
+            }
+            CreateTransactionError::TooManyCurrencies { currencies } => {
+                UpdateTransactionError::TooManyCurrencies { currencies }
+            }
+            err @ (CreateTransactionError::Duplicate { .. }
+            | CreateTransactionError::InvalidInstallmentCount { .. }
+            | CreateTransactionError::OccurrencePosted { .. }) => {
+                UpdateTransactionError::Unknown(err.into())
+            }
+            CreateTransactionError::Unknown(err) => UpdateTransactionError::Unknown(err),
+        }
+    }
+}
+
 /// Specifies errors that may arise from deleting a [Transaction]
 #[derive(Debug, thiserror::Error)]
 pub enum DeleteTransactionError {
@@ -41,3 +135,13 @@ pub enum ListTransactionsError {
     #[error(transparent)]
     SqlxError(#[from] sqlx::Error),
 }
+
+/// Specifies errors that may arise from listing the [AccountPosting](super::AccountPosting)s of
+/// an account
+#[derive(Debug, thiserror::Error)]
+pub enum ListAccountPostingsError {
+    #[error("account with id {id} not found")]
+    AccountNotFound { id: Uuid },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
@@ -1,19 +1,33 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Context;
 use axum::{
     Router,
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
 };
+use chrono_tz::Tz;
 use http::{HeaderValue, Method};
 use tower_http::cors::CorsLayer;
 
-use crate::{configuration::Settings, handlers, service::BerryService};
+use crate::{
+    configuration::{ImportSettings, Settings, get_import_profiles},
+    handlers,
+    import::csv::CsvProfile,
+    service::BerryService,
+};
 
 /// Global state shared by all request handlers
 #[derive(Debug, Clone)]
 pub struct AppState {
     pub service: Arc<BerryService>,
+    /// The timezone used to interpret dates and times without a UTC offset
+    pub timezone: Tz,
+    /// The named profiles CSV files are imported with
+    pub import_profiles: Arc<HashMap<String, CsvProfile>>,
+    /// Where imported transactions are booked
+    pub import_settings: ImportSettings,
 }
 
 /// The app's HTTP server
@@ -21,6 +35,9 @@ pub struct Server {
     router: axum::Router,
     listener: tokio::net::TcpListener,
     port: u16,
+    state: AppState,
+    /// How often the occurrences of scheduled transactions that came due are posted
+    scheduler_period: Duration,
 }
 
 impl Server {
@@ -38,16 +55,21 @@ impl Server {
             .allow_origin("*".parse::<HeaderValue>()?);
 
         let pool = BerryService::new(&config.database).await?;
+        let import_profiles =
+            get_import_profiles(None).context("failed to load import profiles")?;
 
         let state = AppState {
             service: Arc::new(pool),
+            timezone: config.application.timezone,
+            import_profiles: Arc::new(import_profiles),
+            import_settings: config.import.clone(),
         };
 
         let router = axum::Router::new()
             .nest("/api", api_routes())
             .layer(cors_layer)
             .layer(trace_layer)
-            .with_state(state);
+            .with_state(state.clone());
 
         let listener = tokio::net::TcpListener::bind(format!(
             "{}:{}",
@@ -65,11 +87,15 @@ impl Server {
             router,
             listener,
             port,
+            state,
+            scheduler_period: Duration::from_secs(config.application.scheduler_period.max(1)),
         })
     }
 
-    /// Runs the HTTP server.
+    /// Runs the HTTP server, and posts scheduled transactions in the background.
     pub async fn run(self) -> anyhow::Result<()> {
+        tokio::spawn(run_scheduler(self.state, self.scheduler_period));
+
         tracing::info!("listening on {}", self.listener.local_addr().unwrap());
         axum::serve(self.listener, self.router)
             .await
@@ -82,6 +108,27 @@ impl Server {
     }
 }
 
+/// Post the occurrences of scheduled transactions as they come due, every `period` starting
+/// right away.
+///
+/// Each run is a task of its own, so that a run that fails, or even panics, is logged and the
+/// next one still happens.
+async fn run_scheduler(state: AppState, period: Duration) {
+    let mut interval = tokio::time::interval(period);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        let state = state.clone();
+        let run =
+            tokio::spawn(async move { state.service.post_due_occurrences(state.timezone).await });
+        match run.await {
+            Ok(Ok(_)) => {}
+            Ok(Err(e)) => tracing::error!(error = ?e, "Failed to post scheduled transactions"),
+            Err(e) => tracing::error!(error = ?e, "Posting scheduled transactions panicked"),
+        }
+    }
+}
+
 fn api_routes() -> Router<AppState> {
     Router::new()
         .route("/accounts", post(handlers::create_account))
@@ -90,11 +137,79 @@ fn api_routes() -> Router<AppState> {
             "/accounts/find-by-name",
             get(handlers::find_account_by_name),
         )
+        .route("/accounts/tree", get(handlers::get_account_tree))
         .route("/accounts/{id}", get(handlers::get_account))
         .route("/accounts/{id}", delete(handlers::delete_account))
+        .route("/accounts/{id}/card", put(handlers::set_credit_card))
+        .route(
+            "/accounts/{id}/installments",
+            get(handlers::get_installment_commitments),
+        )
         .route("/accounts/{id}/name", patch(handlers::rename_account))
+        .route(
+            "/accounts/{id}/statements",
+            get(handlers::get_card_statements),
+        )
+        .route(
+            "/accounts/{id}/statements/{month}",
+            get(handlers::get_card_statement),
+        )
+        .route(
+            "/accounts/{id}/transactions",
+            get(handlers::list_account_postings),
+        )
+        .route("/budgets", post(handlers::create_budget))
+        .route("/budgets", get(handlers::get_budget_report))
+        .route("/budgets/{id}", patch(handlers::update_budget))
+        .route("/budgets/{id}", delete(handlers::delete_budget))
+        .route("/categories", post(handlers::create_category))
+        .route("/categories", get(handlers::list_categories))
+        .route("/categories/{id}", get(handlers::get_category))
+        .route("/categories/{id}", patch(handlers::update_category))
+        .route("/categories/{id}", delete(handlers::delete_category))
+        .route("/imports", post(handlers::create_import))
+        .route("/imports", get(handlers::list_import_batches))
+        .route("/imports/{id}", delete(handlers::rollback_import_batch))
+        .route("/payees", get(handlers::list_payees))
+        .route("/prices", post(handlers::create_price))
+        .route("/prices", get(handlers::list_prices))
+        .route("/prices/import", post(handlers::import_prices))
+        .route("/prices/lookup", get(handlers::lookup_price))
+        .route("/prices/{id}", get(handlers::get_price))
+        .route("/prices/{id}", put(handlers::update_price))
+        .route("/prices/{id}", delete(handlers::delete_price))
+        .route(
+            "/reports/balance-history",
+            get(handlers::get_balance_history),
+        )
+        .route("/reports/balance-sheet", get(handlers::get_balance_sheet))
+        .route(
+            "/reports/cash-flow-forecast",
+            get(handlers::get_cash_flow_forecast),
+        )
+        .route(
+            "/reports/income-statement",
+            get(handlers::get_income_statement),
+        )
+        .route("/rules", post(handlers::create_rule))
+        .route("/rules", get(handlers::list_rules))
+        .route("/rules/apply", post(handlers::apply_rules))
+        .route("/rules/{id}", get(handlers::get_rule))
+        .route("/rules/{id}", put(handlers::update_rule))
+        .route("/rules/{id}", delete(handlers::delete_rule))
+        .route("/schedules", post(handlers::create_schedule))
+        .route("/schedules", get(handlers::list_schedules))
+        .route(
+            "/schedules/upcoming",
+            get(handlers::list_upcoming_occurrences),
+        )
+        .route("/schedules/{id}", get(handlers::get_schedule))
+        .route("/schedules/{id}", put(handlers::update_schedule))
+        .route("/schedules/{id}", delete(handlers::delete_schedule))
+        .route("/schedules/{id}/skip", post(handlers::skip_occurrence))
         .route("/transactions", post(handlers::create_transaction))
         .route("/transactions", get(handlers::list_transactions))
         .route("/transactions/{id}", get(handlers::get_transaction))
         .route("/transactions/{id}", delete(handlers::delete_transaction))
+        .route("/transactions/{id}", patch(handlers::update_transaction))
 }
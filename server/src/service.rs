@@ -1,24 +1,86 @@
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
 use anyhow::{Context, anyhow};
 use chrono::DateTime;
+use chrono::Days;
+use chrono::Months;
+use chrono::NaiveDate;
+use chrono::SubsecRound as _;
 use chrono::Utc;
+use chrono_tz::Tz;
 use rust_decimal::Decimal;
-use rust_decimal_macros::dec;
+use serde::{Deserialize, Serialize};
 use sqlx::Executor;
 use sqlx::PgPool;
+use sqlx::Postgres;
+use sqlx::QueryBuilder;
 use sqlx::Row;
 use sqlx::postgres::PgPoolOptions;
 use uuid::Uuid;
 
 use crate::configuration::DatabaseSettings;
+use crate::import::{ImportRow, InvalidRow, read_rows};
 use crate::models::account::GetAccountByNameError;
 use crate::models::account::GetOrCreateAccountError;
 use crate::models::account::ListAccountsError;
-use crate::models::account::{Account, AccountName, CreateAccountError, GetAccountError};
+use crate::models::account::{
+    Account, AccountName, AccountTreeNode, AccountType, CreateAccountError, CreateAccountRequest,
+    GetAccountError,
+};
 use crate::models::account::{DeleteAccountError, UpdateAccountError};
+use crate::models::budget::{
+    Budget, BudgetLine, BudgetTarget, CreateBudgetError, CreateBudgetRequest, DeleteBudgetError,
+    GetBudgetReportError, Month, UpdateBudgetError, UpdateBudgetRequest,
+};
+use crate::models::category::{
+    Category, CategoryName, CreateCategoryError, DeleteCategoryError, GetCategoryError,
+    ListCategoriesError, UpdateCategoryError,
+};
+use crate::models::credit_card::{
+    CardStatements, CreditCard, GetStatementsError, SetCreditCardError, SetCreditCardRequest,
+    Statement,
+};
+use crate::models::currency::{Currency, ExchangeRate, ExchangeRates};
+use crate::models::forecast::{
+    AccountForecast, CashFlowForecast, ForecastChange, ForecastSource, GetCashFlowForecastError,
+    MAX_FORECAST_DAYS,
+};
+use crate::models::import_batch::{
+    ImportBatch, ImportError, ImportFormat, ImportReport, ImportRequest, ImportRowResult,
+    ImportRowStatus, ListImportBatchesError, RollbackImportBatchError,
+};
+use crate::models::installment::{
+    GetInstallmentCommitmentsError, Installment, InstallmentCommitment, InstallmentCommitments,
+    InstallmentMarker, InstallmentPlan, MAX_INSTALLMENTS,
+};
+use crate::models::payee::{GetOrCreatePayeeError, ListPayeesError, Payee, PayeeName};
+use crate::models::price::{
+    CreatePriceError, CreatePriceRequest, DeletePriceError, GetPriceError, ImportPricesError,
+    ListPricesError, LookupPriceError, Price, PriceImportReport, PriceQuote, UpdatePriceError,
+    read_prices,
+};
+use crate::models::report::{
+    BalanceHistory, BalanceSheet, GetBalanceHistoryError, GetReportError, IncomeStatement,
+    Interval, Valuation,
+};
+use crate::models::rule::{
+    ApplyRulesError, ApplyRulesReport, Change, CreateRuleError, CreateRuleRequest, DeleteRuleError,
+    GetRuleError, ListRulesError, Rule, RuleActions, RuleApplication, RuleConditions, RuleSet,
+    RuleSubject, RuleValidationError, UpdateRuleError, counterpart_leg,
+};
+use crate::models::schedule::{
+    CreateScheduleError, CreateScheduleRequest, DeleteScheduleError, GetScheduleError,
+    ListSchedulesError, ListUpcomingOccurrencesError, Occurrence, PostDueOccurrencesError,
+    Recurrence, Schedule, ScheduleValidationError, ScheduledTransfer, SkipOccurrenceError,
+    UpcomingOccurrence, UpdateScheduleError,
+};
 use crate::models::transaction::ListTransactionsError;
 use crate::models::transaction::{
-    CreateTransactionError, CreateTransactionRequest, DeleteTransactionError, GetTransactionError,
-    Transaction, TransactionTitle,
+    AccountPosting, CreateTransactionError, CreateTransactionRequest, DeleteTransactionError,
+    GetTransactionError, Leg, ListAccountPostingsError, SortDirection, Transaction,
+    TransactionCursor, TransactionFilters, TransactionSort, TransactionSortField, TransactionTitle,
+    UpdateTransactionError, UpdateTransactionRequest, denominate,
 };
 
 pub struct PaginationParameters {
@@ -26,6 +88,26 @@ pub struct PaginationParameters {
     pub offset: i64,
 }
 
+/// A page of items, along with how many items match the query across all pages
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub total: i64,
+    /// Where the next page starts, if there is one and the query supports cursors
+    pub next_cursor: Option<String>,
+}
+
+/// The part of a query that selects entries along with their amount, to which filters can be
+/// appended with [push_transaction_filters]
+const FILTERED_ENTRIES: &str = r##"
+FROM "entries" e
+CROSS JOIN LATERAL (
+    SELECT COALESCE(SUM(l.amount) FILTER (WHERE l.amount > 0), 0) AS amount
+    FROM "legs" l
+    WHERE l.entry_id = e.id
+) a
+WHERE TRUE"##;
+
 #[derive(Debug, Clone)]
 pub struct BerryService {
     pub pool: PgPool,
@@ -42,69 +124,244 @@ impl BerryService {
     async fn save_account(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
-        name: &AccountName,
+        req: &CreateAccountRequest,
     ) -> Result<Uuid, sqlx::Error> {
         let id = Uuid::new_v4();
-        let name = &name.to_string();
-        let query = sqlx::query!("INSERT INTO accounts (id, name) VALUES ($1, $2)", id, name,);
+        let name = &req.name().to_string();
+        let currency = req.currency();
+        let parent_id = self
+            .ensure_parent_accounts(tx, req.name(), req.account_type(), currency)
+            .await?;
+        let query = sqlx::query!(
+            "INSERT INTO accounts (id, name, account_type, parent_id, currency) VALUES ($1, $2, $3, $4, $5)",
+            id,
+            name,
+            req.account_type().as_str(),
+            parent_id,
+            currency.code(),
+        );
         tx.execute(query).await?;
         Ok(id)
     }
 
-    /// Store a [Transaction] in the database
+    /// Create the missing ancestors of an [Account] with the given name, returning the id of its
+    /// parent, if any.
+    ///
+    /// Missing ancestors are created with the given [AccountType] and [Currency]. Existing ones
+    /// are left as is.
+    async fn ensure_parent_accounts(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        name: &AccountName,
+        account_type: AccountType,
+        currency: Currency,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let mut parent_id: Option<Uuid> = None;
+        for ancestor in name.ancestors() {
+            let ancestor = ancestor.to_string();
+            sqlx::query!(
+                "
+INSERT INTO accounts (id, name, account_type, parent_id, currency)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (name) DO NOTHING
+",
+                Uuid::new_v4(),
+                ancestor,
+                account_type.as_str(),
+                parent_id,
+                currency.code(),
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            let id = sqlx::query_scalar!("SELECT id FROM accounts WHERE name = $1", ancestor)
+                .fetch_one(&mut **tx)
+                .await?;
+            parent_id = Some(id);
+        }
+
+        Ok(parent_id)
+    }
+
+    /// Create the missing ancestors of a [Category] with the given name, returning the id of its
+    /// parent, if any.
+    async fn ensure_parent_categories(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        name: &CategoryName,
+    ) -> Result<Option<Uuid>, sqlx::Error> {
+        let mut parent_id: Option<Uuid> = None;
+        for ancestor in name.ancestors() {
+            let ancestor = ancestor.to_string();
+            sqlx::query!(
+                "
+INSERT INTO categories (id, name, parent_id)
+VALUES ($1, $2, $3)
+ON CONFLICT (name) DO NOTHING
+",
+                Uuid::new_v4(),
+                ancestor,
+                parent_id,
+            )
+            .execute(&mut **tx)
+            .await?;
+
+            let id = sqlx::query_scalar!("SELECT id FROM categories WHERE name = $1", ancestor)
+                .fetch_one(&mut **tx)
+                .await?;
+            parent_id = Some(id);
+        }
+
+        Ok(parent_id)
+    }
+
+    /// Store a [Transaction] and its [Leg]s in the database
     async fn save_transaction(
         &self,
         tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
         req: &CreateTransactionRequest,
+        posting_date: DateTime<Utc>,
     ) -> Result<Uuid, sqlx::Error> {
         let id = Uuid::new_v4();
         let title = &req.title().to_string();
         let category = req.category().as_ref().map(|c| c.to_string());
-        let amount = req.amount();
-        let posting_date = Utc::now();
         let query = sqlx::query!(
-            "INSERT INTO postings (
-id, title, amount, source_account_id, destination_account_id, category, posting_date
-) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            r#"
+INSERT INTO entries (id, title, category, posting_date, import_fingerprint, import_batch_id, payee_id, tags,
+    installment_plan_id, installment_number, schedule_id, occurrence_date,
+    exchange_rate_base, exchange_rate_quote, exchange_rate)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+"#,
             id,
             title,
-            amount,
-            req.source_account_id(),
-            req.destination_account_id(),
             category,
-            posting_date
+            posting_date,
+            req.import_fingerprint(),
+            req.import_batch_id(),
+            req.payee_id(),
+            req.tags(),
+            req.installment().map(|installment| installment.plan_id),
+            req.installment()
+                .map(|installment| installment.number as i32),
+            req.occurrence().map(|occurrence| occurrence.schedule_id),
+            req.occurrence().map(|occurrence| occurrence.date),
+            req.exchange_rate()
+                .map(|rate| rate.base().code().to_string()),
+            req.exchange_rate()
+                .map(|rate| rate.quote().code().to_string()),
+            req.exchange_rate().map(|rate| rate.rate())
         );
         tx.execute(query).await?;
+        self.save_legs(tx, id, req.legs()).await?;
 
         Ok(id)
     }
 
-    /// Checks if both [Account]s with the given ids exist
-    async fn check_if_accounts_exist(
+    /// Store the [Leg]s of a [Transaction] in the database, in the given order
+    async fn save_legs(
         &self,
-        source_account_id: Uuid,
-        destination_account_id: Uuid,
-    ) -> Result<(), CreateTransactionError> {
-        self.get_account_by_id(source_account_id)
-            .await
-            .map_err(|e| match e {
-                GetAccountError::NotFound { id } => {
-                    CreateTransactionError::SourceAccountNotFound { id }
-                }
-                GetAccountError::Unknown(e) => CreateTransactionError::Unknown(e),
-            })?;
-        self.get_account_by_id(destination_account_id)
-            .await
-            .map_err(|e| match e {
-                GetAccountError::NotFound { id } => {
-                    CreateTransactionError::DestinationAccountNotFound { id }
-                }
-                GetAccountError::Unknown(e) => CreateTransactionError::Unknown(e),
-            })?;
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction_id: Uuid,
+        legs: &[Leg],
+    ) -> Result<(), sqlx::Error> {
+        for (position, leg) in legs.iter().enumerate() {
+            let currency = leg.currency();
+            let query = sqlx::query!(
+                "INSERT INTO legs (id, entry_id, position, account_id, amount, currency) VALUES ($1, $2, $3, $4, $5, $6)",
+                Uuid::new_v4(),
+                transaction_id,
+                position as i32,
+                leg.account_id(),
+                leg.amount(),
+                currency.code()
+            );
+            tx.execute(query).await?;
+        }
 
         Ok(())
     }
 
+    /// Fetch the [Leg]s of the given [Transaction]s through `executor`, grouped by transaction
+    /// id and in the order they were created. Pass the open transaction, if any, so that legs it
+    /// changed are seen.
+    async fn fetch_legs<'e>(
+        &self,
+        executor: impl Executor<'e, Database = Postgres>,
+        transaction_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Vec<Leg>>, sqlx::Error> {
+        let rows = sqlx::query!(
+            "SELECT entry_id, account_id, amount, currency FROM legs WHERE entry_id = ANY($1) ORDER BY entry_id, position",
+            transaction_ids
+        )
+        .fetch_all(executor)
+        .await?;
+
+        let mut legs: HashMap<Uuid, Vec<Leg>> = HashMap::new();
+        for row in rows {
+            let currency =
+                Currency::new(&row.currency).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+            legs.entry(row.entry_id)
+                .or_default()
+                .push(Leg::new(row.account_id, row.amount).with_currency(currency));
+        }
+
+        Ok(legs)
+    }
+
+    /// Checks that all of the [Account]s of the [Leg]s of a transaction exist, and that the legs
+    /// balance once counted in the currencies of their accounts. Returns the legs in those
+    /// currencies, see [denominate].
+    async fn validate_legs(
+        &self,
+        legs: &[Leg],
+        exchange_rate: Option<&ExchangeRate>,
+    ) -> Result<Vec<Leg>, CreateTransactionError> {
+        if legs.len() < 2 {
+            return Err(CreateTransactionError::NotEnoughLegs);
+        }
+
+        let mut currencies = HashMap::new();
+        for leg in legs {
+            let account = self
+                .get_account_by_id(leg.account_id())
+                .await
+                .map_err(|e| match e {
+                    GetAccountError::NotFound { id } => {
+                        CreateTransactionError::AccountNotFound { id }
+                    }
+                    GetAccountError::Unknown(e) => CreateTransactionError::Unknown(e),
+                })?;
+            currencies.insert(account.id(), account.currency());
+        }
+
+        denominate(legs, |id| currencies[&id], exchange_rate)
+    }
+
+    /// Checks that the category of a transaction, if it has one, exists
+    async fn validate_category<'e>(
+        &self,
+        executor: impl Executor<'e, Database = Postgres>,
+        category: Option<&CategoryName>,
+    ) -> Result<(), CreateTransactionError> {
+        let Some(name) = category else {
+            return Ok(());
+        };
+
+        let exists = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM categories WHERE name = $1) AS "exists!""#,
+            name.to_string()
+        )
+        .fetch_one(executor)
+        .await
+        .with_context(|| format!("failed to look up category {name}"))?;
+
+        if exists {
+            Ok(())
+        } else {
+            Err(CreateTransactionError::CategoryNotFound { name: name.clone() })
+        }
+    }
+
     /// Persists the [Account] balance change to the database
     ///
     /// # Errors
@@ -129,11 +386,14 @@ id, title, amount, source_account_id, destination_account_id, category, posting_
             _ => UpdateAccountError::Unknown(e.into()),
         })?;
 
-        Ok(Account::new(
+        Ok(account_from_row(
             row.id,
-            AccountName::new(&row.name).unwrap(),
+            &row.name,
+            &row.account_type,
+            row.parent_id,
+            &row.currency,
             row.balance,
-        ))
+        )?)
     }
 
     /// Helper to start a PostgreSQL transaction and avoid boilerplate
@@ -148,19 +408,27 @@ id, title, amount, source_account_id, destination_account_id, category, posting_
 
     /// Persists an [Account] to the database
     ///
+    /// Any missing ancestor of a hierarchical name is created along with it, so creating
+    /// `assets:bank:nubank` also creates `assets` and `assets:bank` if they do not exist yet.
+    ///
     /// # Errors
     ///
     /// - [CreateAccountError::Duplicate] if an [Account] with the given name already exists
     /// - [CreateAccountError::Unknown] if another kind of error occurred
-    pub async fn create_account(&self, req: &AccountName) -> Result<Account, CreateAccountError> {
+    pub async fn create_account(
+        &self,
+        req: &CreateAccountRequest,
+    ) -> Result<Account, CreateAccountError> {
         let mut tx = self.start_psql_transaction().await?;
 
         let account_id = self.save_account(&mut tx, req).await.map_err(|e| {
             if is_unique_constraint_violation(&e) {
-                CreateAccountError::Duplicate { name: req.clone() }
+                CreateAccountError::Duplicate {
+                    name: req.name().clone(),
+                }
             } else {
                 anyhow!(e)
-                    .context(format!("failed to save account with name {:?}", req))
+                    .context(format!("failed to save account with name {:?}", req.name()))
                     .into()
             }
         })?;
@@ -170,29 +438,83 @@ id, title, amount, source_account_id, destination_account_id, category, posting_
             .context("failed to commit PostgreSQL transaction")?;
 
         tracing::info!(?account_id, "Successfully created account");
-        Ok(Account::new(account_id, req.clone(), dec!(0)))
+        self.get_account_by_id(account_id)
+            .await
+            .map_err(|e| anyhow!(e).context("failed to fetch created account").into())
     }
 
-    /// List all accounts in the database.
+    /// List [Account]s ordered by name, optionally only the ones of the given [AccountType].
     ///
-    /// This does not support filters, **yet**. It will return an empty [Vec] if there are no
-    /// accounts in the database.
-    pub async fn list_accounts(&self) -> Result<Vec<Account>, ListAccountsError> {
-        let rows = sqlx::query!("SELECT * FROM accounts")
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| ListAccountsError::Unknown(e.into()))?;
+    /// With a `limit`, at most that many accounts are returned, starting right after the account
+    /// named `after`, and a page that is not the last one comes with a cursor to the next.
+    pub async fn list_accounts(
+        &self,
+        account_type: Option<AccountType>,
+        after: Option<&AccountName>,
+        limit: Option<i64>,
+    ) -> Result<Page<Account>, ListAccountsError> {
+        let account_type = account_type.map(|t| t.as_str());
+        let total = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM accounts WHERE $1::text IS NULL OR account_type = $1"#,
+            account_type
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| ListAccountsError::Unknown(e.into()))?;
 
-        Ok(rows
+        // Fetch one more row than requested to know whether there is a next page
+        let mut rows = sqlx::query!(
+            "
+SELECT * FROM accounts
+WHERE ($1::text IS NULL OR account_type = $1) AND ($2::text IS NULL OR name > $2)
+ORDER BY name
+LIMIT $3
+",
+            account_type,
+            after.map(|name| name.to_string()),
+            limit.map(|limit| limit + 1),
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| ListAccountsError::Unknown(e.into()))?;
+        let has_next_page = limit.is_some_and(|limit| rows.len() as i64 > limit);
+        if let Some(limit) = limit {
+            rows.truncate(limit.max(0) as usize);
+        }
+
+        let items = rows
             .iter()
             .map(|r| {
-                // Account names in the database are, by design, valid
-                let account_name = AccountName::new(&r.name).unwrap();
-                // Account ids in the database are, by design, valid UUIDs
-                tracing::debug!(id = ?r.id, account_name = ?account_name);
-                Account::new(r.id, account_name, r.balance)
+                tracing::debug!(id = ?r.id, account_name = ?r.name);
+                account_from_row(
+                    r.id,
+                    &r.name,
+                    &r.account_type,
+                    r.parent_id,
+                    &r.currency,
+                    r.balance,
+                )
             })
-            .collect())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let next_cursor = items
+            .last()
+            .filter(|_| has_next_page)
+            .map(|last| last.name().to_string());
+
+        Ok(Page {
+            items,
+            total,
+            next_cursor,
+        })
+    }
+
+    /// List all accounts in the database as a tree, following their hierarchical names.
+    ///
+    /// Each node carries the rolled-up balance of its whole subtree.
+    pub async fn get_account_tree(&self) -> Result<Vec<AccountTreeNode>, ListAccountsError> {
+        let accounts = self.list_accounts(None, None, None).await?.items;
+
+        Ok(AccountTreeNode::build(accounts))
     }
 
     /// Fetch an [Account] by its id.
@@ -210,10 +532,14 @@ id, title, amount, source_account_id, destination_account_id, category, posting_
                 err => GetAccountError::Unknown(err.into()),
             })?;
 
-        let account_name = AccountName::new(&row.name)
-            .map_err(|e| GetAccountError::Unknown(e.into()))
-            .context(format!("failed to create account name from {}", row.name))?;
-        let account = Account::new(row.id, account_name, row.balance);
+        let account = account_from_row(
+            row.id,
+            &row.name,
+            &row.account_type,
+            row.parent_id,
+            &row.currency,
+            row.balance,
+        )?;
 
         tracing::debug!(?account, "Found account");
 
@@ -238,10 +564,14 @@ id, title, amount, source_account_id, destination_account_id, category, posting_
                 err => GetAccountByNameError::Unknown(err.into()),
             })?;
 
-        let account_name = AccountName::new(&row.name)
-            .map_err(|e| GetAccountByNameError::Unknown(e.into()))
-            .context(format!("failed to create account name from {}", row.name))?;
-        let account = Account::new(row.id, account_name, row.balance);
+        let account = account_from_row(
+            row.id,
+            &row.name,
+            &row.account_type,
+            row.parent_id,
+            &row.currency,
+            row.balance,
+        )?;
 
         tracing::debug!(?account, "Found account");
 
@@ -250,19 +580,21 @@ id, title, amount, source_account_id, destination_account_id, category, posting_
 
     /// Fetch an [Account] by its name. If it does not exist, create it.
     ///
+    /// An existing [Account] is returned as is, even if its type differs from the requested one.
+    ///
     /// # Errors
     ///
     /// - [GetOrCreateAccountError::Duplicate] in case there is another [Account] in the database with
     /// - [GetOrCreateAccountError::Unknown] if any other error occurred
     pub async fn get_or_create_account(
         &self,
-        name: &AccountName,
+        req: &CreateAccountRequest,
     ) -> Result<Account, GetOrCreateAccountError> {
-        match self.get_account_by_name(name).await {
+        match self.get_account_by_name(req.name()).await {
             Ok(account) => Ok(account),
             Err(err) => match err {
-                GetAccountByNameError::NotFound { name } => self
-                    .create_account(&name)
+                GetAccountByNameError::NotFound { .. } => self
+                    .create_account(req)
                     .await
                     .map_err(GetOrCreateAccountError::from),
                 GetAccountByNameError::Unknown(err) => Err(GetOrCreateAccountError::Unknown(err)),
@@ -272,29 +604,23 @@ id, title, amount, source_account_id, destination_account_id, category, posting_
 
     /// Rename an [Account].
     ///
+    /// Renaming may move the account to another place in the hierarchy, creating any missing
+    /// ancestors. Its sub-accounts are renamed along with it, so renaming `assets:bank` to
+    /// `assets:banks` also renames `assets:bank:nubank` to `assets:banks:nubank`.
+    ///
     /// # Errors
     ///
     /// - [UpdateAccountError::NotFound] if no [Account] with the given id exists
     /// - [UpdateAccountError::Duplicate] in case there is another [Account] in the database with
     ///   the new name
+    /// - [UpdateAccountError::MovedUnderItself] if the new name is below the current one
     /// - [UpdateAccountError::Unknown] in case any other error occurred
     pub async fn rename_account(
         &self,
         id: Uuid,
         new_name: AccountName,
     ) -> Result<(), UpdateAccountError> {
-        let result = sqlx::query!(
-            "
-UPDATE accounts
-SET name = $1
-WHERE id = $2
-",
-            new_name.to_string(),
-            id
-        )
-        .execute(&self.pool)
-        .await
-        .map_err(|e| {
+        let map_err = |e: sqlx::Error| {
             if is_unique_constraint_violation(&e) {
                 UpdateAccountError::Duplicate {
                     name: new_name.to_string(),
@@ -302,19 +628,75 @@ WHERE id = $2
             } else {
                 UpdateAccountError::Unknown(e.into())
             }
-        })?;
+        };
 
-        if result.rows_affected() == 0 {
-            Err(UpdateAccountError::NotFound { id })
-        } else {
-            tracing::info!(new_name = new_name.to_string(), account_id = ?id, "Successfully renamed account");
-            Ok(())
+        let mut tx = self.start_psql_transaction().await?;
+        let row = sqlx::query!(
+            "SELECT name, account_type, currency FROM accounts WHERE id = $1",
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| UpdateAccountError::Unknown(e.into()))?
+        .ok_or(UpdateAccountError::NotFound { id })?;
+        let account_type = AccountType::from_str(&row.account_type)
+            .with_context(|| format!("failed to parse account type of account {}", id))?;
+        let currency = Currency::new(&row.currency)
+            .with_context(|| format!("failed to parse currency of account {}", id))?;
+
+        if new_name
+            .ancestors()
+            .iter()
+            .any(|ancestor| ancestor.to_string() == row.name)
+        {
+            return Err(UpdateAccountError::MovedUnderItself { name: row.name });
         }
+
+        let parent_id = self
+            .ensure_parent_accounts(&mut tx, &new_name, account_type, currency)
+            .await
+            .map_err(map_err)?;
+
+        sqlx::query!(
+            "
+UPDATE accounts
+SET name = $1, parent_id = $2
+WHERE id = $3
+",
+            new_name.to_string(),
+            parent_id,
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        sqlx::query!(
+            "
+UPDATE accounts
+SET name = $1 || substr(name, length($2) + 1)
+WHERE starts_with(name, $2 || ':')
+",
+            new_name.to_string(),
+            row.name
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_err)?;
+
+        tx.commit()
+            .await
+            .context("failed to commit PostgreSQL transaction")?;
+
+        tracing::info!(new_name = new_name.to_string(), account_id = ?id, "Successfully renamed account");
+        Ok(())
     }
 
     /// Update an [Account]'s balance.
     ///
-    /// This adds the given `balance_to_add` to an [Account]'s balance, be it positive or negative.
+    /// This adds the given `balance_to_add` to an [Account]'s ledger balance, be it positive or
+    /// negative. A positive amount is a debit, so it decreases the balance of credit-normal
+    /// accounts.
     ///
     /// # Errors
     ///
@@ -341,12 +723,20 @@ WHERE id = $2
     /// # Errors
     ///
     /// - [DeleteAccountError::NotFound] if no [Account] with the given id exists
+    /// - [DeleteAccountError::InUse] if the [Account] has sub-accounts or transactions, or a
+    ///   [Rule] or a [Schedule] uses it
     /// - [DeleteAccountError::Unknown] in case any other kind of error occurred
     pub async fn delete_account(&self, id: Uuid) -> Result<(), DeleteAccountError> {
         let result = sqlx::query!("DELETE FROM accounts WHERE id = $1", id)
             .execute(&self.pool)
             .await
-            .map_err(|e| DeleteAccountError::Unknown(e.into()))?;
+            .map_err(|e| {
+                if is_foreign_key_violation(&e) {
+                    DeleteAccountError::InUse { id }
+                } else {
+                    DeleteAccountError::Unknown(e.into())
+                }
+            })?;
 
         if result.rows_affected() == 0 {
             Err(DeleteAccountError::NotFound { id })
@@ -356,211 +746,3486 @@ WHERE id = $2
         }
     }
 
-    /// Create a [Transaction].
-    ///
-    /// This also updates the balances of the [Account]s involved (add to the destination account,
-    /// subtract from the source account).
+    /// Create a [Category], along with any of its missing ancestors.
     ///
     /// # Errors
     ///
-    /// - [CreateTransactionError::SourceAccountNotFound] if the source account does not exist
-    /// - [CreateTransactionError::DestinationAccountNotFound] if the destination account does not
-    ///   exist
-    /// - [CreateTransactionError::Unknown] if any other kind of error occurred
-    pub async fn create_transaction(
+    /// - [CreateCategoryError::Duplicate] if a [Category] with the same name already exists
+    /// - [CreateCategoryError::Unknown] in case any other error occurred
+    pub async fn create_category(
         &self,
-        req: &CreateTransactionRequest,
-    ) -> Result<Transaction, CreateTransactionError> {
-        self.check_if_accounts_exist(req.source_account_id(), req.destination_account_id())
-            .await?;
-        let posting_date = req
-            .posting_date()
-            .map(|d| d.and_utc())
-            .unwrap_or_else(Utc::now);
+        name: &CategoryName,
+    ) -> Result<Category, CreateCategoryError> {
+        let map_err = |e: sqlx::Error| {
+            if is_unique_constraint_violation(&e) {
+                CreateCategoryError::Duplicate { name: name.clone() }
+            } else {
+                anyhow!(e)
+                    .context(format!("failed to save category with name {:?}", name))
+                    .into()
+            }
+        };
 
         let mut tx = self.start_psql_transaction().await?;
+        let id = Uuid::new_v4();
+        let parent_id = self
+            .ensure_parent_categories(&mut tx, name)
+            .await
+            .map_err(map_err)?;
+        sqlx::query!(
+            "INSERT INTO categories (id, name, parent_id) VALUES ($1, $2, $3)",
+            id,
+            name.to_string(),
+            parent_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_err)?;
 
-        let transaction_id = self.save_transaction(&mut tx, req).await.map_err(|e| {
-            anyhow!(e).context(format!(
-                "failed to save transaction with title {:?}",
-                req.title()
-            ))
-        })?;
+        tx.commit()
+            .await
+            .context("failed to commit PostgreSQL transaction")?;
 
-        tracing::debug!("created transaction, updating account balances...");
+        tracing::info!(category_id = ?id, "Successfully created category");
+        Ok(Category::new(id, name.clone(), parent_id))
+    }
 
-        let source_account = self
-            .add_balance_to_account(&mut tx, req.source_account_id(), -req.amount())
-            .await
-            .context("failed to reset source account balance")?;
-        let destination_account = self
-            .add_balance_to_account(&mut tx, req.destination_account_id(), req.amount())
+    /// Fetch a [Category] by its name, creating it if it does not exist yet
+    pub async fn get_or_create_category(
+        &self,
+        name: &CategoryName,
+    ) -> Result<Category, CreateCategoryError> {
+        let row = sqlx::query!("SELECT * FROM categories WHERE name = $1", name.to_string())
+            .fetch_optional(&self.pool)
             .await
-            .context("failed to reset destination account balance")?;
+            .map_err(|e| CreateCategoryError::Unknown(e.into()))?;
 
-        tracing::debug!(source_balance = ?source_account.balance(), destination_balance = ?destination_account.balance(), "updated account balances");
+        match row {
+            Some(row) => Ok(category_from_row(row.id, &row.name, row.parent_id)?),
+            None => self.create_category(name).await,
+        }
+    }
 
-        tx.commit()
+    /// List all categories, ordered by name
+    pub async fn list_categories(&self) -> Result<Vec<Category>, ListCategoriesError> {
+        let rows = sqlx::query!("SELECT * FROM categories ORDER BY name")
+            .fetch_all(&self.pool)
             .await
-            .context("failed to commit PostgreSQL transaction")?;
+            .map_err(|e| ListCategoriesError::Unknown(e.into()))?;
+
+        rows.iter()
+            .map(|r| Ok(category_from_row(r.id, &r.name, r.parent_id)?))
+            .collect()
+    }
 
-        tracing::info!(?transaction_id, "Successfully created transaction");
+    /// Fetch a [Category] by its id.
+    ///
+    /// # Errors
+    ///
+    /// - [GetCategoryError::NotFound] if no [Category] with the given id exists
+    /// - [GetCategoryError::Unknown] in case any other error occurred
+    pub async fn get_category_by_id(&self, id: Uuid) -> Result<Category, GetCategoryError> {
+        let row = sqlx::query!("SELECT * FROM categories WHERE id = $1", id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| GetCategoryError::Unknown(e.into()))?
+            .ok_or(GetCategoryError::NotFound { id })?;
 
-        Ok(Transaction::new(
-            transaction_id,
-            req.title().clone(),
-            req.amount(),
-            req.source_account_id(),
-            req.destination_account_id(),
-            req.category().clone(),
-            posting_date,
-        ))
+        Ok(category_from_row(row.id, &row.name, row.parent_id)?)
     }
 
-    /// Delete a [Transaction].
+    /// Rename a [Category].
     ///
-    /// This is the opposite operation of creating a transaction, so it also subtracts the amount
-    /// from the destination account's balance and adds the amount to the source account's balance.
+    /// Like renaming an [Account], this may move the category to another place in the
+    /// hierarchy, and its subcategories are renamed along with it. Transactions follow the new
+    /// names.
     ///
     /// # Errors
     ///
-    /// - [DeleteTransactionError::TransactionNotFound] if no [Transaction] with the given id
-    ///   exists
-    /// - [DeleteTransactionError::Unknown] in casy any other kind of error occurred
-    pub async fn delete_transaction(&self, id: Uuid) -> Result<(), DeleteTransactionError> {
+    /// - [UpdateCategoryError::NotFound] if no [Category] with the given id exists
+    /// - [UpdateCategoryError::Duplicate] in case there is another [Category] with the new name
+    /// - [UpdateCategoryError::MovedUnderItself] if the new name is below the current one
+    /// - [UpdateCategoryError::Unknown] in case any other error occurred
+    pub async fn rename_category(
+        &self,
+        id: Uuid,
+        new_name: &CategoryName,
+    ) -> Result<Category, UpdateCategoryError> {
+        let map_err = |e: sqlx::Error| {
+            if is_unique_constraint_violation(&e) {
+                UpdateCategoryError::Duplicate {
+                    name: new_name.clone(),
+                }
+            } else {
+                UpdateCategoryError::Unknown(e.into())
+            }
+        };
+
         let mut tx = self.start_psql_transaction().await?;
-        let row = sqlx::query!(
-            "
-         DELETE FROM \"postings\"
-         WHERE id = $1
-         RETURNING source_account_id, destination_account_id, amount
-         ",
+        let name = sqlx::query_scalar!("SELECT name FROM categories WHERE id = $1", id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| UpdateCategoryError::Unknown(e.into()))?
+            .ok_or(UpdateCategoryError::NotFound { id })?;
+
+        if new_name
+            .ancestors()
+            .iter()
+            .any(|ancestor| ancestor.to_string() == name)
+        {
+            return Err(UpdateCategoryError::MovedUnderItself { name });
+        }
+
+        let parent_id = self
+            .ensure_parent_categories(&mut tx, new_name)
+            .await
+            .map_err(map_err)?;
+
+        sqlx::query!(
+            "UPDATE categories SET name = $1, parent_id = $2 WHERE id = $3",
+            new_name.to_string(),
+            parent_id,
             id
         )
-        .fetch_one(&mut *tx)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| match e {
-            sqlx::Error::RowNotFound => DeleteTransactionError::TransactionNotFound { id },
-            e => DeleteTransactionError::Unknown(e.into()),
-        })?;
+        .map_err(map_err)?;
 
-        let (source_account_id, destination_acount_id) =
-            (row.source_account_id, row.destination_account_id);
-
-        self.add_balance_to_account(&mut tx, source_account_id, row.amount)
-            .await
-            .context("failed to reset source account balance")?;
-        self.add_balance_to_account(&mut tx, destination_acount_id, -row.amount)
-            .await
-            .context("failed to reset destination account balance")?;
+        sqlx::query!(
+            "
+UPDATE categories
+SET name = $1 || substr(name, length($2) + 1)
+WHERE starts_with(name, $2 || ':')
+",
+            new_name.to_string(),
+            name
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(map_err)?;
 
         tx.commit()
             .await
             .context("failed to commit PostgreSQL transaction")?;
 
-        Ok(())
+        tracing::info!(new_name = new_name.to_string(), category_id = ?id, "Successfully renamed category");
+        Ok(Category::new(id, new_name.clone(), parent_id))
     }
 
-    /// Fetch a [Transaction] by its id.
+    /// Delete a [Category].
     ///
     /// # Errors
     ///
-    /// - [GetTransactionError::TransactionNotFound] if a transaction with the given id does not exist
-    /// - [GetTransactionError::Unknown] if any other kind of error occurred
-    pub async fn get_transaction_by_id(
-        &self,
-        id: Uuid,
-    ) -> Result<Transaction, GetTransactionError> {
-        let row = sqlx::query!("SELECT * FROM postings WHERE id = $1", id)
-            .fetch_one(&self.pool)
+    /// - [DeleteCategoryError::NotFound] if no [Category] with the given id exists
+    /// - [DeleteCategoryError::InUse] if the category still has subcategories or transactions,
+    ///   or a [Rule] or a [Schedule] uses it
+    /// - [DeleteCategoryError::Unknown] in case any other error occurred
+    pub async fn delete_category(&self, id: Uuid) -> Result<(), DeleteCategoryError> {
+        let result = sqlx::query!("DELETE FROM categories WHERE id = $1", id)
+            .execute(&self.pool)
             .await
-            .map_err(|e| match e {
-                sqlx::Error::RowNotFound => GetTransactionError::TransactionNotFound { id },
-                _ => GetTransactionError::Unknown(e.into()),
+            .map_err(|e| {
+                if is_foreign_key_violation(&e) {
+                    DeleteCategoryError::InUse { id }
+                } else {
+                    DeleteCategoryError::Unknown(e.into())
+                }
             })?;
 
-        let transaction_title = TransactionTitle::new(&row.title)
-            .map_err(|e| GetTransactionError::Unknown(e.into()))?;
+        if result.rows_affected() == 0 {
+            Err(DeleteCategoryError::NotFound { id })
+        } else {
+            tracing::info!(?id, "Successfully deleted category");
+            Ok(())
+        }
+    }
 
-        let transaction = Transaction::new(
-            id,
-            transaction_title,
-            row.amount,
-            row.source_account_id,
-            row.destination_account_id,
-            row.category,
-            row.posting_date,
-        );
-        tracing::info!(
-            ?id,
-            ?row.source_account_id,
-            ?row.destination_account_id,
-            "Successfully created transaction"
+    /// Fetch the [Payee] with the given name, creating it if it does not exist yet
+    pub async fn get_or_create_payee(
+        &self,
+        name: &PayeeName,
+    ) -> Result<Payee, GetOrCreatePayeeError> {
+        // Updating the name on conflict makes the existing row returned
+        let id = sqlx::query_scalar!(
+            "
+INSERT INTO payees (id, name)
+VALUES ($1, $2)
+ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+RETURNING id
+",
+            Uuid::new_v4(),
+            name.as_str(),
+        )
+        .fetch_one(&self.pool)
+        .await
+        .with_context(|| format!("failed to get or create payee {name}"))?;
+
+        Ok(Payee::new(id, name.clone()))
+    }
+
+    /// List all payees, ordered by name
+    pub async fn list_payees(&self) -> Result<Vec<Payee>, ListPayeesError> {
+        let rows = sqlx::query!("SELECT * FROM payees ORDER BY name")
+            .fetch_all(&self.pool)
+            .await
+            .context("failed to list payees")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Payee::new(row.id, PayeeName::from_stored(row.name)))
+            .collect())
+    }
+
+    /// Create a [Rule].
+    ///
+    /// # Errors
+    ///
+    /// - [CreateRuleError::Invalid] if the rule has no name or no actions, an invalid title
+    ///   pattern or amount range, or refers to an account, payee or category that does not exist
+    /// - [CreateRuleError::Unknown] in case any other error occurred
+    pub async fn create_rule(&self, req: &CreateRuleRequest) -> Result<Rule, CreateRuleError> {
+        self.validate_rule(req).await?;
+
+        let id = Uuid::new_v4();
+        let conditions = req.conditions();
+        let actions = req.actions();
+        sqlx::query!(
+            "
+INSERT INTO rules (id, name, priority, title_pattern, min_amount, max_amount, source_account_id,
+    payee_id, category, destination_account_id, tags)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+",
+            id,
+            req.name().trim(),
+            req.priority(),
+            conditions.title_pattern,
+            conditions.min_amount,
+            conditions.max_amount,
+            conditions.source_account_id,
+            conditions.payee_id,
+            actions.category.as_ref().map(|c| c.to_string()),
+            actions.destination_account_id,
+            &actions.tags,
+        )
+        .execute(&self.pool)
+        .await
+        .context("failed to create rule")?;
+
+        tracing::info!(rule_id = ?id, "Successfully created rule");
+        Ok(Rule::new(
+            id,
+            req.name().trim().to_string(),
+            req.priority(),
+            conditions.clone(),
+            actions.clone(),
+        ))
+    }
+
+    /// Replace all fields of a [Rule].
+    ///
+    /// # Errors
+    ///
+    /// - [UpdateRuleError::NotFound] if no [Rule] with the given id exists
+    /// - [UpdateRuleError::Invalid] if the new fields are not valid, like when creating a rule
+    /// - [UpdateRuleError::Unknown] in case any other error occurred
+    pub async fn update_rule(
+        &self,
+        id: Uuid,
+        req: &CreateRuleRequest,
+    ) -> Result<Rule, UpdateRuleError> {
+        self.validate_rule(req).await?;
+
+        let conditions = req.conditions();
+        let actions = req.actions();
+        let result = sqlx::query!(
+            "
+UPDATE rules
+SET name = $1, priority = $2, title_pattern = $3, min_amount = $4, max_amount = $5,
+    source_account_id = $6, payee_id = $7, category = $8, destination_account_id = $9, tags = $10
+WHERE id = $11
+",
+            req.name().trim(),
+            req.priority(),
+            conditions.title_pattern,
+            conditions.min_amount,
+            conditions.max_amount,
+            conditions.source_account_id,
+            conditions.payee_id,
+            actions.category.as_ref().map(|c| c.to_string()),
+            actions.destination_account_id,
+            &actions.tags,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .context("failed to update rule")?;
+
+        if result.rows_affected() == 0 {
+            return Err(UpdateRuleError::NotFound { id });
+        }
+
+        tracing::info!(rule_id = ?id, "Successfully updated rule");
+        Ok(Rule::new(
+            id,
+            req.name().trim().to_string(),
+            req.priority(),
+            conditions.clone(),
+            actions.clone(),
+        ))
+    }
+
+    /// Checks that a [Rule] is valid and that everything it refers to exists
+    async fn validate_rule(&self, req: &CreateRuleRequest) -> Result<(), CreateRuleError> {
+        req.validate()?;
+
+        let account_ids = [
+            req.conditions().source_account_id,
+            req.actions().destination_account_id,
+        ];
+        let mut currencies = Vec::new();
+        for id in account_ids.into_iter().flatten() {
+            let account = self.get_account_by_id(id).await.map_err(|e| match e {
+                GetAccountError::NotFound { id } => {
+                    RuleValidationError::AccountNotFound { id }.into()
+                }
+                GetAccountError::Unknown(e) => CreateRuleError::Unknown(e),
+            })?;
+            currencies.push(account.currency());
+        }
+        // The destination account replaces the leg on the other side of transactions from the
+        // source account, which is in the source account's currency
+        if let [source_currency, destination_currency] = currencies[..]
+            && source_currency != destination_currency
+        {
+            return Err(RuleValidationError::CurrencyMismatch {
+                source_currency,
+                destination_currency,
+            }
+            .into());
+        }
+
+        if let Some(id) = req.conditions().payee_id {
+            let exists = sqlx::query_scalar!(
+                r#"SELECT EXISTS(SELECT 1 FROM payees WHERE id = $1) AS "exists!""#,
+                id
+            )
+            .fetch_one(&self.pool)
+            .await
+            .with_context(|| format!("failed to look up payee {id}"))?;
+            if !exists {
+                return Err(RuleValidationError::PayeeNotFound { id }.into());
+            }
+        }
+
+        self.validate_category(&self.pool, req.actions().category.as_ref())
+            .await
+            .map_err(|e| match e {
+                CreateTransactionError::CategoryNotFound { name } => {
+                    RuleValidationError::CategoryNotFound { name }.into()
+                }
+                e => CreateRuleError::Unknown(e.into()),
+            })
+    }
+
+    /// Fetch a [Rule] by its id.
+    ///
+    /// # Errors
+    ///
+    /// - [GetRuleError::NotFound] if no [Rule] with the given id exists
+    /// - [GetRuleError::Unknown] in case any other error occurred
+    pub async fn get_rule(&self, id: Uuid) -> Result<Rule, GetRuleError> {
+        self.fetch_rules(Some(id))
+            .await?
+            .pop()
+            .ok_or(GetRuleError::NotFound { id })
+    }
+
+    /// List all [Rule]s in the order they are tried: by priority, then by name
+    pub async fn list_rules(&self) -> Result<Vec<Rule>, ListRulesError> {
+        Ok(self.fetch_rules(None).await?)
+    }
+
+    /// Fetch the [Rule] with the given id, or all of them if there is none, in the order they
+    /// are tried
+    async fn fetch_rules(&self, id: Option<Uuid>) -> anyhow::Result<Vec<Rule>> {
+        let rows = sqlx::query!(
+            "SELECT * FROM rules WHERE $1::uuid IS NULL OR id = $1 ORDER BY priority, name, id",
+            id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch rules")?;
+
+        rows.into_iter()
+            .map(|row| {
+                let conditions = RuleConditions {
+                    title_pattern: row.title_pattern,
+                    min_amount: row.min_amount,
+                    max_amount: row.max_amount,
+                    source_account_id: row.source_account_id,
+                    payee_id: row.payee_id,
+                };
+                let actions = RuleActions {
+                    category: parse_category(row.category.as_deref())?,
+                    destination_account_id: row.destination_account_id,
+                    tags: row.tags,
+                };
+                Ok(Rule::new(
+                    row.id,
+                    row.name,
+                    row.priority,
+                    conditions,
+                    actions,
+                ))
+            })
+            .collect()
+    }
+
+    /// Delete a [Rule]. Transactions it already categorized are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// - [DeleteRuleError::NotFound] if no [Rule] with the given id exists
+    /// - [DeleteRuleError::Unknown] in case any other error occurred
+    pub async fn delete_rule(&self, id: Uuid) -> Result<(), DeleteRuleError> {
+        let result = sqlx::query!("DELETE FROM rules WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DeleteRuleError::Unknown(e.into()))?;
+
+        if result.rows_affected() == 0 {
+            Err(DeleteRuleError::NotFound { id })
+        } else {
+            tracing::info!(?id, "Successfully deleted rule");
+            Ok(())
+        }
+    }
+
+    /// Re-apply the [Rule]s to all existing transactions, oldest first. When `preview` is set,
+    /// only report what would change.
+    ///
+    /// A rule's destination account replaces the income or expense account of transactions
+    /// between two accounts, moving the amount between the two accounts' balances. Tags are
+    /// added to the ones a transaction already has.
+    pub async fn apply_rules(&self, preview: bool) -> Result<ApplyRulesReport, ApplyRulesError> {
+        let rules = self
+            .fetch_rules(None)
+            .await
+            .and_then(|rules| RuleSet::new(rules).context("failed to compile rule patterns"))?;
+        if rules.is_empty() {
+            return Ok(ApplyRulesReport::new(preview, vec![]));
+        }
+
+        let accounts = sqlx::query!("SELECT id, account_type, currency FROM accounts")
+            .fetch_all(&self.pool)
+            .await
+            .context("failed to fetch account types")?;
+        let account_types = accounts
+            .iter()
+            .map(|row| {
+                let account_type = AccountType::from_str(&row.account_type).with_context(|| {
+                    format!("failed to parse account type of account {}", row.id)
+                })?;
+                Ok((row.id, account_type))
+            })
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+        let currencies = accounts
+            .iter()
+            .map(|row| {
+                let currency = Currency::new(&row.currency)
+                    .with_context(|| format!("failed to parse currency of account {}", row.id))?;
+                Ok((row.id, currency))
+            })
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
+        let entries = sqlx::query!(
+            "SELECT id, title, category, payee_id, tags FROM entries ORDER BY posting_date, id"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch transactions")?;
+        let ids: Vec<Uuid> = entries.iter().map(|entry| entry.id).collect();
+        let mut legs = self
+            .fetch_legs(&self.pool, &ids)
+            .await
+            .context("failed to fetch transaction legs")?;
+
+        // Along with each change, the moved leg's position and amount
+        let mut changes = Vec::new();
+        for entry in entries {
+            let legs = legs.remove(&entry.id).unwrap_or_default();
+            let counterpart = counterpart_leg(&legs, &account_types);
+            let subject = RuleSubject {
+                title: &entry.title,
+                amount: legs
+                    .iter()
+                    .map(Leg::amount)
+                    .filter(|amount| amount.is_sign_positive())
+                    .sum(),
+                source_account_ids: legs
+                    .iter()
+                    .enumerate()
+                    .filter(|(position, _)| Some(*position) != counterpart)
+                    .map(|(_, leg)| leg.account_id())
+                    .collect(),
+                payee_id: entry.payee_id,
+            };
+            let Some(rule) = rules.first_match(&subject) else {
+                continue;
+            };
+            let actions = rule.actions();
+
+            let current_category = parse_category(entry.category.as_deref())?;
+            let category = actions
+                .category
+                .as_ref()
+                .filter(|&category| current_category.as_ref() != Some(category))
+                .map(|category| Change {
+                    from: current_category.clone(),
+                    to: Some(category.clone()),
+                });
+            // Legs are only moved to accounts in their currency, as their amounts stay the same
+            let destination = match (actions.destination_account_id, counterpart) {
+                (Some(to), Some(position))
+                    if legs[position].account_id() != to
+                        && currencies.get(&to) == Some(&legs[position].currency()) =>
+                {
+                    Some((
+                        position,
+                        legs[position].amount(),
+                        Change {
+                            from: legs[position].account_id(),
+                            to,
+                        },
+                    ))
+                }
+                _ => None,
+            };
+            let added_tags: Vec<String> = actions
+                .tags
+                .iter()
+                .filter(|tag| !entry.tags.contains(tag))
+                .cloned()
+                .collect();
+
+            let application = RuleApplication::new(
+                entry.id,
+                entry.title,
+                rule.id(),
+                category,
+                destination.as_ref().map(|(_, _, change)| change.clone()),
+                added_tags,
+            );
+            if !application.is_noop() {
+                changes.push((
+                    application,
+                    destination.map(|(position, amount, _)| (position, amount)),
+                ));
+            }
+        }
+
+        if !preview {
+            let mut tx = self.start_psql_transaction().await?;
+            for (application, moved_leg) in &changes {
+                let id = application.transaction_id();
+                if let Some(change) = application.category() {
+                    sqlx::query!(
+                        "UPDATE entries SET category = $1 WHERE id = $2",
+                        change.to.as_ref().map(|c| c.to_string()),
+                        id
+                    )
+                    .execute(&mut *tx)
+                    .await
+                    .context("failed to update transaction category")?;
+                }
+                if !application.added_tags().is_empty() {
+                    sqlx::query!(
+                        "UPDATE entries SET tags = tags || $1 WHERE id = $2",
+                        application.added_tags(),
+                        id
+                    )
+                    .execute(&mut *tx)
+                    .await
+                    .context("failed to update transaction tags")?;
+                }
+                if let (Some((position, amount)), Some(change)) =
+                    (moved_leg, application.destination_account_id())
+                {
+                    sqlx::query!(
+                        "UPDATE legs SET account_id = $1 WHERE entry_id = $2 AND position = $3",
+                        change.to,
+                        id,
+                        *position as i32
+                    )
+                    .execute(&mut *tx)
+                    .await
+                    .context("failed to move transaction leg")?;
+                    self.add_balance_to_account(&mut tx, change.from, -*amount)
+                        .await
+                        .context("failed to reset account balance")?;
+                    self.add_balance_to_account(&mut tx, change.to, *amount)
+                        .await
+                        .context("failed to update account balance")?;
+                }
+            }
+            tx.commit()
+                .await
+                .context("failed to commit PostgreSQL transaction")?;
+
+            tracing::info!(changed = changes.len(), "Successfully applied rules");
+        }
+
+        Ok(ApplyRulesReport::new(
+            preview,
+            changes
+                .into_iter()
+                .map(|(application, _)| application)
+                .collect(),
+        ))
+    }
+
+    /// Create a [Budget].
+    ///
+    /// # Errors
+    ///
+    /// - [CreateBudgetError::NegativeAmount] if the budgeted amount is negative
+    /// - [CreateBudgetError::CategoryNotFound] if the budgeted category does not exist
+    /// - [CreateBudgetError::AccountNotFound] if the budgeted account does not exist
+    /// - [CreateBudgetError::NotAnExpenseAccount] if the budgeted account is not an expense account
+    /// - [CreateBudgetError::Duplicate] if the target already has a budget in the same month
+    /// - [CreateBudgetError::Unknown] in case any other error occurred
+    pub async fn create_budget(
+        &self,
+        req: &CreateBudgetRequest,
+    ) -> Result<Budget, CreateBudgetError> {
+        if req.amount().is_sign_negative() {
+            return Err(CreateBudgetError::NegativeAmount);
+        }
+
+        let (category_id, account_id) = match req.target() {
+            BudgetTarget::Category(id) => {
+                self.get_category_by_id(id).await.map_err(|e| match e {
+                    GetCategoryError::NotFound { id } => CreateBudgetError::CategoryNotFound { id },
+                    GetCategoryError::Unknown(e) => CreateBudgetError::Unknown(e),
+                })?;
+                (Some(id), None)
+            }
+            BudgetTarget::Account(id) => {
+                let account = self.get_account_by_id(id).await.map_err(|e| match e {
+                    GetAccountError::NotFound { id } => CreateBudgetError::AccountNotFound { id },
+                    GetAccountError::Unknown(e) => CreateBudgetError::Unknown(e),
+                })?;
+                if account.account_type() != AccountType::Expense {
+                    return Err(CreateBudgetError::NotAnExpenseAccount { id });
+                }
+                (None, Some(id))
+            }
+        };
+
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            "
+INSERT INTO budgets (id, month, category_id, account_id, amount, rollover)
+VALUES ($1, $2, $3, $4, $5, $6)
+",
+            id,
+            req.month().first_day(),
+            category_id,
+            account_id,
+            req.amount(),
+            req.rollover()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_constraint_violation(&e) {
+                CreateBudgetError::Duplicate { month: req.month() }
+            } else {
+                CreateBudgetError::Unknown(e.into())
+            }
+        })?;
+
+        tracing::info!(budget_id = ?id, "Successfully created budget");
+        Ok(Budget::new(
+            id,
+            req.month(),
+            req.target(),
+            req.amount(),
+            req.rollover(),
+        ))
+    }
+
+    /// Update the amount or the rollover setting of a [Budget].
+    ///
+    /// # Errors
+    ///
+    /// - [UpdateBudgetError::NotFound] if no [Budget] with the given id exists
+    /// - [UpdateBudgetError::NegativeAmount] if the new amount is negative
+    /// - [UpdateBudgetError::Unknown] in case any other error occurred
+    pub async fn update_budget(
+        &self,
+        id: Uuid,
+        req: &UpdateBudgetRequest,
+    ) -> Result<Budget, UpdateBudgetError> {
+        if req.amount().is_some_and(|amount| amount.is_sign_negative()) {
+            return Err(UpdateBudgetError::NegativeAmount);
+        }
+
+        let row = sqlx::query!(
+            "
+UPDATE budgets
+SET amount = COALESCE($1, amount), rollover = COALESCE($2, rollover)
+WHERE id = $3
+RETURNING *
+",
+            req.amount(),
+            req.rollover(),
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| UpdateBudgetError::Unknown(e.into()))?
+        .ok_or(UpdateBudgetError::NotFound { id })?;
+
+        tracing::info!(budget_id = ?id, "Successfully updated budget");
+        Ok(budget_from_row(
+            row.id,
+            row.month,
+            row.category_id,
+            row.account_id,
+            row.amount,
+            row.rollover,
+        )?)
+    }
+
+    /// Delete a [Budget].
+    ///
+    /// # Errors
+    ///
+    /// - [DeleteBudgetError::NotFound] if no [Budget] with the given id exists
+    /// - [DeleteBudgetError::Unknown] in case any other error occurred
+    pub async fn delete_budget(&self, id: Uuid) -> Result<(), DeleteBudgetError> {
+        let result = sqlx::query!("DELETE FROM budgets WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DeleteBudgetError::Unknown(e.into()))?;
+
+        if result.rows_affected() == 0 {
+            Err(DeleteBudgetError::NotFound { id })
+        } else {
+            tracing::info!(?id, "Successfully deleted budget");
+            Ok(())
+        }
+    }
+
+    /// Compute the envelope of every budgeted target in the given month, with months starting
+    /// and ending at midnight in `timezone`.
+    ///
+    /// Spending is what was debited to expense accounts: for an account target, to the account
+    /// or its sub-accounts, and for a category target, by transactions in the category or its
+    /// subcategories. Credits, like refunds, reduce spending.
+    pub async fn get_budget_report(
+        &self,
+        month: Month,
+        timezone: Tz,
+    ) -> Result<Vec<BudgetLine>, GetBudgetReportError> {
+        let budgets = sqlx::query!("SELECT * FROM budgets WHERE month <= $1", month.first_day())
+            .fetch_all(&self.pool)
+            .await
+            .context("failed to fetch budgets")?
+            .into_iter()
+            .map(|r| {
+                budget_from_row(
+                    r.id,
+                    r.month,
+                    r.category_id,
+                    r.account_id,
+                    r.amount,
+                    r.rollover,
+                )
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let Some(first_month) = budgets.iter().map(Budget::month).min() else {
+            return Ok(Vec::new());
+        };
+        let from = first_month.first_day();
+        let until = month.next().first_day();
+
+        let mut spending: HashMap<(BudgetTarget, Month), Decimal> = HashMap::new();
+        let account_rows = sqlx::query!(
+            r#"
+SELECT
+  target.id AS "target_id!",
+  date_trunc('month', e.posting_date AT TIME ZONE $1)::date AS "month!",
+  SUM(l.amount) AS "spent!"
+FROM accounts target
+JOIN accounts a ON a.id = target.id OR starts_with(a.name, target.name || ':')
+JOIN legs l ON l.account_id = a.id
+JOIN entries e ON e.id = l.entry_id
+WHERE target.id IN (SELECT account_id FROM budgets)
+  AND a.account_type = 'expense'
+  AND (e.posting_date AT TIME ZONE $1)::date >= $2
+  AND (e.posting_date AT TIME ZONE $1)::date < $3
+GROUP BY 1, 2
+"#,
+            timezone.name(),
+            from,
+            until
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to compute spending per account")?;
+        for row in account_rows {
+            spending.insert(
+                (BudgetTarget::Account(row.target_id), Month::of(row.month)),
+                row.spent,
+            );
+        }
+
+        let category_rows = sqlx::query!(
+            r#"
+SELECT
+  c.id AS "target_id!",
+  date_trunc('month', e.posting_date AT TIME ZONE $1)::date AS "month!",
+  SUM(l.amount) AS "spent!"
+FROM categories c
+JOIN entries e ON e.category = c.name OR starts_with(e.category, c.name || ':')
+JOIN legs l ON l.entry_id = e.id
+JOIN accounts a ON a.id = l.account_id
+WHERE c.id IN (SELECT category_id FROM budgets)
+  AND a.account_type = 'expense'
+  AND (e.posting_date AT TIME ZONE $1)::date >= $2
+  AND (e.posting_date AT TIME ZONE $1)::date < $3
+GROUP BY 1, 2
+"#,
+            timezone.name(),
+            from,
+            until
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to compute spending per category")?;
+        for row in category_rows {
+            spending.insert(
+                (BudgetTarget::Category(row.target_id), Month::of(row.month)),
+                row.spent,
+            );
+        }
+
+        Ok(BudgetLine::for_month(month, &budgets, &spending))
+    }
+
+    /// Sum up the amounts of the legs of every account per period of `interval` from `from` to
+    /// `to` (local dates in `timezone`, `to` excluded), keyed by the first day of the period,
+    /// along with the ledger balance of every account right before `from`.
+    async fn account_movements(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: Interval,
+        timezone: Tz,
+    ) -> anyhow::Result<(HashMap<Uuid, Decimal>, HashMap<(Uuid, NaiveDate), Decimal>)> {
+        let opening = sqlx::query!(
+            r#"
+SELECT l.account_id, SUM(l.amount) AS "balance!"
+FROM legs l
+JOIN entries e ON e.id = l.entry_id
+WHERE (e.posting_date AT TIME ZONE $1)::date < $2
+GROUP BY 1
+"#,
+            timezone.name(),
+            from
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to compute opening balances")?
+        .into_iter()
+        .map(|row| (row.account_id, row.balance))
+        .collect();
+
+        let movements = sqlx::query!(
+            r#"
+SELECT
+  l.account_id,
+  date_trunc($4, e.posting_date AT TIME ZONE $1)::date AS "period!",
+  SUM(l.amount) AS "amount!"
+FROM legs l
+JOIN entries e ON e.id = l.entry_id
+WHERE (e.posting_date AT TIME ZONE $1)::date >= $2
+  AND (e.posting_date AT TIME ZONE $1)::date < $3
+GROUP BY 1, 2
+"#,
+            timezone.name(),
+            from,
+            to,
+            interval.as_str()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to compute account movements")?
+        .into_iter()
+        .map(|row| ((row.account_id, row.period), row.amount))
+        .collect();
+
+        Ok((opening, movements))
+    }
+
+    /// Collect the exchange rates recorded on transactions, each one dated on the local posting
+    /// date in `timezone` of its transaction, along with the [Price]s. A price replaces the
+    /// rates recorded on its date.
+    async fn fetch_exchange_rates(&self, timezone: Tz) -> anyhow::Result<ExchangeRates> {
+        let rows = sqlx::query!(
+            r#"
+SELECT
+  (posting_date AT TIME ZONE $1)::date AS "date!",
+  exchange_rate_base AS "base!",
+  exchange_rate_quote AS "quote!",
+  exchange_rate AS "rate!"
+FROM entries
+WHERE exchange_rate IS NOT NULL
+"#,
+            timezone.name()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch exchange rates")?;
+
+        let mut rates = ExchangeRates::default();
+        for row in rows {
+            if let Some(rate) =
+                exchange_rate_from_row(Some(&row.base), Some(&row.quote), Some(row.rate))?
+            {
+                rates.insert(row.date, &rate);
+            }
+        }
+
+        let prices = sqlx::query!("SELECT id, commodity, currency, date, rate FROM prices")
+            .fetch_all(&self.pool)
+            .await
+            .context("failed to fetch prices")?;
+        for row in prices {
+            let price = price_from_row(row.id, &row.commodity, &row.currency, row.date, row.rate)?;
+            let rate = ExchangeRate::new(price.commodity(), price.currency(), price.rate())
+                .with_context(|| format!("failed to build exchange rate of price {}", row.id))?;
+            rates.insert(price.date(), &rate);
+        }
+
+        Ok(rates)
+    }
+
+    /// Build the [IncomeStatement] of the period from `from` to `to`, which are local dates in
+    /// `timezone`, `to` excluded, with amounts converted into `currency`.
+    ///
+    /// # Errors
+    ///
+    /// - [GetReportError::InvalidPeriod] if `to` is not after `from`
+    /// - [GetReportError::Conversion] if an amount can't be converted into `currency`
+    /// - [GetReportError::Unknown] in case any other error occurred
+    pub async fn get_income_statement(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        currency: Currency,
+        timezone: Tz,
+    ) -> Result<IncomeStatement, GetReportError> {
+        if to <= from {
+            return Err(GetReportError::InvalidPeriod { from, to });
+        }
+
+        let accounts = self.list_accounts(None, None, None).await?.items;
+        let (_, movements) = self
+            .account_movements(from, to, Interval::Month, timezone)
+            .await?;
+        let movements = by_month(movements);
+        let rates = self.fetch_exchange_rates(timezone).await?;
+
+        Ok(IncomeStatement::build(
+            from,
+            to,
+            &accounts,
+            &movements,
+            Valuation::new(currency, &rates),
+        )?)
+    }
+
+    /// Build the [BalanceSheet] of the period from `from` to `to`, which are local dates in
+    /// `timezone`, `to` excluded, with amounts converted into `currency`.
+    ///
+    /// # Errors
+    ///
+    /// - [GetReportError::InvalidPeriod] if `to` is not after `from`
+    /// - [GetReportError::Conversion] if an amount can't be converted into `currency`
+    /// - [GetReportError::Unknown] in case any other error occurred
+    pub async fn get_balance_sheet(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        currency: Currency,
+        timezone: Tz,
+    ) -> Result<BalanceSheet, GetReportError> {
+        if to <= from {
+            return Err(GetReportError::InvalidPeriod { from, to });
+        }
+
+        let accounts = self.list_accounts(None, None, None).await?.items;
+        let (opening, movements) = self
+            .account_movements(from, to, Interval::Month, timezone)
+            .await?;
+        let rates = self.fetch_exchange_rates(timezone).await?;
+
+        Ok(BalanceSheet::build(
+            from,
+            to,
+            &accounts,
+            &opening,
+            &by_month(movements),
+            Valuation::new(currency, &rates),
+        )?)
+    }
+
+    /// Build the [BalanceHistory] of the accounts with the given ids, or of all of them if there
+    /// are none, from `from` to `to`, which are local dates in `timezone`, `to` excluded, with
+    /// balances converted into `currency`.
+    ///
+    /// # Errors
+    ///
+    /// - [GetBalanceHistoryError::InvalidPeriod] if `to` is not after `from`
+    /// - [GetBalanceHistoryError::TooManyPeriods] if the period is too long for the interval
+    /// - [GetBalanceHistoryError::AccountNotFound] if one of the accounts does not exist
+    /// - [GetBalanceHistoryError::Conversion] if a balance can't be converted into `currency`
+    /// - [GetBalanceHistoryError::Unknown] in case any other error occurred
+    pub async fn get_balance_history(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        interval: Interval,
+        account_ids: &[Uuid],
+        currency: Currency,
+        timezone: Tz,
+    ) -> Result<BalanceHistory, GetBalanceHistoryError> {
+        if to <= from {
+            return Err(GetBalanceHistoryError::InvalidPeriod { from, to });
+        }
+        let count = interval.count_periods(from, to);
+        if count > BalanceHistory::MAX_PERIODS as u64 {
+            return Err(GetBalanceHistoryError::TooManyPeriods {
+                count,
+                max: BalanceHistory::MAX_PERIODS,
+            });
+        }
+
+        let mut accounts = self.list_accounts(None, None, None).await?.items;
+        if !account_ids.is_empty() {
+            if let Some(id) = account_ids
+                .iter()
+                .find(|id| !accounts.iter().any(|account| account.id() == **id))
+            {
+                return Err(GetBalanceHistoryError::AccountNotFound { id: *id });
+            }
+            accounts.retain(|account| account_ids.contains(&account.id()));
+        }
+        let (opening, movements) = self.account_movements(from, to, interval, timezone).await?;
+        let rates = self.fetch_exchange_rates(timezone).await?;
+
+        Ok(BalanceHistory::build(
+            from,
+            to,
+            interval,
+            &accounts,
+            &opening,
+            &movements,
+            Valuation::new(currency, &rates),
+        )?)
+    }
+
+    /// Create a [Transaction].
+    ///
+    /// This also updates the balances of the [Account]s involved, adding each [Leg]'s amount to
+    /// its account's balance.
+    ///
+    /// A transaction split into installments creates an [InstallmentPlan] and one transaction
+    /// per installment, future ones included, and the first installment is returned.
+    ///
+    /// # Errors
+    ///
+    /// - [CreateTransactionError::NotEnoughLegs] if the transaction has less than two legs
+    /// - [CreateTransactionError::Unbalanced] if the legs do not sum up to zero
+    /// - [CreateTransactionError::MissingExchangeRate] if the legs are in two currencies but no
+    ///   exchange rate is given
+    /// - [CreateTransactionError::ExchangeRateMismatch] if the exchange rate is not between the
+    ///   currencies of the legs
+    /// - [CreateTransactionError::TooManyCurrencies] if the legs are in more than two currencies
+    /// - [CreateTransactionError::AccountNotFound] if any of the legs' accounts does not exist
+    /// - [CreateTransactionError::CategoryNotFound] if the transaction's category does not exist
+    /// - [CreateTransactionError::Duplicate] if a transaction with the same import fingerprint
+    ///   exists
+    /// - [CreateTransactionError::InvalidInstallmentCount] if the transaction is split into no
+    ///   installments or more than [MAX_INSTALLMENTS]
+    /// - [CreateTransactionError::OccurrencePosted] if the occurrence of a schedule the
+    ///   transaction is for was already posted
+    /// - [CreateTransactionError::Unknown] if any other kind of error occurred
+    pub async fn create_transaction(
+        &self,
+        req: &CreateTransactionRequest,
+    ) -> Result<Transaction, CreateTransactionError> {
+        let installment_count = req.installment_count();
+        if installment_count == 0 || installment_count > MAX_INSTALLMENTS {
+            return Err(CreateTransactionError::InvalidInstallmentCount {
+                count: installment_count,
+                max: MAX_INSTALLMENTS,
+            });
+        }
+        let legs = self
+            .validate_legs(req.legs(), req.exchange_rate().as_ref())
+            .await?;
+        let req = &req.clone().with_legs(legs);
+        self.validate_category(&self.pool, req.category().as_ref())
+            .await?;
+        // PostgreSQL only stores microseconds, so truncate to return what is actually stored
+        let posting_date = req.posting_date().unwrap_or_else(Utc::now).trunc_subsecs(6);
+
+        let mut tx = self.start_psql_transaction().await?;
+
+        if let Some(plan) = req.new_installment_plan() {
+            self.save_installment_plan(&mut tx, plan)
+                .await
+                .context("failed to save installment plan")?;
+        }
+        let requests = if installment_count > 1 {
+            let plan = InstallmentPlan::new(
+                Uuid::new_v4(),
+                req.title().clone(),
+                req.paying_account_id()
+                    .unwrap_or_else(|| req.legs()[0].account_id()),
+                req.amount(),
+                installment_count,
+                posting_date,
+            );
+            self.save_installment_plan(&mut tx, &plan)
+                .await
+                .context("failed to save installment plan")?;
+            req.split_into_installments(&plan)
+        } else {
+            vec![req.clone()]
+        };
+
+        let mut transactions = Vec::with_capacity(requests.len());
+        for req in &requests {
+            let posting_date = req.posting_date().unwrap_or(posting_date);
+            let transaction_id = self
+                .save_transaction(&mut tx, req, posting_date)
+                .await
+                .map_err(|e| match (req.import_fingerprint(), req.occurrence()) {
+                    (Some(fingerprint), _)
+                        if violates_unique_index(&e, "entries_import_fingerprint_idx") =>
+                    {
+                        CreateTransactionError::Duplicate {
+                            fingerprint: fingerprint.to_string(),
+                        }
+                    }
+                    (_, Some(occurrence))
+                        if violates_unique_index(&e, "entries_schedule_occurrence_idx") =>
+                    {
+                        CreateTransactionError::OccurrencePosted {
+                            schedule_id: occurrence.schedule_id,
+                            date: occurrence.date,
+                        }
+                    }
+                    _ => CreateTransactionError::Unknown(anyhow!(e).context(format!(
+                        "failed to save transaction with title {:?}",
+                        req.title()
+                    ))),
+                })?;
+
+            tracing::debug!("created transaction, updating account balances...");
+
+            for leg in req.legs() {
+                let account = self
+                    .add_balance_to_account(&mut tx, leg.account_id(), leg.amount())
+                    .await
+                    .context("failed to update account balance")?;
+                tracing::debug!(account_id = ?account.id(), balance = ?account.balance(), "updated account balance");
+            }
+
+            transactions.push(
+                Transaction::new(
+                    transaction_id,
+                    req.title().clone(),
+                    req.legs().to_vec(),
+                    req.category().clone(),
+                    posting_date,
+                )
+                .with_payee(req.payee_id())
+                .with_tags(req.tags().to_vec())
+                .with_installment(req.installment())
+                .with_exchange_rate(req.exchange_rate()),
+            );
+        }
+
+        tx.commit()
+            .await
+            .context("failed to commit PostgreSQL transaction")?;
+
+        let transaction = transactions.swap_remove(0);
+        tracing::info!(transaction_id = ?transaction.id(), installment_count, "Successfully created transaction");
+
+        Ok(transaction)
+    }
+
+    /// Store an [InstallmentPlan] in the database
+    async fn save_installment_plan(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        plan: &InstallmentPlan,
+    ) -> Result<(), sqlx::Error> {
+        let query = sqlx::query!(
+            "
+INSERT INTO installment_plans (id, title, account_id, total_amount, installment_count, first_posting_date)
+VALUES ($1, $2, $3, $4, $5, $6)
+",
+            plan.id(),
+            plan.title().to_string(),
+            plan.account_id(),
+            plan.total_amount(),
+            plan.installment_count() as i32,
+            plan.first_posting_date()
+        );
+        tx.execute(query).await?;
+
+        Ok(())
+    }
+
+    /// Update a [Transaction].
+    ///
+    /// When the legs change, the balance effects of the old legs are reversed and the ones of
+    /// the new legs are applied, all in the same PostgreSQL transaction. The new legs are
+    /// converted at the request's exchange rate, or else at the one recorded with the
+    /// transaction.
+    ///
+    /// # Errors
+    ///
+    /// - [UpdateTransactionError::TransactionNotFound] if no [Transaction] with the given id
+    ///   exists
+    /// - [UpdateTransactionError::NotATransfer] if changing the amount or the accounts of a
+    ///   transaction that does not move money between exactly two accounts
+    /// - [UpdateTransactionError::NotEnoughLegs] if the new legs are less than two
+    /// - [UpdateTransactionError::Unbalanced] if the new legs do not sum up to zero
+    /// - [UpdateTransactionError::MissingExchangeRate] if the new legs are in two currencies but
+    ///   no exchange rate is given or recorded with the transaction
+    /// - [UpdateTransactionError::ExchangeRateMismatch] if the exchange rate is not between the
+    ///   currencies of the new legs
+    /// - [UpdateTransactionError::TooManyCurrencies] if the new legs are in more than two
+    ///   currencies
+    /// - [UpdateTransactionError::AccountNotFound] if any of the new legs' accounts does not exist
+    /// - [UpdateTransactionError::CategoryNotFound] if the new category does not exist
+    /// - [UpdateTransactionError::Unknown] if any other kind of error occurred
+    pub async fn update_transaction(
+        &self,
+        id: Uuid,
+        req: &UpdateTransactionRequest,
+    ) -> Result<Transaction, UpdateTransactionError> {
+        let mut tx = self.start_psql_transaction().await?;
+
+        let row = sqlx::query!("SELECT * FROM entries WHERE id = $1 FOR UPDATE", id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| UpdateTransactionError::Unknown(e.into()))?
+            .ok_or(UpdateTransactionError::TransactionNotFound { id })?;
+        let current_legs = self
+            .fetch_legs(&mut *tx, &[id])
+            .await
+            .map_err(|e| UpdateTransactionError::Unknown(e.into()))?
+            .remove(&id)
+            .unwrap_or_default();
+
+        let title = match req.title() {
+            Some(title) => title.clone(),
+            None => TransactionTitle::new(&row.title)
+                .map_err(|e| UpdateTransactionError::Unknown(e.into()))?,
+        };
+        let exchange_rate = match req.exchange_rate() {
+            Some(exchange_rate) => Some(exchange_rate),
+            None => exchange_rate_from_row(
+                row.exchange_rate_base.as_deref(),
+                row.exchange_rate_quote.as_deref(),
+                row.exchange_rate,
+            )?,
+        };
+        let category = match req.category() {
+            Some(category) => {
+                self.validate_category(&mut *tx, category.as_ref()).await?;
+                category.clone()
+            }
+            None => parse_category(row.category.as_deref())?,
+        };
+        let posting_date = req
+            .posting_date()
+            .map(|d| d.trunc_subsecs(6))
+            .unwrap_or(row.posting_date);
+
+        sqlx::query!(
+            "UPDATE entries SET title = $1, category = $2, posting_date = $3 WHERE id = $4",
+            title.to_string(),
+            category.as_ref().map(|c| c.to_string()),
+            posting_date,
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| UpdateTransactionError::Unknown(e.into()))?;
+
+        let legs = match req.legs() {
+            Some(update) => {
+                let legs = update
+                    .apply(&current_legs)
+                    .ok_or(UpdateTransactionError::NotATransfer { id })?;
+                let legs = self.validate_legs(&legs, exchange_rate.as_ref()).await?;
+
+                sqlx::query!(
+                    "
+UPDATE entries SET exchange_rate_base = $1, exchange_rate_quote = $2, exchange_rate = $3
+WHERE id = $4
+",
+                    exchange_rate.map(|rate| rate.base().code().to_string()),
+                    exchange_rate.map(|rate| rate.quote().code().to_string()),
+                    exchange_rate.map(|rate| rate.rate()),
+                    id
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| UpdateTransactionError::Unknown(e.into()))?;
+
+                tracing::debug!("updating legs, reverting old account balances...");
+                for leg in &current_legs {
+                    self.add_balance_to_account(&mut tx, leg.account_id(), -leg.amount())
+                        .await
+                        .context("failed to reset account balance")?;
+                }
+                sqlx::query!("DELETE FROM legs WHERE entry_id = $1", id)
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| UpdateTransactionError::Unknown(e.into()))?;
+
+                self.save_legs(&mut tx, id, &legs)
+                    .await
+                    .context("failed to save transaction legs")?;
+                for leg in &legs {
+                    self.add_balance_to_account(&mut tx, leg.account_id(), leg.amount())
+                        .await
+                        .context("failed to update account balance")?;
+                }
+
+                legs
+            }
+            None => current_legs,
+        };
+
+        tx.commit()
+            .await
+            .context("failed to commit PostgreSQL transaction")?;
+
+        tracing::info!(?id, "Successfully updated transaction");
+
+        Ok(Transaction::new(id, title, legs, category, posting_date)
+            .with_payee(row.payee_id)
+            .with_tags(row.tags)
+            .with_installment(installment_from_row(
+                row.installment_plan_id,
+                row.installment_number,
+            ))
+            .with_exchange_rate(exchange_rate))
+    }
+
+    /// Delete a [Transaction].
+    ///
+    /// This is the opposite operation of creating a transaction, so it also subtracts each
+    /// [Leg]'s amount from its account's balance.
+    ///
+    /// # Errors
+    ///
+    /// - [DeleteTransactionError::TransactionNotFound] if no [Transaction] with the given id
+    ///   exists
+    /// - [DeleteTransactionError::Unknown] in casy any other kind of error occurred
+    pub async fn delete_transaction(&self, id: Uuid) -> Result<(), DeleteTransactionError> {
+        let mut tx = self.start_psql_transaction().await?;
+        let legs = sqlx::query!(
+            "SELECT account_id, amount FROM legs WHERE entry_id = $1",
+            id
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| DeleteTransactionError::Unknown(e.into()))?;
+
+        let result = sqlx::query!("DELETE FROM entries WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| DeleteTransactionError::Unknown(e.into()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(DeleteTransactionError::TransactionNotFound { id });
+        }
+
+        for leg in legs {
+            self.add_balance_to_account(&mut tx, leg.account_id, -leg.amount)
+                .await
+                .context("failed to reset account balance")?;
+        }
+
+        tx.commit()
+            .await
+            .context("failed to commit PostgreSQL transaction")?;
+
+        Ok(())
+    }
+
+    /// Fetch a [Transaction] by its id.
+    ///
+    /// # Errors
+    ///
+    /// - [GetTransactionError::TransactionNotFound] if a transaction with the given id does not exist
+    /// - [GetTransactionError::Unknown] if any other kind of error occurred
+    pub async fn get_transaction_by_id(
+        &self,
+        id: Uuid,
+    ) -> Result<Transaction, GetTransactionError> {
+        let row = sqlx::query!("SELECT * FROM entries WHERE id = $1", id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => GetTransactionError::TransactionNotFound { id },
+                _ => GetTransactionError::Unknown(e.into()),
+            })?;
+
+        let transaction_title = TransactionTitle::new(&row.title)
+            .map_err(|e| GetTransactionError::Unknown(e.into()))?;
+        let category = parse_category(row.category.as_deref())?;
+        let legs = self
+            .fetch_legs(&self.pool, &[id])
+            .await
+            .map_err(|e| GetTransactionError::Unknown(e.into()))?
+            .remove(&id)
+            .unwrap_or_default();
+
+        let transaction = Transaction::new(id, transaction_title, legs, category, row.posting_date)
+            .with_payee(row.payee_id)
+            .with_tags(row.tags)
+            .with_installment(installment_from_row(
+                row.installment_plan_id,
+                row.installment_number,
+            ))
+            .with_exchange_rate(exchange_rate_from_row(
+                row.exchange_rate_base.as_deref(),
+                row.exchange_rate_quote.as_deref(),
+                row.exchange_rate,
+            )?);
+        tracing::info!(?id, "Successfully retrieved transaction");
+
+        Ok(transaction)
+    }
+
+    /// List the [Transaction]s matching the given filters, in the given order, along with how
+    /// many of them there are in total.
+    ///
+    /// When sorting by posting date, the listing can start right after the transaction `after`
+    /// points to, and a page that is not the last one comes with a cursor to the next.
+    pub async fn list_transactions(
+        &self,
+        filters: &TransactionFilters,
+        sort: TransactionSort,
+        pagination: Option<PaginationParameters>,
+        after: Option<TransactionCursor>,
+    ) -> Result<Page<Transaction>, ListTransactionsError> {
+        let mut count_query = QueryBuilder::<Postgres>::new("SELECT COUNT(*)");
+        count_query.push(FILTERED_ENTRIES);
+        push_transaction_filters(&mut count_query, filters);
+        let total: i64 = count_query
+            .build_query_scalar()
+            .fetch_one(&self.pool)
+            .await?;
+
+        let mut query = QueryBuilder::<Postgres>::new(
+            "SELECT e.id, e.title, e.category, e.posting_date, e.payee_id, e.tags, e.installment_plan_id, e.installment_number, e.exchange_rate_base, e.exchange_rate_quote, e.exchange_rate",
+        );
+        query.push(FILTERED_ENTRIES);
+        push_transaction_filters(&mut query, filters);
+
+        let column = match sort.field {
+            TransactionSortField::PostingDate => "e.posting_date",
+            TransactionSortField::Amount => "a.amount",
+            TransactionSortField::Title => "e.title",
+        };
+        let (direction, comparison) = match sort.direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+        let keyset = sort.field == TransactionSortField::PostingDate;
+        if let Some(after) = after.filter(|_| keyset) {
+            query.push(format!(" AND (e.posting_date, e.id) {comparison} ("));
+            query.push_bind(after.posting_date);
+            query.push(", ");
+            query.push_bind(after.id);
+            query.push(")");
+        }
+        query.push(format!(" ORDER BY {column} {direction}, e.id {direction}"));
+
+        // Fetch one more row than requested to know whether there is a next page
+        if let Some(pagination) = &pagination {
+            query.push(" LIMIT ");
+            query.push_bind(pagination.limit + 1);
+            query.push(" OFFSET ");
+            query.push_bind(pagination.offset);
+        }
+
+        let mut rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| ListTransactionsError::Unknown(e.into()))?;
+        let has_next_page = pagination
+            .as_ref()
+            .is_some_and(|p| rows.len() as i64 > p.limit);
+        if let Some(pagination) = &pagination {
+            rows.truncate(pagination.limit.max(0) as usize);
+        }
+
+        tracing::debug!(row_count = rows.len(), "Got rows from the database");
+
+        let ids = rows
+            .iter()
+            .map(|r| r.try_get::<Uuid, &str>("id"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut legs = self.fetch_legs(&self.pool, &ids).await?;
+
+        rows.iter()
+            .map(|r| {
+                let title: String = r.try_get::<String, &str>("title")?;
+                let transaction_title = TransactionTitle::new(&title).map_err(|e| {
+                    tracing::error!(error = ?e, "Failed to fetch transactions from the database");
+                    ListTransactionsError::Unknown(e.into())
+                })?;
+                let id = r.try_get::<Uuid, &str>("id")?;
+                let category = parse_category(r.try_get("category")?)?;
+                let posting_date = r.try_get::<DateTime<Utc>, &str>("posting_date")?;
+                let payee_id = r.try_get::<Option<Uuid>, &str>("payee_id")?;
+                let tags = r.try_get::<Vec<String>, &str>("tags")?;
+                let installment = installment_from_row(
+                    r.try_get("installment_plan_id")?,
+                    r.try_get("installment_number")?,
+                );
+                let exchange_rate = exchange_rate_from_row(
+                    r.try_get("exchange_rate_base")?,
+                    r.try_get("exchange_rate_quote")?,
+                    r.try_get("exchange_rate")?,
+                )?;
+
+                let transaction = Transaction::new(
+                    id,
+                    transaction_title,
+                    legs.remove(&id).unwrap_or_default(),
+                    category,
+                    posting_date,
+                )
+                .with_payee(payee_id)
+                .with_tags(tags)
+                .with_installment(installment)
+                .with_exchange_rate(exchange_rate);
+                tracing::info!(?id, "Successfully retrieved transaction");
+
+                Ok(transaction)
+            })
+            .collect::<Result<Vec<_>, ListTransactionsError>>()
+            .map(|items| {
+                let next_cursor = items
+                    .last()
+                    .filter(|_| keyset && has_next_page)
+                    .map(|last| TransactionCursor::new(last).to_string());
+
+                Page {
+                    items,
+                    total,
+                    next_cursor,
+                }
+            })
+    }
+
+    /// List the transactions with a leg on the given account as [AccountPosting]s, ordered by
+    /// posting date, along with how many of them there are in total.
+    ///
+    /// The running balance is computed over the whole history of the account, so it does not
+    /// depend on the page. Like [Self::list_transactions], the listing can start right after the
+    /// posting `after` points to, and a page that is not the last one comes with a cursor to the
+    /// next.
+    ///
+    /// # Errors
+    ///
+    /// - [ListAccountPostingsError::AccountNotFound] if the account does not exist
+    /// - [ListAccountPostingsError::Unknown] in case any other error occurred
+    pub async fn list_account_postings(
+        &self,
+        account_id: Uuid,
+        direction: SortDirection,
+        pagination: Option<PaginationParameters>,
+        after: Option<TransactionCursor>,
+    ) -> Result<Page<AccountPosting>, ListAccountPostingsError> {
+        let account = self
+            .get_account_by_id(account_id)
+            .await
+            .map_err(|e| match e {
+                GetAccountError::NotFound { id } => {
+                    ListAccountPostingsError::AccountNotFound { id }
+                }
+                GetAccountError::Unknown(err) => ListAccountPostingsError::Unknown(err),
+            })?;
+
+        let total = sqlx::query_scalar!(
+            r#"SELECT COUNT(DISTINCT entry_id) AS "count!" FROM legs WHERE account_id = $1"#,
+            account_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("failed to count the postings of the account")?;
+
+        let mut query = QueryBuilder::<Postgres>::new(
+            r#"
+WITH postings AS (
+  SELECT e.id, e.title, e.category, e.posting_date, SUM(l.amount) AS amount
+  FROM legs l
+  JOIN entries e ON e.id = l.entry_id
+  WHERE l.account_id = "#,
         );
+        query.push_bind(account_id);
+        query.push(
+            r#"
+  GROUP BY e.id
+), running AS (
+  SELECT
+    *,
+    SUM(amount) OVER (
+      ORDER BY posting_date, id ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+    ) AS balance
+  FROM postings
+)
+SELECT * FROM running WHERE TRUE"#,
+        );
+
+        let (order, comparison) = match direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+        if let Some(after) = after {
+            query.push(format!(" AND (posting_date, id) {comparison} ("));
+            query.push_bind(after.posting_date);
+            query.push(", ");
+            query.push_bind(after.id);
+            query.push(")");
+        }
+        query.push(format!(" ORDER BY posting_date {order}, id {order}"));
+
+        // Fetch one more row than requested to know whether there is a next page
+        if let Some(pagination) = &pagination {
+            query.push(" LIMIT ");
+            query.push_bind(pagination.limit + 1);
+            query.push(" OFFSET ");
+            query.push_bind(pagination.offset);
+        }
+
+        let mut rows = query
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .context("failed to fetch the postings of the account")?;
+        let has_next_page = pagination
+            .as_ref()
+            .is_some_and(|p| rows.len() as i64 > p.limit);
+        if let Some(pagination) = &pagination {
+            rows.truncate(pagination.limit.max(0) as usize);
+        }
+
+        let account_type = account.account_type();
+        let items = rows
+            .iter()
+            .map(|r| {
+                let title = TransactionTitle::new(&r.try_get::<String, &str>("title")?)?;
+
+                Ok(AccountPosting::new(
+                    r.try_get("id")?,
+                    title,
+                    parse_category(r.try_get("category")?)?,
+                    r.try_get("posting_date")?,
+                    account_type.balance(r.try_get("amount")?),
+                    account_type.balance(r.try_get("balance")?),
+                ))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let next_cursor = items
+            .last()
+            .filter(|_| has_next_page)
+            .map(|last| TransactionCursor::from(last).to_string());
+
+        Ok(Page {
+            items,
+            total,
+            next_cursor,
+        })
+    }
+
+    /// Import the transactions of a file into an account, recording the import as an
+    /// [ImportBatch] the created transactions are tagged with.
+    ///
+    /// Each transaction moves money between the account and the request's default expense or
+    /// income account, and gets the [Payee] its title normalizes to. Rows that were already
+    /// imported, according to their fingerprint, are skipped, and rows that cannot be read or
+    /// saved are reported as failed without stopping the import.
+    ///
+    /// On a dry run nothing is written, not even the account or the batch: the report tells
+    /// which rows would be created.
+    ///
+    /// # Errors
+    ///
+    /// - [ImportError::MissingProfile] if a CSV file comes without an import profile
+    /// - [ImportError::Csv] or [ImportError::Ofx] if the file cannot be read at all
+    /// - [ImportError::Unknown] if any other kind of error occurred
+    pub async fn import(
+        &self,
+        req: &ImportRequest,
+        timezone: Tz,
+    ) -> Result<ImportReport, ImportError> {
+        let account = if req.is_dry_run() {
+            match self.get_account_by_name(req.account().name()).await {
+                Ok(account) => Some(account),
+                Err(GetAccountByNameError::NotFound { .. }) => None,
+                Err(GetAccountByNameError::Unknown(e)) => return Err(ImportError::Unknown(e)),
+            }
+        } else {
+            let account = self
+                .get_or_create_account(req.account())
+                .await
+                .context("failed to get or create the imported account")?;
+            Some(account)
+        };
+        // An account that does not exist yet has nothing imported into it, whatever its id
+        let account_id = account.as_ref().map(Account::id).unwrap_or_default();
+        let rows = read_rows(
+            req.format(),
+            req.profile(),
+            req.contents(),
+            account_id,
+            timezone,
+        )?;
+
+        let batch_id = match (&account, req.is_dry_run()) {
+            (Some(account), false) => Some(
+                self.create_import_batch(req, account.id(), rows.len())
+                    .await
+                    .context("failed to create import batch")?,
+            ),
+            _ => None,
+        };
+        let rules = match batch_id {
+            Some(_) => self
+                .fetch_rules(None)
+                .await
+                .and_then(|rules| RuleSet::new(rules).context("failed to compile rule patterns"))?,
+            None => RuleSet::default(),
+        };
+        let existing = match (&account, req.is_dry_run()) {
+            (Some(_), true) => self
+                .find_import_fingerprints(&rows)
+                .await
+                .context("failed to look up import fingerprints")?,
+            _ => HashSet::new(),
+        };
+
+        let mut results = Vec::with_capacity(rows.len());
+        for row in rows {
+            let result = match row {
+                Ok(row) => {
+                    let status = match batch_id {
+                        Some(batch_id) => {
+                            self.import_row(req, &row, account_id, batch_id, &rules)
+                                .await
+                        }
+                        None => preview_row(&row, &existing),
+                    };
+                    ImportRowResult::new(
+                        row.line(),
+                        Some(row.posting_date()),
+                        Some(row.title().to_string()),
+                        Some(row.amount()),
+                        status,
+                    )
+                }
+                Err(invalid) => ImportRowResult::new(
+                    invalid.line,
+                    None,
+                    None,
+                    None,
+                    ImportRowStatus::Failed {
+                        error: invalid.error,
+                    },
+                ),
+            };
+            results.push(result);
+        }
+
+        let batch = match batch_id {
+            Some(id) => Some(
+                self.fetch_import_batch(id)
+                    .await
+                    .context("failed to fetch import batch")?
+                    .ok_or_else(|| anyhow!("import batch {id} vanished during the import"))?,
+            ),
+            None => None,
+        };
+        let report = ImportReport::new(batch, req.is_dry_run(), results);
+        tracing::info!(source = req.source(), summary = ?report.summary(), "Imported file");
+
+        Ok(report)
+    }
+
+    /// Store a new [ImportBatch] for the given request, returning its id
+    async fn create_import_batch(
+        &self,
+        req: &ImportRequest,
+        account_id: Uuid,
+        row_count: usize,
+    ) -> Result<Uuid, sqlx::Error> {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            "
+INSERT INTO import_batches (id, source, format, profile, account_id, row_count)
+VALUES ($1, $2, $3, $4, $5, $6)
+",
+            id,
+            req.source(),
+            req.format().as_str(),
+            req.profile_name(),
+            account_id,
+            row_count as i32,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(id)
+    }
+
+    /// The fingerprints of the given rows that were already imported
+    async fn find_import_fingerprints(
+        &self,
+        rows: &[Result<ImportRow, InvalidRow>],
+    ) -> Result<HashSet<String>, sqlx::Error> {
+        let fingerprints: Vec<String> = rows
+            .iter()
+            .flatten()
+            .map(|row| row.fingerprint().to_string())
+            .collect();
+
+        let existing = sqlx::query_scalar!(
+            r#"SELECT import_fingerprint AS "fingerprint!" FROM entries WHERE import_fingerprint = ANY($1)"#,
+            &fingerprints
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(existing.into_iter().collect())
+    }
+
+    /// Create the transaction of an imported row, along with its counterpart account, its payee
+    /// and its category if they do not exist yet
+    async fn import_row(
+        &self,
+        import: &ImportRequest,
+        row: &ImportRow,
+        account_id: Uuid,
+        batch_id: Uuid,
+        rules: &RuleSet,
+    ) -> ImportRowStatus {
+        let req = match self
+            .import_row_request(import, row, account_id, rules)
+            .await
+        {
+            Ok(req) => req.with_import_batch(batch_id),
+            Err(e) => {
+                tracing::error!(error = %e, line = row.line(), "Failed to read imported row");
+                return ImportRowStatus::Failed {
+                    error: e.to_string(),
+                };
+            }
+        };
+
+        match self.create_transaction(&req).await {
+            Ok(transaction) => ImportRowStatus::Created {
+                transaction_id: Some(transaction.id()),
+            },
+            Err(CreateTransactionError::Duplicate { fingerprint }) => {
+                tracing::info!(fingerprint, "Skipping already imported transaction");
+                ImportRowStatus::Duplicate { fingerprint }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, line = row.line(), "Failed to create transaction");
+                ImportRowStatus::Failed {
+                    error: e.to_string(),
+                }
+            }
+        }
+    }
+
+    /// Build the transaction of an imported row. The first of `rules` matching the row sets its
+    /// category, its counterpart account and its tags, falling back to the row's category and
+    /// the import's default accounts.
+    async fn import_row_request(
+        &self,
+        import: &ImportRequest,
+        row: &ImportRow,
+        account_id: Uuid,
+        rules: &RuleSet,
+    ) -> anyhow::Result<CreateTransactionRequest> {
+        let payee_id = match PayeeName::from_title(row.title()) {
+            Some(name) => Some(self.get_or_create_payee(&name).await?.id()),
+            None => None,
+        };
+        let rule = rules.first_match(&RuleSubject {
+            title: row.title(),
+            amount: row.amount().abs(),
+            source_account_ids: vec![account_id],
+            payee_id,
+        });
+        let actions = rule.map(Rule::actions);
+
+        let counterpart_id = match actions.and_then(|actions| actions.destination_account_id) {
+            Some(id) => id,
+            None => self
+                .get_or_create_account(&import.counterpart(row))
+                .await?
+                .id(),
+        };
+        let category = match (
+            actions.and_then(|actions| actions.category.clone()),
+            row.category_name()?,
+        ) {
+            (Some(category), _) => Some(category),
+            (None, Some(name)) => Some(self.get_or_create_category(&name).await?.name().clone()),
+            (None, None) => None,
+        };
+
+        let mut req = row.request(account_id, counterpart_id, category)?;
+        if let Some(payee_id) = payee_id {
+            req = req.with_payee(payee_id);
+        }
+        if let Some(actions) = actions {
+            req = req.with_tags(actions.tags.clone());
+        }
+        if let Some(marker) = InstallmentMarker::parse(row.title()) {
+            let title = TransactionTitle::new(&marker.title)
+                .or_else(|_| TransactionTitle::new(row.title()))?;
+            let first_posting_date = row.posting_date() - Months::new(marker.number - 1);
+            req = match self
+                .find_installment_plan(account_id, &title, marker.count, first_posting_date)
+                .await?
+            {
+                Some(plan_id) => req.with_installment(Installment {
+                    plan_id,
+                    number: marker.number,
+                }),
+                None => req.with_new_installment_plan(
+                    InstallmentPlan::new(
+                        Uuid::new_v4(),
+                        title,
+                        account_id,
+                        row.amount().abs() * Decimal::from(marker.count),
+                        marker.count,
+                        first_posting_date,
+                    ),
+                    marker.number,
+                ),
+            };
+        }
+
+        Ok(req)
+    }
+
+    /// Find the [InstallmentPlan] an imported installment belongs to, if an installment of the
+    /// same purchase was imported before. Installments of a plan share the paying account, the
+    /// title without the marker and the count, and their first installment is posted within 15
+    /// days of each other's, as statement dates vary. Their amounts are not compared, as cards
+    /// round installments unevenly.
+    async fn find_installment_plan(
+        &self,
+        account_id: Uuid,
+        title: &TransactionTitle,
+        count: u32,
+        first_posting_date: DateTime<Utc>,
+    ) -> anyhow::Result<Option<Uuid>> {
+        sqlx::query_scalar!(
+            "
+SELECT id FROM installment_plans
+WHERE account_id = $1 AND title = $2 AND installment_count = $3
+    AND first_posting_date BETWEEN $4::timestamptz - interval '15 days'
+        AND $4::timestamptz + interval '15 days'
+ORDER BY abs(extract(epoch FROM first_posting_date - $4::timestamptz))
+LIMIT 1
+",
+            account_id,
+            title.to_string(),
+            count as i32,
+            first_posting_date
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to look up installment plan")
+    }
+
+    /// What an account, like a credit card, is still committed to pay in installments after
+    /// now. Plans whose transactions were all deleted are left out.
+    ///
+    /// # Errors
+    ///
+    /// - [GetInstallmentCommitmentsError::AccountNotFound] if the account does not exist
+    /// - [GetInstallmentCommitmentsError::Unknown] if any other kind of error occurred
+    pub async fn get_installment_commitments(
+        &self,
+        account_id: Uuid,
+    ) -> Result<InstallmentCommitments, GetInstallmentCommitmentsError> {
+        self.get_account_by_id(account_id)
+            .await
+            .map_err(|e| match e {
+                GetAccountError::NotFound { id } => {
+                    GetInstallmentCommitmentsError::AccountNotFound { id }
+                }
+                GetAccountError::Unknown(e) => GetInstallmentCommitmentsError::Unknown(e),
+            })?;
+
+        let rows = sqlx::query!(
+            "
+SELECT * FROM installment_plans p
+WHERE p.account_id = $1 AND EXISTS (SELECT 1 FROM entries e WHERE e.installment_plan_id = p.id)
+",
+            account_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch installment plans")?;
+
+        let now = Utc::now();
+        let mut plans = Vec::with_capacity(rows.len());
+        for row in rows {
+            let title = TransactionTitle::new(&row.title)
+                .with_context(|| format!("failed to parse title of installment plan {}", row.id))?;
+            let plan = InstallmentPlan::new(
+                row.id,
+                title,
+                row.account_id,
+                row.total_amount,
+                row.installment_count as u32,
+                row.first_posting_date,
+            );
+            let commitment = InstallmentCommitment::new(plan, now);
+            if commitment.remaining_count() > 0 {
+                plans.push(commitment);
+            }
+        }
+        plans.sort_by_key(|commitment| {
+            let plan = commitment.plan();
+            (plan.posting_date(plan.installment_count()), plan.id())
+        });
+
+        Ok(InstallmentCommitments::new(account_id, plans))
+    }
+
+    /// Set the [CreditCard] details of a liability account, replacing any it had
+    pub async fn set_credit_card(
+        &self,
+        account_id: Uuid,
+        req: &SetCreditCardRequest,
+    ) -> Result<CreditCard, SetCreditCardError> {
+        for day in [req.closing_day(), req.due_day()] {
+            if !(1..=31).contains(&day) {
+                return Err(SetCreditCardError::InvalidDay { day });
+            }
+        }
+        if req.credit_limit().is_sign_negative() {
+            return Err(SetCreditCardError::NegativeLimit);
+        }
+
+        let account = self
+            .get_account_by_id(account_id)
+            .await
+            .map_err(|e| match e {
+                GetAccountError::NotFound { id } => SetCreditCardError::AccountNotFound { id },
+                GetAccountError::Unknown(e) => SetCreditCardError::Unknown(e),
+            })?;
+        if account.account_type() != AccountType::Liability {
+            return Err(SetCreditCardError::NotALiabilityAccount { id: account_id });
+        }
+        if let Some(id) = req.payment_account_id() {
+            let payment_account = self.get_account_by_id(id).await.map_err(|e| match e {
+                GetAccountError::NotFound { id } => SetCreditCardError::AccountNotFound { id },
+                GetAccountError::Unknown(e) => SetCreditCardError::Unknown(e),
+            })?;
+            if payment_account.account_type() != AccountType::Asset {
+                return Err(SetCreditCardError::NotAnAssetAccount { id });
+            }
+        }
+
+        sqlx::query!(
+            "
+INSERT INTO credit_cards (account_id, closing_day, due_day, credit_limit, payment_account_id)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (account_id) DO UPDATE
+SET closing_day = EXCLUDED.closing_day, due_day = EXCLUDED.due_day, credit_limit = EXCLUDED.credit_limit,
+    payment_account_id = EXCLUDED.payment_account_id
+",
+            account_id,
+            req.closing_day() as i32,
+            req.due_day() as i32,
+            req.credit_limit(),
+            req.payment_account_id()
+        )
+        .execute(&self.pool)
+        .await
+        .context("failed to save credit card")?;
+
+        Ok(CreditCard::new(
+            account_id,
+            req.closing_day(),
+            req.due_day(),
+            req.credit_limit(),
+        )
+        .with_payment_account(req.payment_account_id()))
+    }
+
+    /// Compute the [Statement]s of a credit card from `from` to `to`, both included, with
+    /// posting dates taken in `timezone`.
+    ///
+    /// By default, the range starts five months before the open statement and ends at the
+    /// latest statement with postings, or the open one if that is later.
+    ///
+    /// # Errors
+    ///
+    /// - [GetStatementsError::AccountNotFound] if the account does not exist
+    /// - [GetStatementsError::NotACreditCard] if the account has no credit card details
+    /// - [GetStatementsError::InvalidRange] if `from` is after `to`
+    /// - [GetStatementsError::TooManyStatements] if the range has more than
+    ///   [CardStatements::MAX_STATEMENTS] months
+    /// - [GetStatementsError::Unknown] in case any other error occurred
+    pub async fn get_card_statements(
+        &self,
+        account_id: Uuid,
+        from: Option<Month>,
+        to: Option<Month>,
+        timezone: Tz,
+    ) -> Result<CardStatements, GetStatementsError> {
+        let (account, card) = self.fetch_card_account(account_id).await?;
+        let postings = self.fetch_card_postings(account_id, timezone).await?;
+
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+        let open_month = card.statement_month(today);
+        let from = from.unwrap_or_else(|| (0..5).fold(open_month, |month, _| month.previous()));
+        let to = to.unwrap_or_else(|| {
+            postings
+                .last()
+                .map(|(date, _)| card.statement_month(*date))
+                .unwrap_or(open_month)
+                .max(open_month)
+        });
+        if from > to {
+            return Err(GetStatementsError::InvalidRange { from, to });
+        }
+        let count = from.count_until(to);
+        if count > CardStatements::MAX_STATEMENTS as u64 {
+            return Err(GetStatementsError::TooManyStatements {
+                count,
+                max: CardStatements::MAX_STATEMENTS,
+            });
+        }
+
+        let mut statements = Vec::new();
+        let mut month = from;
+        while month <= to {
+            statements.push(Statement::new(&card, month, &postings, today));
+            month = month.next();
+        }
+
+        Ok(CardStatements::new(card, account.balance(), statements))
+    }
+
+    /// Compute the [Statement] of a credit card that closes in `month`, with posting dates taken
+    /// in `timezone`.
+    ///
+    /// # Errors
+    ///
+    /// - [GetStatementsError::AccountNotFound] if the account does not exist
+    /// - [GetStatementsError::NotACreditCard] if the account has no credit card details
+    /// - [GetStatementsError::Unknown] in case any other error occurred
+    pub async fn get_card_statement(
+        &self,
+        account_id: Uuid,
+        month: Month,
+        timezone: Tz,
+    ) -> Result<Statement, GetStatementsError> {
+        let (_, card) = self.fetch_card_account(account_id).await?;
+        let postings = self.fetch_card_postings(account_id, timezone).await?;
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+
+        Ok(Statement::new(&card, month, &postings, today))
+    }
+
+    /// Fetch a credit card account along with its [CreditCard] details
+    async fn fetch_card_account(
+        &self,
+        account_id: Uuid,
+    ) -> Result<(Account, CreditCard), GetStatementsError> {
+        let account = self
+            .get_account_by_id(account_id)
+            .await
+            .map_err(|e| match e {
+                GetAccountError::NotFound { id } => GetStatementsError::AccountNotFound { id },
+                GetAccountError::Unknown(e) => GetStatementsError::Unknown(e),
+            })?;
+        let card = self
+            .fetch_credit_cards(Some(account_id))
+            .await?
+            .pop()
+            .ok_or(GetStatementsError::NotACreditCard { id: account_id })?;
+
+        Ok((account, card))
+    }
+
+    /// Fetch the [CreditCard] of the given account, or all of them if there is none
+    async fn fetch_credit_cards(
+        &self,
+        account_id: Option<Uuid>,
+    ) -> anyhow::Result<Vec<CreditCard>> {
+        let rows = sqlx::query!(
+            "SELECT * FROM credit_cards WHERE $1::uuid IS NULL OR account_id = $1",
+            account_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch credit cards")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                CreditCard::new(
+                    row.account_id,
+                    row.closing_day as u32,
+                    row.due_day as u32,
+                    row.credit_limit,
+                )
+                .with_payment_account(row.payment_account_id)
+            })
+            .collect())
+    }
+
+    /// The postings of a credit card, one per transaction, as local posting dates in `timezone`
+    /// and amounts following the sign convention of liabilities, the earliest first
+    async fn fetch_card_postings(
+        &self,
+        account_id: Uuid,
+        timezone: Tz,
+    ) -> anyhow::Result<Vec<(NaiveDate, Decimal)>> {
+        let rows = sqlx::query!(
+            r#"
+SELECT (e.posting_date AT TIME ZONE $2)::date AS "date!", SUM(l.amount) AS "amount!"
+FROM legs l
+JOIN entries e ON e.id = l.entry_id
+WHERE l.account_id = $1
+GROUP BY e.id
+ORDER BY 1
+"#,
+            account_id,
+            timezone.name()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch the postings of the credit card")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.date, AccountType::Liability.balance(row.amount)))
+            .collect())
+    }
+
+    /// Create a [Schedule]. Its occurrences are posted by [Self::post_due_occurrences] once
+    /// they come due, including the ones already past.
+    ///
+    /// # Errors
+    ///
+    /// - [CreateScheduleError::Invalid] if the fields are not valid or refer to accounts or a
+    ///   category that do not exist
+    /// - [CreateScheduleError::Unknown] in case any other error occurred
+    pub async fn create_schedule(
+        &self,
+        req: &CreateScheduleRequest,
+    ) -> Result<Schedule, CreateScheduleError> {
+        self.validate_schedule(req).await?;
+
+        let id = Uuid::new_v4();
+        let transfer = req.transfer();
+        let recurrence = req.recurrence();
+        sqlx::query!(
+            "
+INSERT INTO schedules (id, title, amount, source_account_id, destination_account_id, category,
+    frequency, every, day, start_date, end_date)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+",
+            id,
+            transfer.title.to_string(),
+            transfer.amount,
+            transfer.source_account_id,
+            transfer.destination_account_id,
+            transfer.category.as_ref().map(|c| c.to_string()),
+            recurrence.frequency(),
+            recurrence.interval() as i32,
+            recurrence.day().map(|day| day as i32),
+            req.start_date(),
+            req.end_date()
+        )
+        .execute(&self.pool)
+        .await
+        .context("failed to create schedule")?;
+
+        tracing::info!(schedule_id = ?id, "Successfully created schedule");
+        Ok(Schedule::new(
+            id,
+            transfer.clone(),
+            recurrence,
+            req.start_date(),
+            req.end_date(),
+        ))
+    }
+
+    /// Replace all fields of a [Schedule]. Occurrences already posted are left as they are, and
+    /// only the occurrences of the new series after the last posted one are posted.
+    ///
+    /// # Errors
+    ///
+    /// - [UpdateScheduleError::NotFound] if no [Schedule] with the given id exists
+    /// - [UpdateScheduleError::Invalid] if the new fields are not valid, like when creating a
+    ///   schedule
+    /// - [UpdateScheduleError::Unknown] in case any other error occurred
+    pub async fn update_schedule(
+        &self,
+        id: Uuid,
+        req: &CreateScheduleRequest,
+    ) -> Result<Schedule, UpdateScheduleError> {
+        self.validate_schedule(req).await?;
+
+        let transfer = req.transfer();
+        let recurrence = req.recurrence();
+        let result = sqlx::query!(
+            "
+UPDATE schedules
+SET title = $1, amount = $2, source_account_id = $3, destination_account_id = $4, category = $5,
+    frequency = $6, every = $7, day = $8, start_date = $9, end_date = $10
+WHERE id = $11
+",
+            transfer.title.to_string(),
+            transfer.amount,
+            transfer.source_account_id,
+            transfer.destination_account_id,
+            transfer.category.as_ref().map(|c| c.to_string()),
+            recurrence.frequency(),
+            recurrence.interval() as i32,
+            recurrence.day().map(|day| day as i32),
+            req.start_date(),
+            req.end_date(),
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .context("failed to update schedule")?;
+
+        if result.rows_affected() == 0 {
+            return Err(UpdateScheduleError::NotFound { id });
+        }
+
+        tracing::info!(schedule_id = ?id, "Successfully updated schedule");
+        Ok(Schedule::new(
+            id,
+            transfer.clone(),
+            recurrence,
+            req.start_date(),
+            req.end_date(),
+        ))
+    }
+
+    /// Checks that a [Schedule] is valid, that the accounts and category it posts to exist and
+    /// that the accounts are in the same currency
+    async fn validate_schedule(
+        &self,
+        req: &CreateScheduleRequest,
+    ) -> Result<(), CreateScheduleError> {
+        req.validate()?;
+
+        let transfer = req.transfer();
+        let mut currencies = Vec::new();
+        for id in [transfer.source_account_id, transfer.destination_account_id] {
+            let account = self.get_account_by_id(id).await.map_err(|e| match e {
+                GetAccountError::NotFound { id } => {
+                    ScheduleValidationError::AccountNotFound { id }.into()
+                }
+                GetAccountError::Unknown(e) => CreateScheduleError::Unknown(e),
+            })?;
+            currencies.push(account.currency());
+        }
+        // Occurrences are posted without an exchange rate, so both legs must be in one currency
+        if let [source_currency, destination_currency] = currencies[..]
+            && source_currency != destination_currency
+        {
+            return Err(ScheduleValidationError::CurrencyMismatch {
+                source_currency,
+                destination_currency,
+            }
+            .into());
+        }
+
+        self.validate_category(&self.pool, transfer.category.as_ref())
+            .await
+            .map_err(|e| match e {
+                CreateTransactionError::CategoryNotFound { name } => {
+                    ScheduleValidationError::CategoryNotFound { name }.into()
+                }
+                e => CreateScheduleError::Unknown(e.into()),
+            })
+    }
+
+    /// Fetch a [Schedule] by its id.
+    ///
+    /// # Errors
+    ///
+    /// - [GetScheduleError::NotFound] if no [Schedule] with the given id exists
+    /// - [GetScheduleError::Unknown] in case any other error occurred
+    pub async fn get_schedule(&self, id: Uuid) -> Result<Schedule, GetScheduleError> {
+        self.fetch_schedules(Some(id))
+            .await?
+            .pop()
+            .ok_or(GetScheduleError::NotFound { id })
+    }
+
+    /// List all [Schedule]s, by title
+    pub async fn list_schedules(&self) -> Result<Vec<Schedule>, ListSchedulesError> {
+        Ok(self.fetch_schedules(None).await?)
+    }
+
+    /// Fetch the [Schedule] with the given id, or all of them if there is none, by title
+    async fn fetch_schedules(&self, id: Option<Uuid>) -> anyhow::Result<Vec<Schedule>> {
+        let rows = sqlx::query!(
+            "SELECT * FROM schedules WHERE $1::uuid IS NULL OR id = $1 ORDER BY title, id",
+            id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch schedules")?;
+
+        rows.into_iter()
+            .map(|row| {
+                let transfer = ScheduledTransfer {
+                    title: TransactionTitle::new(&row.title)
+                        .with_context(|| format!("failed to parse title of schedule {}", row.id))?,
+                    amount: row.amount,
+                    source_account_id: row.source_account_id,
+                    destination_account_id: row.destination_account_id,
+                    category: parse_category(row.category.as_deref())?,
+                };
+                let recurrence = Recurrence::from_parts(
+                    &row.frequency,
+                    row.every as u32,
+                    row.day.map(|day| day as u32),
+                )
+                .with_context(|| format!("failed to parse recurrence of schedule {}", row.id))?;
+                Ok(Schedule::new(
+                    row.id,
+                    transfer,
+                    recurrence,
+                    row.start_date,
+                    row.end_date,
+                ))
+            })
+            .collect()
+    }
+
+    /// Delete a [Schedule]. Transactions it already posted are left as they are.
+    ///
+    /// # Errors
+    ///
+    /// - [DeleteScheduleError::NotFound] if no [Schedule] with the given id exists
+    /// - [DeleteScheduleError::Unknown] in case any other error occurred
+    pub async fn delete_schedule(&self, id: Uuid) -> Result<(), DeleteScheduleError> {
+        let result = sqlx::query!("DELETE FROM schedules WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DeleteScheduleError::Unknown(e.into()))?;
+
+        if result.rows_affected() == 0 {
+            Err(DeleteScheduleError::NotFound { id })
+        } else {
+            tracing::info!(?id, "Successfully deleted schedule");
+            Ok(())
+        }
+    }
+
+    /// The date of the last posted occurrence of each [Schedule] that posted any
+    async fn fetch_last_posted_occurrences(&self) -> anyhow::Result<HashMap<Uuid, NaiveDate>> {
+        let rows = sqlx::query!(
+            r#"
+SELECT schedule_id AS "schedule_id!", MAX(occurrence_date) AS "date!"
+FROM entries
+WHERE schedule_id IS NOT NULL
+GROUP BY schedule_id
+"#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch the last posted occurrences")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.schedule_id, row.date))
+            .collect())
+    }
+
+    async fn fetch_skipped_occurrences(&self) -> anyhow::Result<HashSet<Occurrence>> {
+        let rows = sqlx::query!("SELECT * FROM skipped_occurrences")
+            .fetch_all(&self.pool)
+            .await
+            .context("failed to fetch skipped occurrences")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Occurrence {
+                schedule_id: row.schedule_id,
+                date: row.occurrence_date,
+            })
+            .collect())
+    }
+
+    /// List the occurrences of all [Schedule]s that are not posted yet, up to `until`, the
+    /// earliest first. Skipped occurrences are listed too.
+    ///
+    /// # Errors
+    ///
+    /// - [ListUpcomingOccurrencesError::TooFarAhead] if `until` is more than
+    ///   [UpcomingOccurrence::MAX_DAYS_AHEAD] days after today in `timezone`
+    /// - [ListUpcomingOccurrencesError::Unknown] in case any other error occurred
+    pub async fn list_upcoming_occurrences(
+        &self,
+        until: NaiveDate,
+        timezone: Tz,
+    ) -> Result<Vec<UpcomingOccurrence>, ListUpcomingOccurrencesError> {
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+        if today
+            .checked_add_days(Days::new(UpcomingOccurrence::MAX_DAYS_AHEAD))
+            .is_some_and(|max| until > max)
+        {
+            return Err(ListUpcomingOccurrencesError::TooFarAhead {
+                until,
+                max: UpcomingOccurrence::MAX_DAYS_AHEAD,
+            });
+        }
+        let schedules = self.fetch_schedules(None).await?;
+        let last_posted = self.fetch_last_posted_occurrences().await?;
+        let skipped = self.fetch_skipped_occurrences().await?;
+
+        let mut occurrences: Vec<UpcomingOccurrence> = schedules
+            .iter()
+            .flat_map(|schedule| {
+                schedule
+                    .occurrences(last_posted.get(&schedule.id()).copied(), until)
+                    .into_iter()
+                    .map(|date| {
+                        let occurrence = Occurrence {
+                            schedule_id: schedule.id(),
+                            date,
+                        };
+                        UpcomingOccurrence::new(schedule, date, skipped.contains(&occurrence))
+                    })
+            })
+            .collect();
+        occurrences.sort_by_key(|occurrence| (occurrence.date(), occurrence.schedule_id()));
+
+        Ok(occurrences)
+    }
+
+    /// Skip the occurrence of a [Schedule] on `date`, so that it is never posted.
+    ///
+    /// # Errors
+    ///
+    /// - [SkipOccurrenceError::NotFound] if no [Schedule] with the given id exists
+    /// - [SkipOccurrenceError::NotAnOccurrence] if the schedule does not occur on `date`
+    /// - [SkipOccurrenceError::AlreadyPosted] if the occurrence was already posted
+    /// - [SkipOccurrenceError::Unknown] in case any other error occurred
+    pub async fn skip_occurrence(
+        &self,
+        id: Uuid,
+        date: NaiveDate,
+    ) -> Result<UpcomingOccurrence, SkipOccurrenceError> {
+        let schedule = self.get_schedule(id).await.map_err(|e| match e {
+            GetScheduleError::NotFound { id } => SkipOccurrenceError::NotFound { id },
+            GetScheduleError::Unknown(e) => SkipOccurrenceError::Unknown(e),
+        })?;
+        if !schedule.occurrences(date.pred_opt(), date).contains(&date) {
+            return Err(SkipOccurrenceError::NotAnOccurrence { id, date });
+        }
+
+        let posted = sqlx::query_scalar!(
+            r#"SELECT EXISTS(SELECT 1 FROM entries WHERE schedule_id = $1 AND occurrence_date = $2) AS "exists!""#,
+            id,
+            date
+        )
+        .fetch_one(&self.pool)
+        .await
+        .context("failed to look up posted occurrence")?;
+        if posted {
+            return Err(SkipOccurrenceError::AlreadyPosted { id, date });
+        }
+
+        sqlx::query!(
+            "INSERT INTO skipped_occurrences (schedule_id, occurrence_date) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+            id,
+            date
+        )
+        .execute(&self.pool)
+        .await
+        .context("failed to skip occurrence")?;
+
+        tracing::info!(schedule_id = ?id, %date, "Skipped occurrence");
+        Ok(UpcomingOccurrence::new(&schedule, date, true))
+    }
+
+    /// Post a [Transaction] for each occurrence of the [Schedule]s due by `today` in
+    /// `timezone` that was neither posted nor skipped, returning the posted transactions.
+    ///
+    /// Occurrences that fail to post, like ones whose category was deleted, are logged and
+    /// tried again on the next run.
+    pub async fn post_due_occurrences(
+        &self,
+        timezone: Tz,
+    ) -> Result<Vec<Transaction>, PostDueOccurrencesError> {
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+        let schedules = self.fetch_schedules(None).await?;
+        let last_posted = self.fetch_last_posted_occurrences().await?;
+        let skipped = self.fetch_skipped_occurrences().await?;
 
-        Ok(transaction)
+        let mut transactions = Vec::new();
+        for schedule in &schedules {
+            let after = last_posted.get(&schedule.id()).copied();
+            for date in schedule.occurrences(after, today) {
+                let occurrence = Occurrence {
+                    schedule_id: schedule.id(),
+                    date,
+                };
+                if skipped.contains(&occurrence) {
+                    continue;
+                }
+
+                let req = schedule
+                    .transaction_request(date, timezone)
+                    .context("failed to compute the posting date of the occurrence")?;
+                match self.create_transaction(&req).await {
+                    Ok(transaction) => transactions.push(transaction),
+                    Err(CreateTransactionError::OccurrencePosted { .. }) => {}
+                    Err(e) => {
+                        tracing::error!(schedule_id = ?schedule.id(), %date, error = %e, "Failed to post occurrence");
+                        break;
+                    }
+                }
+            }
+        }
+
+        if !transactions.is_empty() {
+            tracing::info!(count = transactions.len(), "Posted scheduled transactions");
+        }
+        Ok(transactions)
     }
 
-    /// List all transactions in the database.
+    /// Project the balances of asset accounts over the next `days` days, flagging the first
+    /// day each one ends below `threshold`. Only the accounts in `account_ids` are projected,
+    /// or all asset accounts if it is empty.
     ///
-    /// It will return an empty [Vec] if there are no transactions in the database.
-    /// TODO: Add support for filters in the future.
-    pub async fn list_transactions(
+    /// Balances start from what they are at the end of today, and change with:
+    /// - transactions already posted with later dates
+    /// - occurrences of [Schedule]s that are not posted nor skipped
+    /// - statements of [CreditCard]s with a payment account, on their due dates, for what is
+    ///   left to pay of them
+    ///
+    /// # Errors
+    ///
+    /// - [GetCashFlowForecastError::InvalidDays] if `days` is 0 or more than
+    ///   [MAX_FORECAST_DAYS]
+    /// - [GetCashFlowForecastError::AccountNotFound] if any of `account_ids` does not exist
+    /// - [GetCashFlowForecastError::NotAnAssetAccount] if any of `account_ids` is not an asset
+    ///   account
+    /// - [GetCashFlowForecastError::Unknown] in case any other error occurred
+    pub async fn get_cash_flow_forecast(
         &self,
-        pagination: Option<PaginationParameters>,
-    ) -> Result<Vec<Transaction>, ListTransactionsError> {
-        let mut query = String::from(
-            r##"SELECT
-                id, title, amount, source_account_id, destination_account_id, category, posting_date
-               FROM "postings"
-               ORDER BY posting_date DESC"##,
-        );
+        days: u32,
+        threshold: Decimal,
+        account_ids: &[Uuid],
+        timezone: Tz,
+    ) -> Result<CashFlowForecast, GetCashFlowForecastError> {
+        if days == 0 || days > MAX_FORECAST_DAYS {
+            return Err(GetCashFlowForecastError::InvalidDays {
+                days,
+                max: MAX_FORECAST_DAYS,
+            });
+        }
+
+        let accounts = self
+            .list_accounts(None, None, None)
+            .await
+            .map_err(|e| match e {
+                ListAccountsError::Unknown(e) => GetCashFlowForecastError::Unknown(e),
+            })?
+            .items;
+        for id in account_ids {
+            let account = accounts
+                .iter()
+                .find(|account| account.id() == *id)
+                .ok_or(GetCashFlowForecastError::AccountNotFound { id: *id })?;
+            if account.account_type() != AccountType::Asset {
+                return Err(GetCashFlowForecastError::NotAnAssetAccount { id: *id });
+            }
+        }
+        let forecast_accounts: Vec<&Account> = accounts
+            .iter()
+            .filter(|account| account.account_type() == AccountType::Asset)
+            .filter(|account| account_ids.is_empty() || account_ids.contains(&account.id()))
+            .collect();
 
-        let mut args = sqlx::query(&query);
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+        let from = today + chrono::Days::new(1);
+        let until = today + chrono::Days::new(u64::from(days));
 
-        if let Some(pagination) = pagination {
-            query.push_str(" LIMIT $1 OFFSET $2");
-            args = sqlx::query(&query)
-                .bind(pagination.limit)
-                .bind(pagination.offset)
+        let mut changes: HashMap<Uuid, Vec<ForecastChange>> = HashMap::new();
+        let mut later_postings: HashMap<Uuid, Decimal> = HashMap::new();
+        let rows = sqlx::query!(
+            r#"
+SELECT
+  l.account_id,
+  e.id AS entry_id,
+  e.title,
+  (e.posting_date AT TIME ZONE $1)::date AS "date!",
+  SUM(l.amount) AS "amount!"
+FROM legs l
+JOIN entries e ON e.id = l.entry_id
+JOIN accounts a ON a.id = l.account_id
+WHERE a.account_type = 'asset' AND (e.posting_date AT TIME ZONE $1)::date > $2
+GROUP BY l.account_id, e.id
+"#,
+            timezone.name(),
+            today
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch transactions posted with later dates")?;
+        for row in rows {
+            *later_postings.entry(row.account_id).or_default() += row.amount;
+            if row.date <= until {
+                changes
+                    .entry(row.account_id)
+                    .or_default()
+                    .push(ForecastChange::new(
+                        row.date,
+                        ForecastSource::Transaction {
+                            transaction_id: row.entry_id,
+                        },
+                        row.title,
+                        row.amount,
+                    ));
+            }
         }
 
-        let rows = args
-            .fetch_all(&self.pool)
+        let occurrences = self
+            .list_upcoming_occurrences(until, timezone)
             .await
-            .map_err(|e| ListTransactionsError::Unknown(e.into()))?;
+            .map_err(|e| match e {
+                ListUpcomingOccurrencesError::Unknown(e) => GetCashFlowForecastError::Unknown(e),
+                e @ ListUpcomingOccurrencesError::TooFarAhead { .. } => {
+                    GetCashFlowForecastError::Unknown(e.into())
+                }
+            })?;
+        for occurrence in occurrences.iter().filter(|o| !o.skipped()) {
+            let transfer = occurrence.transfer();
+            let source = ForecastSource::Schedule {
+                schedule_id: occurrence.schedule_id(),
+                date: occurrence.date(),
+            };
+            for (account_id, amount) in [
+                (transfer.source_account_id, -transfer.amount),
+                (transfer.destination_account_id, transfer.amount),
+            ] {
+                changes
+                    .entry(account_id)
+                    .or_default()
+                    .push(ForecastChange::new(
+                        occurrence.date(),
+                        source.clone(),
+                        transfer.title.to_string(),
+                        amount,
+                    ));
+            }
+        }
 
-        tracing::debug!(row_count = rows.len(), "Got rows from the database");
+        for card in self.fetch_credit_cards(None).await? {
+            let Some(payment_account_id) = card.payment_account_id() else {
+                continue;
+            };
+            let card_name = accounts
+                .iter()
+                .find(|account| account.id() == card.account_id())
+                .map(|account| account.name().to_string())
+                .unwrap_or_default();
+            // Scheduled payments and charges count toward the statements they fall in, so paying
+            // the card with a schedule does not take the money out twice
+            let mut postings = self
+                .fetch_card_postings(card.account_id(), timezone)
+                .await?;
+            for occurrence in occurrences.iter().filter(|o| !o.skipped()) {
+                let transfer = occurrence.transfer();
+                if transfer.source_account_id == card.account_id() {
+                    postings.push((occurrence.date(), transfer.amount));
+                } else if transfer.destination_account_id == card.account_id() {
+                    postings.push((occurrence.date(), -transfer.amount));
+                }
+            }
 
-        rows.iter()
-            .map(|r| {
-                let title: String = r.try_get::<String, &str>("title")?;
-                let transaction_title = TransactionTitle::new(&title).map_err(|e| {
-                    tracing::error!(error = ?e, "Failed to fetch transactions from the database");
-                    ListTransactionsError::Unknown(e.into())
-                })?;
-                let id = r.try_get::<Uuid, &str>("id")?;
-                let amount = r.try_get::<Decimal, &str>("amount")?;
-                let source_account_id = r.try_get::<Uuid, &str>("source_account_id")?;
-                let destination_account_id = r.try_get::<Uuid, &str>("destination_account_id")?;
-                let category = r.try_get::<Option<String>, &str>("category")?;
-                let posting_date = r.try_get::<DateTime<Utc>, &str>("posting_date")?;
+            // Statements closed up to two months ago may still be due
+            let mut month = card.statement_month(today).previous().previous();
+            while month <= card.statement_month(until) {
+                let statement = Statement::new(&card, month, &postings, today);
+                let due = statement.amount_due(&postings, statement.due_date());
+                if statement.due_date() > today
+                    && statement.due_date() <= until
+                    && due > Decimal::ZERO
+                {
+                    changes
+                        .entry(payment_account_id)
+                        .or_default()
+                        .push(ForecastChange::new(
+                            statement.due_date(),
+                            ForecastSource::CardStatement {
+                                account_id: card.account_id(),
+                                month,
+                            },
+                            format!("{card_name} statement {month}"),
+                            -due,
+                        ));
+                    // Later statements carry what is left unpaid, which this pays
+                    postings.push((statement.due_date(), -due));
+                }
+                month = month.next();
+            }
+        }
 
-                let transaction = Transaction::new(
-                    id,
-                    transaction_title,
-                    amount,
-                    source_account_id,
-                    destination_account_id,
-                    category,
-                    posting_date,
-                );
-                tracing::info!(?id, "Successfully retrieved transaction");
+        let account_forecasts = forecast_accounts
+            .into_iter()
+            .map(|account| {
+                let later = later_postings
+                    .get(&account.id())
+                    .copied()
+                    .unwrap_or_default();
+                AccountForecast::new(
+                    account.id(),
+                    account.name().clone(),
+                    account.balance() - later,
+                    from,
+                    until,
+                    changes.remove(&account.id()).unwrap_or_default(),
+                    threshold,
+                )
+            })
+            .collect();
 
-                Ok(transaction)
+        Ok(CashFlowForecast::new(
+            from,
+            until,
+            threshold,
+            account_forecasts,
+        ))
+    }
+
+    /// Fetch an [ImportBatch] by its id, if it exists
+    async fn fetch_import_batch(&self, id: Uuid) -> Result<Option<ImportBatch>, anyhow::Error> {
+        let row = sqlx::query!(
+            r#"
+SELECT b.*, (SELECT COUNT(*) FROM entries e WHERE e.import_batch_id = b.id) AS "transaction_count!"
+FROM import_batches b
+WHERE b.id = $1
+"#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            import_batch_from_row(
+                row.id,
+                row.source,
+                &row.format,
+                row.profile,
+                row.account_id,
+                row.row_count,
+                row.transaction_count,
+                row.created_at,
+            )
+        })
+        .transpose()
+    }
+
+    /// List all [ImportBatch]es, the most recent first
+    pub async fn list_import_batches(&self) -> Result<Vec<ImportBatch>, ListImportBatchesError> {
+        let rows = sqlx::query!(
+            r#"
+SELECT b.*, (SELECT COUNT(*) FROM entries e WHERE e.import_batch_id = b.id) AS "transaction_count!"
+FROM import_batches b
+ORDER BY b.created_at DESC, b.id
+"#
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to list import batches")?;
+
+        let batches = rows
+            .into_iter()
+            .map(|row| {
+                import_batch_from_row(
+                    row.id,
+                    row.source,
+                    &row.format,
+                    row.profile,
+                    row.account_id,
+                    row.row_count,
+                    row.transaction_count,
+                    row.created_at,
+                )
             })
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(batches)
+    }
+
+    /// Roll back an [ImportBatch], deleting the transactions it created and the batch itself.
+    ///
+    /// Like deleting each transaction, this subtracts the amounts of their [Leg]s from the
+    /// balances of their accounts. Accounts and categories created by the import are kept.
+    /// Returns how many transactions were deleted.
+    ///
+    /// # Errors
+    ///
+    /// - [RollbackImportBatchError::NotFound] if no [ImportBatch] with the given id exists
+    /// - [RollbackImportBatchError::Unknown] if any other kind of error occurred
+    pub async fn rollback_import_batch(&self, id: Uuid) -> Result<u64, RollbackImportBatchError> {
+        let mut tx = self.start_psql_transaction().await?;
+
+        sqlx::query!("SELECT id FROM import_batches WHERE id = $1 FOR UPDATE", id)
+            .fetch_optional(&mut *tx)
+            .await
+            .context("failed to look up import batch")?
+            .ok_or(RollbackImportBatchError::NotFound { id })?;
+
+        let balances = sqlx::query!(
+            r#"
+SELECT l.account_id, SUM(l.amount) AS "amount!"
+FROM legs l
+JOIN entries e ON e.id = l.entry_id
+WHERE e.import_batch_id = $1
+GROUP BY l.account_id
+"#,
+            id
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .context("failed to sum up the legs of the import batch")?;
+
+        let deleted = sqlx::query!("DELETE FROM entries WHERE import_batch_id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .context("failed to delete the transactions of the import batch")?
+            .rows_affected();
+
+        for balance in balances {
+            self.add_balance_to_account(&mut tx, balance.account_id, -balance.amount)
+                .await
+                .context("failed to reset account balance")?;
+        }
+
+        sqlx::query!("DELETE FROM import_batches WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .context("failed to delete import batch")?;
+
+        tx.commit()
+            .await
+            .context("failed to commit PostgreSQL transaction")?;
+
+        tracing::info!(?id, deleted, "Successfully rolled back import batch");
+
+        Ok(deleted)
+    }
+
+    /// Create a [Price].
+    ///
+    /// # Errors
+    ///
+    /// - [CreatePriceError::Invalid] if the commodity is priced in itself or the rate is not
+    ///   positive
+    /// - [CreatePriceError::Duplicate] if the commodity already has a price in the currency on
+    ///   the same date
+    /// - [CreatePriceError::Unknown] in case any other error occurred
+    pub async fn create_price(&self, req: &CreatePriceRequest) -> Result<Price, CreatePriceError> {
+        req.exchange_rate()?;
+
+        let id = Uuid::new_v4();
+        let (commodity, currency) = (req.commodity(), req.currency());
+        sqlx::query!(
+            "INSERT INTO prices (id, commodity, currency, date, rate) VALUES ($1, $2, $3, $4, $5)",
+            id,
+            commodity.code(),
+            currency.code(),
+            req.date(),
+            req.rate()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_constraint_violation(&e) {
+                CreatePriceError::Duplicate {
+                    commodity,
+                    currency,
+                    date: req.date(),
+                }
+            } else {
+                anyhow!(e).context("failed to create price").into()
+            }
+        })?;
+
+        tracing::info!(price_id = ?id, "Successfully created price");
+        Ok(Price::new(id, commodity, currency, req.date(), req.rate()))
+    }
+
+    /// Replace all the fields of a [Price].
+    ///
+    /// # Errors
+    ///
+    /// - [UpdatePriceError::NotFound] if no [Price] with the given id exists
+    /// - [UpdatePriceError::Invalid] if the commodity is priced in itself or the rate is not
+    ///   positive
+    /// - [UpdatePriceError::Duplicate] if the commodity already has another price in the
+    ///   currency on the same date
+    /// - [UpdatePriceError::Unknown] in case any other error occurred
+    pub async fn update_price(
+        &self,
+        id: Uuid,
+        req: &CreatePriceRequest,
+    ) -> Result<Price, UpdatePriceError> {
+        req.exchange_rate()?;
+
+        let (commodity, currency) = (req.commodity(), req.currency());
+        let result = sqlx::query!(
+            "UPDATE prices SET commodity = $1, currency = $2, date = $3, rate = $4 WHERE id = $5",
+            commodity.code(),
+            currency.code(),
+            req.date(),
+            req.rate(),
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if is_unique_constraint_violation(&e) {
+                UpdatePriceError::Duplicate {
+                    commodity,
+                    currency,
+                    date: req.date(),
+                }
+            } else {
+                anyhow!(e).context("failed to update price").into()
+            }
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(UpdatePriceError::NotFound { id });
+        }
+
+        tracing::info!(price_id = ?id, "Successfully updated price");
+        Ok(Price::new(id, commodity, currency, req.date(), req.rate()))
+    }
+
+    /// Get a [Price] by its id.
+    ///
+    /// # Errors
+    ///
+    /// - [GetPriceError::NotFound] if no [Price] with the given id exists
+    /// - [GetPriceError::Unknown] in case any other error occurred
+    pub async fn get_price(&self, id: Uuid) -> Result<Price, GetPriceError> {
+        self.fetch_prices(Some(id), None, None)
+            .await?
+            .pop()
+            .ok_or(GetPriceError::NotFound { id })
+    }
+
+    /// List the [Price]s of `commodity` in `currency`, or of any commodity or currency when
+    /// they are not given, by commodity, currency and date
+    pub async fn list_prices(
+        &self,
+        commodity: Option<Currency>,
+        currency: Option<Currency>,
+    ) -> Result<Vec<Price>, ListPricesError> {
+        Ok(self.fetch_prices(None, commodity, currency).await?)
+    }
+
+    /// Fetch the [Price]s matching all the given filters, by commodity, currency and date
+    async fn fetch_prices(
+        &self,
+        id: Option<Uuid>,
+        commodity: Option<Currency>,
+        currency: Option<Currency>,
+    ) -> anyhow::Result<Vec<Price>> {
+        let rows = sqlx::query!(
+            "
+SELECT id, commodity, currency, date, rate
+FROM prices
+WHERE ($1::uuid IS NULL OR id = $1)
+  AND ($2::text IS NULL OR commodity = $2)
+  AND ($3::text IS NULL OR currency = $3)
+ORDER BY commodity, currency, date
+",
+            id,
+            commodity.as_ref().map(Currency::code),
+            currency.as_ref().map(Currency::code)
+        )
+        .fetch_all(&self.pool)
+        .await
+        .context("failed to fetch prices")?;
+
+        rows.into_iter()
+            .map(|row| price_from_row(row.id, &row.commodity, &row.currency, row.date, row.rate))
             .collect()
     }
+
+    /// Delete a [Price].
+    ///
+    /// # Errors
+    ///
+    /// - [DeletePriceError::NotFound] if no [Price] with the given id exists
+    /// - [DeletePriceError::Unknown] in case any other error occurred
+    pub async fn delete_price(&self, id: Uuid) -> Result<(), DeletePriceError> {
+        let result = sqlx::query!("DELETE FROM prices WHERE id = $1", id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| DeletePriceError::Unknown(e.into()))?;
+
+        if result.rows_affected() == 0 {
+            Err(DeletePriceError::NotFound { id })
+        } else {
+            tracing::info!(?id, "Successfully deleted price");
+            Ok(())
+        }
+    }
+
+    /// Import a CSV file of [Price]s, as read by [read_prices]. A price of a commodity in a
+    /// currency on a date that already has one replaces its rate, so files can be imported
+    /// again safely. The valid rows are all imported or none is.
+    ///
+    /// # Errors
+    ///
+    /// - [ImportPricesError::Malformed] if the file is not valid CSV
+    /// - [ImportPricesError::MissingColumn] if the file misses one of the columns
+    /// - [ImportPricesError::Unknown] in case any other error occurred
+    pub async fn import_prices(
+        &self,
+        contents: &[u8],
+    ) -> Result<PriceImportReport, ImportPricesError> {
+        let rows = read_prices(contents)?;
+
+        let mut tx = self.start_psql_transaction().await?;
+        let (mut created, mut updated) = (0, 0);
+        let mut failed = Vec::new();
+        for row in rows {
+            let req = match row {
+                Ok(req) => req,
+                Err(row) => {
+                    failed.push(row);
+                    continue;
+                }
+            };
+
+            let id = Uuid::new_v4();
+            let (commodity, currency) = (req.commodity(), req.currency());
+            let row = sqlx::query!(
+                r#"
+INSERT INTO prices (id, commodity, currency, date, rate)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (commodity, currency, date) DO UPDATE SET rate = EXCLUDED.rate
+RETURNING id = $1 AS "created!"
+"#,
+                id,
+                commodity.code(),
+                currency.code(),
+                req.date(),
+                req.rate()
+            )
+            .fetch_one(&mut *tx)
+            .await
+            .context("failed to save price")?;
+
+            if row.created {
+                created += 1;
+            } else {
+                updated += 1;
+            }
+        }
+
+        tx.commit()
+            .await
+            .context("failed to commit PostgreSQL transaction")?;
+
+        tracing::info!(
+            created,
+            updated,
+            failed = failed.len(),
+            "Successfully imported prices"
+        );
+        Ok(PriceImportReport::new(created, updated, failed))
+    }
+
+    /// Find the rate of `commodity` in `currency` on `date`, from the latest [Price] of one in
+    /// the other on or before that date. A price of `commodity` in `currency` wins over one of
+    /// `currency` in `commodity` on the same date.
+    ///
+    /// # Errors
+    ///
+    /// - [LookupPriceError::NotFound] if there is no such price
+    /// - [LookupPriceError::Unknown] in case any other error occurred
+    pub async fn lookup_price(
+        &self,
+        commodity: Currency,
+        currency: Currency,
+        date: NaiveDate,
+    ) -> Result<PriceQuote, LookupPriceError> {
+        let row = sqlx::query!(
+            "
+SELECT id, commodity, currency, date, rate
+FROM prices
+WHERE ((commodity = $1 AND currency = $2) OR (commodity = $2 AND currency = $1))
+  AND date <= $3
+ORDER BY date DESC, commodity = $1 DESC
+LIMIT 1
+",
+            commodity.code(),
+            currency.code(),
+            date
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .context("failed to look up price")?
+        .ok_or(LookupPriceError::NotFound {
+            commodity,
+            currency,
+            date,
+        })?;
+
+        let price = price_from_row(row.id, &row.commodity, &row.currency, row.date, row.rate)?;
+        Ok(PriceQuote::new(commodity, currency, date, price))
+    }
+}
+
+/// Append a condition to the query for each filter that is set. The query must already select
+/// from [FILTERED_ENTRIES].
+fn push_transaction_filters(query: &mut QueryBuilder<'_, Postgres>, filters: &TransactionFilters) {
+    if let Some(account_id) = filters.account_id {
+        query.push(
+            r#" AND EXISTS (SELECT 1 FROM "legs" f WHERE f.entry_id = e.id AND f.account_id = "#,
+        );
+        query.push_bind(account_id);
+        query.push(")");
+    }
+    if let Some(from) = filters.from {
+        query.push(" AND e.posting_date >= ");
+        query.push_bind(from);
+    }
+    if let Some(to) = filters.to {
+        query.push(" AND e.posting_date < ");
+        query.push_bind(to);
+    }
+    if let Some(min_amount) = filters.min_amount {
+        query.push(" AND a.amount >= ");
+        query.push_bind(min_amount);
+    }
+    if let Some(max_amount) = filters.max_amount {
+        query.push(" AND a.amount <= ");
+        query.push_bind(max_amount);
+    }
+    if let Some(category) = &filters.category {
+        query.push(" AND (e.category = ");
+        query.push_bind(category.to_string());
+        query.push(" OR starts_with(e.category, ");
+        query.push_bind(format!("{category}{}", CategoryName::SEPARATOR));
+        query.push("))");
+    }
+    if let Some(title) = &filters.title {
+        let escaped = title
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        query.push(" AND e.title ILIKE ");
+        query.push_bind(format!("%{escaped}%"));
+    }
+}
+
+/// Build a [Budget] from the columns of its database row
+fn budget_from_row(
+    id: Uuid,
+    month: NaiveDate,
+    category_id: Option<Uuid>,
+    account_id: Option<Uuid>,
+    amount: Decimal,
+    rollover: bool,
+) -> anyhow::Result<Budget> {
+    let target = match (category_id, account_id) {
+        (Some(id), None) => BudgetTarget::Category(id),
+        (None, Some(id)) => BudgetTarget::Account(id),
+        _ => {
+            return Err(anyhow!(
+                "budget {id} must have either a category or an account"
+            ));
+        }
+    };
+
+    Ok(Budget::new(id, Month::of(month), target, amount, rollover))
+}
+
+/// Build the [Installment] of a transaction from its columns, if it belongs to a plan
+fn installment_from_row(plan_id: Option<Uuid>, number: Option<i32>) -> Option<Installment> {
+    Some(Installment {
+        plan_id: plan_id?,
+        number: number? as u32,
+    })
+}
+
+/// Build the [ExchangeRate] of a transaction from its columns, if it has one
+fn exchange_rate_from_row(
+    base: Option<&str>,
+    quote: Option<&str>,
+    rate: Option<Decimal>,
+) -> anyhow::Result<Option<ExchangeRate>> {
+    let (Some(base), Some(quote), Some(rate)) = (base, quote, rate) else {
+        return Ok(None);
+    };
+
+    let rate = ExchangeRate::new(
+        Currency::new(base).context("failed to parse exchange rate base")?,
+        Currency::new(quote).context("failed to parse exchange rate quote")?,
+        rate,
+    )
+    .context("failed to build exchange rate")?;
+
+    Ok(Some(rate))
+}
+
+/// Build a [Price] from the columns of its row
+fn price_from_row(
+    id: Uuid,
+    commodity: &str,
+    currency: &str,
+    date: NaiveDate,
+    rate: Decimal,
+) -> anyhow::Result<Price> {
+    let commodity = Currency::new(commodity)
+        .with_context(|| format!("failed to parse commodity of price {id}"))?;
+    let currency = Currency::new(currency)
+        .with_context(|| format!("failed to parse currency of price {id}"))?;
+
+    Ok(Price::new(id, commodity, currency, date, rate))
+}
+
+/// Parse the category column of a transaction
+fn parse_category(raw: Option<&str>) -> anyhow::Result<Option<CategoryName>> {
+    raw.map(|raw| {
+        CategoryName::new(raw).with_context(|| format!("failed to parse category {raw:?}"))
+    })
+    .transpose()
+}
+
+/// Build a [Category] from the columns of its database row
+fn category_from_row(id: Uuid, name: &str, parent_id: Option<Uuid>) -> anyhow::Result<Category> {
+    let name = CategoryName::new(name)
+        .with_context(|| format!("failed to create category name from {}", name))?;
+
+    Ok(Category::new(id, name, parent_id))
+}
+
+/// Build an [Account] from the columns of its database row, whose balance is stored as a ledger
+/// balance (debits minus credits).
+fn account_from_row(
+    id: Uuid,
+    name: &str,
+    account_type: &str,
+    parent_id: Option<Uuid>,
+    currency: &str,
+    ledger_balance: Decimal,
+) -> anyhow::Result<Account> {
+    let account_name = AccountName::new(name)
+        .with_context(|| format!("failed to create account name from {}", name))?;
+    let account_type = AccountType::from_str(account_type)
+        .with_context(|| format!("failed to parse account type of account {}", id))?;
+    let currency = Currency::new(currency)
+        .with_context(|| format!("failed to parse currency of account {}", id))?;
+
+    Ok(
+        Account::from_ledger_balance(id, account_name, account_type, parent_id, ledger_balance)
+            .with_currency(currency),
+    )
+}
+
+/// What importing a row would do, on a dry run: a row is a duplicate if its fingerprint is in
+/// `existing`, and it fails if its title or category is not valid
+fn preview_row(row: &ImportRow, existing: &HashSet<String>) -> ImportRowStatus {
+    if existing.contains(row.fingerprint()) {
+        return ImportRowStatus::Duplicate {
+            fingerprint: row.fingerprint().to_string(),
+        };
+    }
+
+    let valid = TransactionTitle::new(row.title())
+        .map(drop)
+        .map_err(|e| e.to_string())
+        .and_then(|_| row.category_name().map(drop).map_err(|e| e.to_string()));
+
+    match valid {
+        Ok(()) => ImportRowStatus::Created {
+            transaction_id: None,
+        },
+        Err(error) => ImportRowStatus::Failed { error },
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn import_batch_from_row(
+    id: Uuid,
+    source: String,
+    format: &str,
+    profile: Option<String>,
+    account_id: Uuid,
+    row_count: i32,
+    transaction_count: i64,
+    created_at: DateTime<Utc>,
+) -> anyhow::Result<ImportBatch> {
+    let format = ImportFormat::from_str(format)
+        .with_context(|| format!("failed to parse format of import batch {id}"))?;
+
+    Ok(ImportBatch::new(
+        id,
+        source,
+        format,
+        profile,
+        account_id,
+        row_count.into(),
+        transaction_count,
+        created_at,
+    ))
 }
 
 const UNIQUE_CONSTRAINT_VIOLATION_CODE: &str = "23505";
@@ -569,10 +4234,37 @@ const UNIQUE_CONSTRAINT_VIOLATION_CODE: &str = "23505";
 ///
 /// This means that the record had a duplicate.
 fn is_unique_constraint_violation(err: &sqlx::Error) -> bool {
-    if let sqlx::Error::Database(db_err) = err {
-        if let Some(code) = db_err.code() {
-            return code == UNIQUE_CONSTRAINT_VIOLATION_CODE;
-        }
+    if let sqlx::Error::Database(db_err) = err
+        && let Some(code) = db_err.code()
+    {
+        return code == UNIQUE_CONSTRAINT_VIOLATION_CODE;
+    }
+
+    false
+}
+
+/// Check if an error happened due to a duplicate in the unique index or constraint named `name`,
+/// telling apart the unique indexes of a table.
+fn violates_unique_index(err: &sqlx::Error, name: &str) -> bool {
+    if let sqlx::Error::Database(db_err) = err
+        && db_err.constraint() == Some(name)
+    {
+        return is_unique_constraint_violation(err);
+    }
+
+    false
+}
+
+const FOREIGN_KEY_VIOLATION_CODE: &str = "23503";
+
+/// Check if an error happened due to a foreign key constraint violation.
+///
+/// This means that the record is still referenced by another one.
+fn is_foreign_key_violation(err: &sqlx::Error) -> bool {
+    if let sqlx::Error::Database(db_err) = err
+        && let Some(code) = db_err.code()
+    {
+        return code == FOREIGN_KEY_VIOLATION_CODE;
     }
 
     false
@@ -582,3 +4274,11 @@ fn is_unique_constraint_violation(err: &sqlx::Error) -> bool {
 pub fn get_connection_pool(configuration: &DatabaseSettings) -> PgPool {
     PgPoolOptions::new().connect_lazy_with(configuration.connect_options())
 }
+
+/// Key monthly movements by [Month] rather than by the first day of the month
+fn by_month(movements: HashMap<(Uuid, NaiveDate), Decimal>) -> HashMap<(Uuid, Month), Decimal> {
+    movements
+        .into_iter()
+        .map(|((account_id, period), amount)| ((account_id, Month::of(period)), amount))
+        .collect()
+}
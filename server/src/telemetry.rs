@@ -20,8 +20,8 @@ where
 {
     let env_filter =
         EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(env_filter));
-    let _formatting_layer = BunyanFormattingLayer::new(name, sink);
-    let formatting_layer = tracing_subscriber::fmt::layer();
+    let _formatting_layer = BunyanFormattingLayer::new(name, std::io::sink);
+    let formatting_layer = tracing_subscriber::fmt::layer().with_writer(sink);
     Registry::default()
         .with(env_filter)
         .with(JsonStorageLayer)
@@ -0,0 +1,144 @@
+use berry::models::account::{Account, AccountName};
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::helpers::spawn_app;
+
+#[tokio::test]
+async fn creating_a_nested_account_creates_its_parents() {
+    let app = spawn_app().await;
+
+    let response = app
+        .post_account("name=Assets%3ABank%3ANubank".to_string())
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status().as_u16());
+    let nubank: Account = response.json().await.unwrap();
+
+    let bank: Account = app
+        .find_account_by_name("assets%3Abank".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    let assets: Account = app
+        .find_account_by_name("assets".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(Some(bank.id()), nubank.parent_id());
+    assert_eq!(Some(assets.id()), bank.parent_id());
+    assert_eq!(None, assets.parent_id());
+}
+
+#[tokio::test]
+async fn account_tree_rolls_up_subtotals() {
+    let app = spawn_app().await;
+    let nubank: Account = app
+        .post_account("name=assets%3Abank%3Anubank".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    let itau: Account = app
+        .post_account("name=assets%3Abank%3Aitau".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let body = format!(
+        "title=Transfer&amount=42&source_account_id={}&destination_account_id={}",
+        app.test_account.id,
+        nubank.id()
+    );
+    app.post_transaction(body).await;
+    let body = format!(
+        "title=Transfer&amount=8&source_account_id={}&destination_account_id={}",
+        app.test_account.id,
+        itau.id()
+    );
+    app.post_transaction(body).await;
+
+    let response = app.get_account_tree().await;
+    assert_eq!(StatusCode::OK, response.status().as_u16());
+
+    let tree: Vec<Value> = response.json().await.unwrap();
+    let assets = tree
+        .iter()
+        .find(|node| node["name"] == "assets")
+        .expect("assets should be a root account");
+    let bank = &assets["children"][0];
+
+    assert_eq!("50", assets["subtotal"]);
+    assert_eq!("assets:bank", bank["name"]);
+    assert_eq!("50", bank["subtotal"]);
+    assert_eq!(2, bank["children"].as_array().unwrap().len());
+}
+
+#[tokio::test]
+async fn renaming_an_account_renames_its_children() {
+    let app = spawn_app().await;
+    app.post_account("name=assets%3Abank%3Anubank".to_string())
+        .await;
+    let bank: Account = app
+        .find_account_by_name("assets%3Abank".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let response = app
+        .rename_account(bank.id().to_string(), "name=assets%3Abanks".to_string())
+        .await;
+    assert_eq!(StatusCode::OK, response.status().as_u16());
+
+    let nubank: Account = app
+        .find_account_by_name("assets%3Abanks%3Anubank".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(Some(bank.id()), nubank.parent_id());
+    assert_eq!(
+        &AccountName::new("assets:banks:nubank").unwrap(),
+        nubank.name()
+    );
+}
+
+#[tokio::test]
+async fn accounts_cannot_be_moved_under_themselves() {
+    let app = spawn_app().await;
+    let bank: Account = app
+        .post_account("name=assets%3Abank".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let response = app
+        .rename_account(
+            bank.id().to_string(),
+            "name=assets%3Abank%3Anubank".to_string(),
+        )
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn accounts_with_children_cannot_be_deleted() {
+    let app = spawn_app().await;
+    app.post_account("name=assets%3Abank".to_string()).await;
+    let assets: Account = app
+        .find_account_by_name("assets".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let response = app.delete_account(assets.id().to_string()).await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status().as_u16());
+}
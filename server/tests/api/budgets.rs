@@ -0,0 +1,206 @@
+use berry::models::budget::{Budget, BudgetLine};
+use reqwest::StatusCode;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{
+    TestApp, create_account_in_app, create_category_in_app, create_expense_account, post_transfer,
+    spawn_app,
+};
+
+async fn report(app: &TestApp, month: &str) -> Vec<BudgetLine> {
+    let response = app.get_budget_report(month).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn budget_report_rolls_remaining_amounts_over_into_the_next_months() {
+    let app = spawn_app().await;
+    let bank = app.test_account().id;
+    let groceries = create_expense_account(&app, "expenses:groceries").await;
+    let response = app
+        .post_budget(&json!({
+            "month": "2026-01",
+            "account_id": groceries.id().to_string(),
+            "amount": "100",
+            "rollover": true,
+        }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    post_transfer(
+        &app,
+        "Spending",
+        "30",
+        bank,
+        groceries.id(),
+        None,
+        "2026-01-15",
+    )
+    .await;
+    post_transfer(
+        &app,
+        "Spending",
+        "90",
+        bank,
+        groceries.id(),
+        None,
+        "2026-02-03",
+    )
+    .await;
+
+    let january = report(&app, "2026-01").await;
+    assert_eq!(1, january.len());
+    assert_eq!(dec!(30), january[0].spent());
+    assert_eq!(dec!(70), january[0].remaining());
+
+    let february = report(&app, "2026-02").await;
+    assert_eq!(1, february.len());
+    assert_eq!(None, february[0].budget_id());
+    assert_eq!(dec!(70), february[0].carried_over());
+    assert_eq!(dec!(-20), february[0].remaining());
+}
+
+#[tokio::test]
+async fn category_budgets_count_spending_in_subcategories() {
+    let app = spawn_app().await;
+    let bank = app.test_account().id;
+    let food = create_category_in_app(&app, "food").await;
+    create_category_in_app(&app, "food:restaurants").await;
+    create_category_in_app(&app, "travel").await;
+    let restaurants = create_expense_account(&app, "expenses:restaurants").await;
+    let response = app
+        .post_budget(&json!({
+            "month": "2026-03",
+            "category_id": food.id().to_string(),
+            "amount": "200",
+        }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    post_transfer(
+        &app,
+        "Spending",
+        "25",
+        bank,
+        restaurants.id(),
+        Some("food"),
+        "2026-03-01",
+    )
+    .await;
+    post_transfer(
+        &app,
+        "Spending",
+        "50",
+        bank,
+        restaurants.id(),
+        Some("food:restaurants"),
+        "2026-03-20",
+    )
+    .await;
+    post_transfer(
+        &app,
+        "Spending",
+        "80",
+        bank,
+        restaurants.id(),
+        Some("travel"),
+        "2026-03-21",
+    )
+    .await;
+    post_transfer(
+        &app,
+        "Spending",
+        "10",
+        bank,
+        restaurants.id(),
+        Some("food"),
+        "2026-04-01",
+    )
+    .await;
+
+    let march = report(&app, "2026-03").await;
+
+    assert_eq!(1, march.len());
+    assert_eq!(dec!(75), march[0].spent());
+    assert_eq!(dec!(125), march[0].remaining());
+    assert!(report(&app, "2026-04").await.is_empty());
+}
+
+#[tokio::test]
+async fn creating_a_budget_validates_its_target_and_amount() {
+    let app = spawn_app().await;
+    let asset = create_account_in_app(&app).await;
+    let expense = create_expense_account(&app, "expenses:rent").await;
+
+    let cases = [
+        (
+            json!({"month": "2026-01", "account_id": asset.id().to_string(), "amount": "1"}),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            json!({"month": "2026-01", "amount": "1"}),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            json!({"month": "2026-01", "account_id": expense.id().to_string(), "amount": "-1"}),
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            json!({"month": "January", "account_id": expense.id().to_string(), "amount": "1"}),
+            StatusCode::BAD_REQUEST,
+        ),
+        (
+            json!({"month": "2026-01", "category_id": Uuid::new_v4().to_string(), "amount": "1"}),
+            StatusCode::NOT_FOUND,
+        ),
+    ];
+    for (body, status) in cases {
+        let response = app.post_budget(&body).await;
+        assert_eq!(status, response.status(), "{body}");
+    }
+
+    let body = json!({"month": "2026-01", "account_id": expense.id().to_string(), "amount": "1"});
+    assert_eq!(StatusCode::CREATED, app.post_budget(&body).await.status());
+    assert_eq!(
+        StatusCode::UNPROCESSABLE_ENTITY,
+        app.post_budget(&body).await.status()
+    );
+}
+
+#[tokio::test]
+async fn budgets_can_be_updated_and_deleted() {
+    let app = spawn_app().await;
+    let rent = create_expense_account(&app, "expenses:rent").await;
+    let budget: Budget = app
+        .post_budget(&json!({
+            "month": "2026-01",
+            "account_id": rent.id().to_string(),
+            "amount": "900",
+        }))
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let response = app
+        .update_budget(
+            budget.id().to_string(),
+            &json!({"amount": "950", "rollover": true}),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let updated: Budget = response.json().await.unwrap();
+    assert_eq!(dec!(950), updated.amount());
+    assert!(updated.rollover());
+
+    let response = app.delete_budget(budget.id().to_string()).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    assert!(report(&app, "2026-01").await.is_empty());
+
+    let response = app.delete_budget(budget.id().to_string()).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
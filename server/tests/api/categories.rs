@@ -0,0 +1,111 @@
+use berry::models::{category::Category, transaction::Transaction};
+use reqwest::StatusCode;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{create_account_in_app, create_category_in_app, spawn_app};
+
+#[tokio::test]
+async fn creating_a_category_creates_its_missing_parents() {
+    let app = spawn_app().await;
+
+    let restaurants = create_category_in_app(&app, "Food%20:%20Restaurants").await;
+    assert_eq!("food:restaurants", restaurants.name().to_string());
+
+    let categories: Vec<Category> = app.list_categories().await.json().await.unwrap();
+
+    assert_eq!(2, categories.len());
+    assert_eq!("food", categories[0].name().to_string());
+    assert_eq!(Some(categories[0].id()), restaurants.parent_id());
+}
+
+#[tokio::test]
+async fn creating_a_category_fails_for_invalid_or_duplicate_names() {
+    let app = spawn_app().await;
+    create_category_in_app(&app, "food").await;
+
+    let response = app.post_category("name=food".to_string()).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    for body in ["name=", "name=food::restaurants"] {
+        let response = app.post_category(body.to_string()).await;
+        assert_eq!(StatusCode::BAD_REQUEST, response.status(), "{body}");
+    }
+}
+
+#[tokio::test]
+async fn get_category_returns_404_for_unknown_categories() {
+    let app = spawn_app().await;
+    let food = create_category_in_app(&app, "food").await;
+
+    let response = app.get_category(food.id().to_string()).await;
+    assert_eq!(StatusCode::OK, response.status());
+    assert_eq!(food, response.json::<Category>().await.unwrap());
+
+    let response = app.get_category(Uuid::new_v4().to_string()).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn renaming_a_category_renames_its_subcategories_and_transactions() {
+    let app = spawn_app().await;
+    let food = create_category_in_app(&app, "food").await;
+    create_category_in_app(&app, "food:restaurants").await;
+    let destination = create_account_in_app(&app).await;
+    let transaction: Transaction = app
+        .post_transaction_json(&json!({
+            "title": "Dinner",
+            "amount": "42",
+            "source_account_id": app.test_account().id.to_string(),
+            "destination_account_id": destination.id().to_string(),
+            "category": "food:restaurants",
+        }))
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let response = app
+        .update_category(food.id().to_string(), "name=meals".to_string())
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let categories: Vec<Category> = app.list_categories().await.json().await.unwrap();
+    let names: Vec<String> = categories.iter().map(|c| c.name().to_string()).collect();
+    assert_eq!(vec!["meals", "meals:restaurants"], names);
+
+    let transaction: Transaction = app
+        .get_transaction(transaction.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        Some("meals:restaurants".to_string()),
+        transaction.category().as_ref().map(|c| c.to_string())
+    );
+
+    let response = app
+        .update_category(
+            food.id().to_string(),
+            "name=meals:restaurants:meals".to_string(),
+        )
+        .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn deleting_a_category_in_use_fails() {
+    let app = spawn_app().await;
+    let food = create_category_in_app(&app, "food").await;
+    let restaurants = create_category_in_app(&app, "food:restaurants").await;
+
+    let response = app.delete_category(food.id().to_string()).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    let response = app.delete_category(restaurants.id().to_string()).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let response = app.delete_category(restaurants.id().to_string()).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
@@ -1,6 +1,8 @@
 use std::str::from_utf8;
 
+use berry::models::account::{Account, AccountType};
 use reqwest::StatusCode;
+use rust_decimal_macros::dec;
 
 use crate::helpers::spawn_app;
 
@@ -44,3 +46,91 @@ async fn accounts_with_duplicate_names_are_invalid() {
 
     assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, status);
 }
+
+#[tokio::test]
+async fn accounts_default_to_the_asset_type() {
+    let app = spawn_app().await;
+
+    let response = app.post_account("name=Checking".to_string()).await;
+    assert_eq!(StatusCode::CREATED, response.status().as_u16());
+
+    let account: Account = response.json().await.unwrap();
+    assert_eq!(AccountType::Asset, account.account_type());
+}
+
+#[tokio::test]
+async fn create_account_with_a_type() {
+    let app = spawn_app().await;
+
+    let response = app
+        .post_account("name=Groceries&type=expense".to_string())
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status().as_u16());
+
+    let account: Account = response.json().await.unwrap();
+    assert_eq!(AccountType::Expense, account.account_type());
+}
+
+#[tokio::test]
+async fn accounts_can_be_created_from_json() {
+    let app = spawn_app().await;
+
+    let response = app
+        .post_account_json(&serde_json::json!({ "name": "expenses:food", "type": "expense" }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status().as_u16());
+
+    let account: Account = response.json().await.unwrap();
+    assert_eq!(AccountType::Expense, account.account_type());
+}
+
+#[tokio::test]
+async fn accounts_with_invalid_types_are_rejected() {
+    let app = spawn_app().await;
+
+    let response = app
+        .post_account("name=Savings&type=savings".to_string())
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn credit_normal_accounts_grow_with_credits() {
+    let app = spawn_app().await;
+    let card: Account = app
+        .post_account("name=Credit%20card&type=liability".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    let groceries: Account = app
+        .post_account("name=Groceries&type=expense".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let body = format!(
+        "title=Supermarket&amount=42&source_account_id={}&destination_account_id={}",
+        card.id(),
+        groceries.id()
+    );
+    app.post_transaction(body).await;
+
+    let card: Account = app
+        .get_account(card.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    let groceries: Account = app
+        .get_account(groceries.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(dec!(42), card.balance());
+    assert_eq!(dec!(42), groceries.balance());
+}
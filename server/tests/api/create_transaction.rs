@@ -1,8 +1,11 @@
-use berry::models::account::Account;
+use berry::models::{account::Account, transaction::Transaction};
+use berry::service::Page;
+use chrono::{NaiveDate, TimeZone as _, Utc};
 use reqwest::StatusCode;
 use rust_decimal_macros::dec;
+use serde_json::json;
 
-use crate::helpers::{spawn_app, TestAccount};
+use crate::helpers::{create_account_in_app, create_category_in_app, spawn_app, TestAccount};
 
 #[tokio::test]
 async fn accounts_must_have_their_balances_updated() {
@@ -163,6 +166,7 @@ async fn posting_date_is_allowed() {
 #[tokio::test]
 async fn category_is_allowed() {
     let app = spawn_app().await;
+    create_category_in_app(&app, "expenses").await;
     let source_account = app.test_account();
     let destination_account = TestAccount::generate();
     let destination_account = app
@@ -195,6 +199,21 @@ async fn category_is_allowed() {
     assert_eq!(StatusCode::CREATED, status);
 }
 
+#[tokio::test]
+async fn unknown_categories_are_rejected() {
+    let app = spawn_app().await;
+    let destination_account = create_account_in_app(&app).await;
+
+    let body = format!(
+        "title=Test%20transaction&amount=42&source_account_id={}&destination_account_id={}&category=typo",
+        app.test_account().id,
+        destination_account.id()
+    );
+    let response = app.post_transaction(body).await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status().as_u16());
+}
+
 #[tokio::test]
 async fn creating_multiple_transactions_correctly_updates_the_balances() {
     let app = spawn_app().await;
@@ -266,3 +285,184 @@ async fn creating_multiple_transactions_correctly_updates_the_balances() {
     let destination_account: Account = serde_json::from_slice(&destination_account).unwrap();
     assert_eq!(dec!(47), destination_account.balance());
 }
+
+#[tokio::test]
+async fn transactions_may_have_many_legs() {
+    let app = spawn_app().await;
+    let salary = create_account_in_app(&app).await;
+    let taxes = create_account_in_app(&app).await;
+    let pension = create_account_in_app(&app).await;
+    let checking = create_account_in_app(&app).await;
+
+    let body = json!({
+        "title": "Paycheck",
+        "legs": [
+            { "account_id": salary.id(), "amount": "-5000" },
+            { "account_id": taxes.id(), "amount": "1100" },
+            { "account_id": pension.id(), "amount": "400" },
+            { "account_id": checking.id(), "amount": "3500" },
+        ],
+    });
+
+    let response = app.post_transaction_json(&body).await;
+    assert_eq!(StatusCode::CREATED, response.status().as_u16());
+
+    let transaction: Transaction = response.json().await.unwrap();
+    assert_eq!(4, transaction.legs().len());
+    assert_eq!(dec!(5000), transaction.amount());
+
+    for (account, expected) in [
+        (salary, dec!(-5000)),
+        (taxes, dec!(1100)),
+        (pension, dec!(400)),
+        (checking, dec!(3500)),
+    ] {
+        let account: Account = app
+            .get_account(account.id().to_string())
+            .await
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(expected, account.balance());
+    }
+}
+
+#[tokio::test]
+async fn transaction_legs_must_sum_to_zero() {
+    let app = spawn_app().await;
+    let source_account = create_account_in_app(&app).await;
+    let destination_account = create_account_in_app(&app).await;
+
+    let body = json!({
+        "title": "Unbalanced",
+        "legs": [
+            { "account_id": source_account.id(), "amount": "-42" },
+            { "account_id": destination_account.id(), "amount": "41" },
+        ],
+    });
+
+    let response = app.post_transaction_json(&body).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status().as_u16());
+
+    let source_account: Account = app
+        .get_account(source_account.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dec!(0), source_account.balance());
+}
+
+#[tokio::test]
+async fn transactions_must_have_at_least_two_legs() {
+    let app = spawn_app().await;
+    let account = create_account_in_app(&app).await;
+
+    let body = json!({
+        "title": "Lonely leg",
+        "legs": [{ "account_id": account.id(), "amount": "0" }],
+    });
+
+    let response = app.post_transaction_json(&body).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status().as_u16());
+}
+
+#[tokio::test]
+async fn posting_date_is_persisted() {
+    let app = spawn_app().await;
+    let destination_account = create_account_in_app(&app).await;
+
+    let body = format!(
+        "title=Test%20transaction&amount=42&source_account_id={}&destination_account_id={}&posting_date=2023-05-17T09%3A30%3A00Z",
+        app.test_account.id,
+        destination_account.id()
+    );
+
+    let response = app.post_transaction(body).await;
+    assert_eq!(StatusCode::CREATED, response.status().as_u16());
+    let created: Transaction = response.json().await.unwrap();
+
+    let expected = Utc.with_ymd_and_hms(2023, 5, 17, 9, 30, 0).unwrap();
+    assert_eq!(expected, created.posting_date());
+
+    let stored: Transaction = app
+        .get_transaction(created.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(expected, stored.posting_date());
+}
+
+#[tokio::test]
+async fn posting_date_offsets_are_respected() {
+    let app = spawn_app().await;
+    let destination_account = create_account_in_app(&app).await;
+
+    let body = format!(
+        "title=Test%20transaction&amount=42&source_account_id={}&destination_account_id={}&posting_date=2023-05-17T22%3A00%3A00-03%3A00",
+        app.test_account.id,
+        destination_account.id()
+    );
+
+    let created: Transaction = app.post_transaction(body).await.json().await.unwrap();
+    let stored: Transaction = app
+        .get_transaction(created.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        Utc.with_ymd_and_hms(2023, 5, 18, 1, 0, 0).unwrap(),
+        stored.posting_date()
+    );
+}
+
+#[tokio::test]
+async fn plain_posting_dates_are_read_back_in_order() {
+    let app = spawn_app().await;
+    let destination_account = create_account_in_app(&app).await;
+
+    for date in ["2021-03-01", "2024-11-30", "2019-07-15"] {
+        let body = format!(
+            "title=Test%20transaction&amount=42&source_account_id={}&destination_account_id={}&posting_date={}",
+            app.test_account.id,
+            destination_account.id(),
+            date
+        );
+        let response = app.post_transaction(body).await;
+        assert_eq!(StatusCode::CREATED, response.status().as_u16());
+    }
+
+    let transactions: Page<Transaction> = app.list_transactions(None).await.json().await.unwrap();
+    let dates: Vec<NaiveDate> = transactions
+        .items
+        .iter()
+        .map(|t| t.posting_date().date_naive())
+        .collect();
+
+    assert_eq!(
+        vec![
+            NaiveDate::from_ymd_opt(2024, 11, 30).unwrap(),
+            NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2019, 7, 15).unwrap(),
+        ],
+        dates
+    );
+}
+
+#[tokio::test]
+async fn invalid_posting_dates_are_rejected() {
+    let app = spawn_app().await;
+    let destination_account = create_account_in_app(&app).await;
+
+    let body = format!(
+        "title=Test%20transaction&amount=42&source_account_id={}&destination_account_id={}&posting_date=yesterday",
+        app.test_account.id,
+        destination_account.id()
+    );
+
+    let response = app.post_transaction(body).await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status().as_u16());
+}
@@ -0,0 +1,192 @@
+use berry::models::{currency::Currency, report::BalanceSheet, transaction::Transaction};
+use reqwest::StatusCode;
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+use crate::helpers::{create_account_with, spawn_app};
+
+#[tokio::test]
+async fn accounts_default_to_brl_and_take_a_currency() {
+    let app = spawn_app().await;
+
+    let bank = create_account_with(&app, "name=assets:bank").await;
+    let dollars = create_account_with(&app, "name=assets:dollars&currency=usd").await;
+
+    assert_eq!(Currency::BRL, bank.currency());
+    assert_eq!("USD", dollars.currency().code());
+}
+
+#[tokio::test]
+async fn accounts_with_invalid_currencies_are_rejected() {
+    let app = spawn_app().await;
+
+    let response = app
+        .post_account("name=assets:bank&currency=us%24".to_string())
+        .await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn cross_currency_transfers_need_an_exchange_rate() {
+    let app = spawn_app().await;
+    let bank = create_account_with(&app, "name=assets:bank").await;
+    let dollars = create_account_with(&app, "name=assets:dollars&currency=USD").await;
+    let body = json!({
+        "title": "Buy dollars",
+        "amount": "500",
+        "source_account_id": bank.id().to_string(),
+        "destination_account_id": dollars.id().to_string(),
+        "posting_date": "2026-01-10",
+    });
+
+    let response = app.post_transaction_json(&body).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    let mut body = body;
+    body["exchange_rate"] = json!({ "base": "EUR", "quote": "BRL", "rate": "6" });
+    let response = app.post_transaction_json(&body).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn cross_currency_transfers_convert_the_destination_at_the_recorded_rate() {
+    let app = spawn_app().await;
+    let bank = create_account_with(&app, "name=assets:bank").await;
+    let dollars = create_account_with(&app, "name=assets:dollars&currency=USD").await;
+
+    let response = app
+        .post_transaction_json(&json!({
+            "title": "Buy dollars",
+            "amount": "500",
+            "source_account_id": bank.id().to_string(),
+            "destination_account_id": dollars.id().to_string(),
+            "posting_date": "2026-01-10",
+            "exchange_rate": { "base": "USD", "quote": "BRL", "rate": "5" },
+        }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+    let transaction: Transaction = response.json().await.unwrap();
+
+    let rate = transaction.exchange_rate().expect("the rate is recorded");
+    assert_eq!(dec!(5), rate.rate());
+    let dollar_leg = transaction
+        .legs()
+        .iter()
+        .find(|leg| leg.account_id() == dollars.id())
+        .unwrap();
+    assert_eq!(dec!(100), dollar_leg.amount());
+    assert_eq!("USD", dollar_leg.currency().code());
+
+    let fetched: Transaction = app
+        .get_transaction(transaction.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(transaction.exchange_rate(), fetched.exchange_rate());
+}
+
+#[tokio::test]
+async fn updating_the_exchange_rate_converts_the_transfer_again() {
+    let app = spawn_app().await;
+    let bank = create_account_with(&app, "name=assets:bank").await;
+    let dollars = create_account_with(&app, "name=assets:dollars&currency=USD").await;
+    let transaction: Transaction = app
+        .post_transaction_json(&json!({
+            "title": "Buy dollars",
+            "amount": "500",
+            "source_account_id": bank.id().to_string(),
+            "destination_account_id": dollars.id().to_string(),
+            "posting_date": "2026-01-10",
+            "exchange_rate": { "base": "USD", "quote": "BRL", "rate": "5" },
+        }))
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let response = app
+        .update_transaction_json(
+            transaction.id().to_string(),
+            &json!({ "exchange_rate": { "base": "USD", "quote": "BRL", "rate": "4" } }),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let updated: Transaction = response.json().await.unwrap();
+
+    assert_eq!(dec!(4), updated.exchange_rate().unwrap().rate());
+    let dollar_leg = updated
+        .legs()
+        .iter()
+        .find(|leg| leg.account_id() == dollars.id())
+        .unwrap();
+    assert_eq!(dec!(125), dollar_leg.amount());
+
+    let fetched: Transaction = app
+        .get_transaction(transaction.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(updated.exchange_rate(), fetched.exchange_rate());
+
+    let response = app
+        .update_transaction_json(
+            transaction.id().to_string(),
+            &json!({ "exchange_rate": { "base": "EUR", "quote": "BRL", "rate": "6" } }),
+        )
+        .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn balance_sheets_convert_balances_into_the_reporting_currency() {
+    let app = spawn_app().await;
+    let equity = create_account_with(&app, "name=equity:opening&type=equity").await;
+    let bank = create_account_with(&app, "name=assets:bank").await;
+    let dollars = create_account_with(&app, "name=assets:dollars&currency=USD").await;
+    for (source, destination, amount, exchange_rate) in [
+        (&equity, &bank, "1000", None),
+        (
+            &bank,
+            &dollars,
+            "500",
+            Some(json!({ "base": "USD", "quote": "BRL", "rate": "5" })),
+        ),
+    ] {
+        let response = app
+            .post_transaction_json(&json!({
+                "title": "Transfer",
+                "amount": amount,
+                "source_account_id": source.id().to_string(),
+                "destination_account_id": destination.id().to_string(),
+                "posting_date": "2026-01-10",
+                "exchange_rate": exchange_rate,
+            }))
+            .await;
+        assert_eq!(StatusCode::CREATED, response.status());
+    }
+
+    let response = app
+        .get_balance_sheet(&[
+            ("from", "2026-01-01"),
+            ("to", "2026-02-01"),
+            ("currency", "USD"),
+        ])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let sheet: BalanceSheet = response.json().await.unwrap();
+    assert_eq!("USD", sheet.currency().code());
+    assert_eq!([dec!(200)], sheet.assets().totals());
+    assert_eq!([dec!(200)], sheet.equity().totals());
+
+    let response = app
+        .get_balance_sheet(&[
+            ("from", "2026-01-01"),
+            ("to", "2026-02-01"),
+            ("currency", "EUR"),
+        ])
+        .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
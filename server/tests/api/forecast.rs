@@ -0,0 +1,194 @@
+use berry::models::{
+    account::Account,
+    forecast::{CashFlowForecast, ForecastSource},
+};
+use chrono::{Datelike, Days, NaiveDate, Utc};
+use reqwest::StatusCode;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, create_account_in_app, spawn_app, transfer};
+
+fn today() -> NaiveDate {
+    Utc::now().date_naive()
+}
+
+fn in_days(days: u64) -> NaiveDate {
+    today() + Days::new(days)
+}
+
+async fn forecast(app: &TestApp, query: &[(&str, &str)]) -> CashFlowForecast {
+    let response = app.get_cash_flow_forecast(query).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn forecasts_apply_later_postings_and_schedules_and_flag_the_threshold() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let other = create_account_in_app(&app).await;
+    transfer(&app, &other, &bank, "1000", &today().to_string()).await;
+    transfer(&app, &bank, &other, "300", &in_days(5).to_string()).await;
+    let response = app
+        .post_schedule(&json!({
+            "title": "Groceries",
+            "amount": "200",
+            "source_account_id": bank.id(),
+            "destination_account_id": other.id(),
+            "frequency": "weekly",
+            "start_date": in_days(3).to_string(),
+        }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let forecast = forecast(
+        &app,
+        &[
+            ("days", "10"),
+            ("threshold", "600"),
+            ("account_id", &bank.id().to_string()),
+        ],
+    )
+    .await;
+
+    assert_eq!(in_days(1), forecast.from());
+    assert_eq!(in_days(10), forecast.until());
+    let [account] = forecast.accounts() else {
+        panic!("expected one account: {:?}", forecast.accounts());
+    };
+    assert_eq!(dec!(1000), account.opening_balance());
+    assert_eq!(10, account.days().len());
+    let balance_on = |days: u64| {
+        account
+            .days()
+            .iter()
+            .find(|day| day.date() == in_days(days))
+            .unwrap()
+            .balance()
+    };
+    assert_eq!(dec!(1000), balance_on(2));
+    assert_eq!(dec!(800), balance_on(3));
+    assert_eq!(dec!(500), balance_on(5));
+    assert_eq!(dec!(300), balance_on(10));
+    assert_eq!(dec!(300), account.lowest_balance());
+    assert_eq!(Some(in_days(5)), account.below_threshold_on());
+}
+
+#[tokio::test]
+async fn forecasts_pay_what_is_due_of_card_statements_on_their_due_dates() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let store = create_account_in_app(&app).await;
+    let card: Account = app
+        .post_account("name=liabilities:card&type=liability".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    let response = app
+        .set_credit_card(
+            card.id().to_string(),
+            &json!({
+                "closing_day": today().day(),
+                "due_day": in_days(10).day(),
+                "credit_limit": "1000",
+                "payment_account_id": bank.id(),
+            }),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    transfer(&app, &store, &bank, "500", &today().to_string()).await;
+    transfer(&app, &card, &store, "250", &today().to_string()).await;
+    transfer(&app, &bank, &card, "100", &today().to_string()).await;
+
+    let forecast = forecast(
+        &app,
+        &[("days", "20"), ("account_id", &bank.id().to_string())],
+    )
+    .await;
+
+    let [account] = forecast.accounts() else {
+        panic!("expected one account: {:?}", forecast.accounts());
+    };
+    let due_day = account
+        .days()
+        .iter()
+        .find(|day| day.date() == in_days(10))
+        .unwrap();
+    let [change] = due_day.changes() else {
+        panic!("expected one change: {:?}", due_day.changes());
+    };
+    assert_eq!(dec!(-150), change.amount());
+    assert!(matches!(
+        change.source(),
+        ForecastSource::CardStatement { account_id, .. } if *account_id == card.id()
+    ));
+    assert_eq!(dec!(250), account.lowest_balance());
+    assert_eq!(None, account.below_threshold_on());
+}
+
+#[tokio::test]
+async fn credit_cards_are_paid_from_asset_accounts() {
+    let app = spawn_app().await;
+    let card: Account = app
+        .post_account("name=liabilities:card&type=liability".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    let response = app
+        .set_credit_card(
+            card.id().to_string(),
+            &json!({
+                "closing_day": 3,
+                "due_day": 10,
+                "credit_limit": "1000",
+                "payment_account_id": card.id(),
+            }),
+        )
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn forecasts_reject_invalid_queries() {
+    let app = spawn_app().await;
+    let card: Account = app
+        .post_account("name=liabilities:card&type=liability".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    for (query, expected) in [
+        (
+            vec![("days", "0".to_string())],
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            vec![("days", "367".to_string())],
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            vec![("account_id", card.id().to_string())],
+            StatusCode::UNPROCESSABLE_ENTITY,
+        ),
+        (
+            vec![("account_id", Uuid::new_v4().to_string())],
+            StatusCode::NOT_FOUND,
+        ),
+        (
+            vec![("account_id", "nope".to_string())],
+            StatusCode::BAD_REQUEST,
+        ),
+    ] {
+        let query: Vec<(&str, &str)> = query.iter().map(|(k, v)| (*k, v.as_str())).collect();
+        let response = app.get_cash_flow_forecast(&query).await;
+        assert_eq!(expected, response.status(), "{query:?}");
+    }
+}
@@ -4,6 +4,7 @@ use berry::{
     configuration::{DatabaseSettings, get_configuration},
     models::{
         account::{Account, AccountName},
+        category::Category,
         transaction::Transaction,
     },
     server::Server,
@@ -52,6 +53,15 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    pub async fn post_account_json(&self, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/accounts", &self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn list_accounts(&self) -> reqwest::Response {
         self.api_client
             .get(format!("{}/accounts", &self.address))
@@ -60,6 +70,44 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    pub async fn list_accounts_of_type(&self, account_type: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/accounts?type={}", &self.address, account_type))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_accounts_with_query(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/accounts", &self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_account_postings(
+        &self,
+        id: String,
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/accounts/{}/transactions", &self.address, id))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_account_tree(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/accounts/tree", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn get_account(&self, id: String) -> reqwest::Response {
         self.api_client
             .get(format!("{}/accounts/{}", &self.address, id))
@@ -87,6 +135,57 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    pub async fn get_installment_commitments(&self, account_id: String) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "{}/accounts/{}/installments",
+                &self.address, account_id
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn set_credit_card(
+        &self,
+        account_id: String,
+        body: &serde_json::Value,
+    ) -> reqwest::Response {
+        self.api_client
+            .put(format!("{}/accounts/{}/card", &self.address, account_id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_card_statements(
+        &self,
+        account_id: String,
+        query: &[(&str, &str)],
+    ) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "{}/accounts/{}/statements",
+                &self.address, account_id
+            ))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_card_statement(&self, account_id: String, month: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!(
+                "{}/accounts/{}/statements/{}",
+                &self.address, account_id, month
+            ))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn rename_account(&self, id: String, body: String) -> reqwest::Response {
         self.api_client
             .patch(format!("{}/accounts/{}/name", &self.address, id))
@@ -97,6 +196,321 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    pub async fn post_budget(&self, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/budgets", &self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_budget_report(&self, month: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/budgets?month={}", &self.address, month))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_income_statement(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/reports/income-statement", &self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_balance_history(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/reports/balance-history", &self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_cash_flow_forecast(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/reports/cash-flow-forecast", &self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_balance_sheet(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/reports/balance-sheet", &self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn update_budget(&self, id: String, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .patch(format!("{}/budgets/{}", &self.address, id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_budget(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .delete(format!("{}/budgets/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_import(&self, form: reqwest::multipart::Form) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/imports", &self.address))
+            .multipart(form)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_import_batches(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/imports", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn rollback_import_batch(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .delete(format!("{}/imports/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_payees(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/payees", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_rule(&self, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/rules", &self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_rules(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/rules", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_rule(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/rules/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn update_rule(&self, id: String, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .put(format!("{}/rules/{}", &self.address, id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_rule(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .delete(format!("{}/rules/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_schedule(&self, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/schedules", &self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_schedule(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/schedules/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn update_schedule(&self, id: String, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .put(format!("{}/schedules/{}", &self.address, id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_schedule(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .delete(format!("{}/schedules/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_upcoming_occurrences(&self, until: &str) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/schedules/upcoming", &self.address))
+            .query(&[("until", until)])
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn skip_occurrence(&self, id: String, date: &str) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/schedules/{}/skip", &self.address, id))
+            .json(&serde_json::json!({ "date": date }))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_price(&self, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/prices", &self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_prices(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/prices", &self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_price(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/prices/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn update_price(&self, id: String, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .put(format!("{}/prices/{}", &self.address, id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_price(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .delete(format!("{}/prices/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn import_prices(&self, csv: &'static str) -> reqwest::Response {
+        let form = reqwest::multipart::Form::new().part(
+            "file",
+            reqwest::multipart::Part::bytes(csv.as_bytes()).file_name("prices.csv"),
+        );
+        self.api_client
+            .post(format!("{}/prices/import", &self.address))
+            .multipart(form)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn lookup_price(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/prices/lookup", &self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn apply_rules(&self, preview: bool) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/rules/apply", &self.address))
+            .query(&[("preview", preview)])
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn post_category(&self, body: String) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/categories", &self.address))
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn list_categories(&self) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/categories", &self.address))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn get_category(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/categories/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn update_category(&self, id: String, body: String) -> reqwest::Response {
+        self.api_client
+            .patch(format!("{}/categories/{}", &self.address, id))
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn delete_category(&self, id: String) -> reqwest::Response {
+        self.api_client
+            .delete(format!("{}/categories/{}", &self.address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn post_transaction(&self, body: String) -> reqwest::Response {
         self.api_client
             .post(format!("{}/transactions", &self.address))
@@ -107,6 +521,15 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    pub async fn post_transaction_json(&self, body: &serde_json::Value) -> reqwest::Response {
+        self.api_client
+            .post(format!("{}/transactions", &self.address))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn list_transactions(
         &self,
         pagination: Option<PaginationParameters>,
@@ -122,6 +545,15 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    pub async fn list_transactions_with_query(&self, query: &[(&str, &str)]) -> reqwest::Response {
+        self.api_client
+            .get(format!("{}/transactions", &self.address))
+            .query(query)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn get_transaction(&self, id: String) -> reqwest::Response {
         self.api_client
             .get(format!("{}/transactions/{}", &self.address, id))
@@ -130,6 +562,29 @@ impl TestApp {
             .expect("Failed to execute request.")
     }
 
+    pub async fn update_transaction(&self, id: String, body: String) -> reqwest::Response {
+        self.api_client
+            .patch(format!("{}/transactions/{}", &self.address, id))
+            .body(body)
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
+    pub async fn update_transaction_json(
+        &self,
+        id: String,
+        body: &serde_json::Value,
+    ) -> reqwest::Response {
+        self.api_client
+            .patch(format!("{}/transactions/{}", &self.address, id))
+            .json(body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+    }
+
     pub async fn delete_transaction(&self, id: String) -> reqwest::Response {
         self.api_client
             .delete(format!("{}/transactions/{}", &self.address, id))
@@ -150,6 +605,8 @@ pub async fn spawn_app() -> TestApp {
     configuration.database.database_name = Uuid::new_v4().to_string();
     // Use a random OS port
     configuration.application.port = 0;
+    // Post scheduled transactions without making tests wait long
+    configuration.application.scheduler_period = 1;
 
     let (container, host, port) = start_postgres_container(
         &configuration.database.username,
@@ -286,7 +743,7 @@ pub async fn generate_fake_transaction(app: &TestApp) -> Transaction {
     serde_json::from_slice(&res).unwrap()
 }
 
-async fn create_account_in_app(app: &TestApp) -> Account {
+pub async fn create_account_in_app(app: &TestApp) -> Account {
     let account = TestAccount::generate();
     let account = app
         .post_account(format!("name={}", account.name.into_url_encoding()))
@@ -297,3 +754,76 @@ async fn create_account_in_app(app: &TestApp) -> Account {
 
     serde_json::from_slice(&account).expect("failed to deserialize body into account")
 }
+
+/// Create an account from a form `body`, like `name=expenses:food&type=expense`
+pub async fn create_account_with(app: &TestApp, body: &str) -> Account {
+    let response = app.post_account(body.to_string()).await;
+    assert_eq!(201, response.status().as_u16());
+
+    response
+        .json()
+        .await
+        .expect("failed to deserialize body into account")
+}
+
+pub async fn create_expense_account(app: &TestApp, name: &str) -> Account {
+    create_account_with(app, &format!("name={name}&type=expense")).await
+}
+
+/// Post a transaction moving `amount` from one account to another, which must be created
+pub async fn post_transfer(
+    app: &TestApp,
+    title: &str,
+    amount: &str,
+    source_account_id: Uuid,
+    destination_account_id: Uuid,
+    category: Option<&str>,
+    posting_date: &str,
+) -> Transaction {
+    let response = app
+        .post_transaction_json(&serde_json::json!({
+            "title": title,
+            "amount": amount,
+            "source_account_id": source_account_id.to_string(),
+            "destination_account_id": destination_account_id.to_string(),
+            "category": category,
+            "posting_date": posting_date,
+        }))
+        .await;
+    assert_eq!(201, response.status().as_u16());
+
+    response
+        .json()
+        .await
+        .expect("failed to deserialize body into transaction")
+}
+
+/// Post a transfer of `amount` between two accounts on `posting_date`, which must be created
+pub async fn transfer(
+    app: &TestApp,
+    source: &Account,
+    destination: &Account,
+    amount: &str,
+    posting_date: &str,
+) -> Transaction {
+    post_transfer(
+        app,
+        "Transfer",
+        amount,
+        source.id(),
+        destination.id(),
+        None,
+        posting_date,
+    )
+    .await
+}
+
+pub async fn create_category_in_app(app: &TestApp, name: &str) -> Category {
+    let response = app.post_category(format!("name={name}")).await;
+    assert_eq!(201, response.status().as_u16());
+
+    response
+        .json()
+        .await
+        .expect("failed to deserialize body into category")
+}
@@ -0,0 +1,228 @@
+use berry::models::{
+    account::Account,
+    import_batch::{ImportBatch, ImportReport, ImportRowStatus, ImportSummary},
+    payee::Payee,
+    transaction::Transaction,
+};
+use reqwest::{StatusCode, multipart::Form, multipart::Part};
+use rust_decimal_macros::dec;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, spawn_app};
+
+const CSV: &str = "Data,Descrição,Valor,category
+10/04/2026,Bookstore,35.90,books
+11/04/2026,Refund,-5,
+not a date,Broken,1,
+";
+
+fn csv_form(dry_run: bool) -> Form {
+    Form::new()
+        .part(
+            "file",
+            Part::bytes(CSV.as_bytes()).file_name("statement.csv"),
+        )
+        .text("source_account", "liabilities:card")
+        .text("profile", "credit_card")
+        .text("dry_run", dry_run.to_string())
+}
+
+async fn import(app: &TestApp, form: Form, status: StatusCode) -> ImportReport {
+    let response = app.post_import(form).await;
+    assert_eq!(status, response.status());
+
+    response.json().await.unwrap()
+}
+
+async fn card(app: &TestApp) -> Account {
+    app.find_account_by_name("liabilities:card".to_string())
+        .await
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn importing_a_csv_file_reports_each_row() {
+    let app = spawn_app().await;
+
+    let report = import(&app, csv_form(false), StatusCode::CREATED).await;
+
+    assert_eq!(
+        ImportSummary {
+            created: 2,
+            duplicates: 0,
+            failed: 1
+        },
+        report.summary()
+    );
+    let batch = report.batch().unwrap();
+    assert_eq!("statement.csv", batch.source());
+    assert_eq!(3, batch.row_count());
+    assert_eq!(2, batch.transaction_count());
+
+    let rows = report.rows();
+    assert_eq!(Some(2), rows[0].line());
+    assert_eq!(Some("Bookstore"), rows[0].title());
+    assert_eq!(Some(dec!(-35.90)), rows[0].amount());
+    let ImportRowStatus::Created {
+        transaction_id: Some(id),
+    } = rows[0].status()
+    else {
+        panic!("the first row was not created: {:?}", rows[0].status());
+    };
+    assert_eq!(
+        StatusCode::OK,
+        app.get_transaction(id.to_string()).await.status()
+    );
+    assert_eq!(Some(4), rows[2].line());
+    assert!(matches!(rows[2].status(), ImportRowStatus::Failed { .. }));
+
+    // The card is a liability, which spending increases
+    assert_eq!(dec!(30.90), card(&app).await.balance());
+}
+
+#[tokio::test]
+async fn imported_titles_are_mapped_to_payees_and_the_default_accounts() {
+    let app = spawn_app().await;
+    let csv = "Data,Descrição,Valor
+10/05/2026,UBER *TRIP 1234,20.00
+11/05/2026,UBER *TRIP 5678,15.00
+12/05/2026,Refund,-5
+";
+    let form = Form::new()
+        .part("file", Part::bytes(csv.as_bytes()).file_name("uber.csv"))
+        .text("source_account", "liabilities:card");
+
+    let report = import(&app, form, StatusCode::CREATED).await;
+
+    let payees: Vec<Payee> = app.list_payees().await.json().await.unwrap();
+    let names: Vec<_> = payees.iter().map(|p| p.name().as_str()).collect();
+    assert_eq!(vec!["Refund", "Uber Trip"], names);
+
+    let mut payee_ids = Vec::new();
+    for row in &report.rows()[..2] {
+        let ImportRowStatus::Created {
+            transaction_id: Some(id),
+        } = row.status()
+        else {
+            panic!("row was not created: {:?}", row.status());
+        };
+        let transaction: Transaction = app
+            .get_transaction(id.to_string())
+            .await
+            .json()
+            .await
+            .unwrap();
+        payee_ids.push(transaction.payee_id());
+    }
+    assert_eq!(payee_ids[0], payee_ids[1]);
+    assert!(payee_ids[0].is_some());
+
+    let expenses: Account = app
+        .find_account_by_name("expenses:uncategorized".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dec!(35), expenses.balance());
+    let income: Account = app
+        .find_account_by_name("income:uncategorized".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dec!(5), income.balance());
+}
+
+#[tokio::test]
+async fn importing_a_file_again_skips_its_rows() {
+    let app = spawn_app().await;
+    import(&app, csv_form(false), StatusCode::CREATED).await;
+
+    let report = import(&app, csv_form(false), StatusCode::CREATED).await;
+
+    assert_eq!(0, report.summary().created);
+    assert_eq!(2, report.summary().duplicates);
+    assert!(matches!(
+        report.rows()[0].status(),
+        ImportRowStatus::Duplicate { .. }
+    ));
+    assert_eq!(dec!(30.90), card(&app).await.balance());
+}
+
+#[tokio::test]
+async fn dry_runs_do_not_write_anything() {
+    let app = spawn_app().await;
+
+    let report = import(&app, csv_form(true), StatusCode::OK).await;
+
+    assert!(report.dry_run());
+    assert!(report.batch().is_none());
+    assert_eq!(2, report.summary().created);
+    let batches: Vec<ImportBatch> = app.list_import_batches().await.json().await.unwrap();
+    assert!(batches.is_empty());
+    assert_eq!(
+        StatusCode::NOT_FOUND,
+        app.find_account_by_name("liabilities:card".to_string())
+            .await
+            .status()
+    );
+}
+
+#[tokio::test]
+async fn importing_with_an_unknown_profile_returns_422() {
+    let app = spawn_app().await;
+    let form = csv_form(false).text("profile", "unknown");
+
+    let response = app.post_import(form).await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn importing_without_a_file_returns_400() {
+    let app = spawn_app().await;
+    let form = Form::new().text("source_account", "liabilities:card");
+
+    let response = app.post_import(form).await;
+
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn rolling_back_an_import_batch_deletes_its_transactions() {
+    let app = spawn_app().await;
+    let report = import(&app, csv_form(false), StatusCode::CREATED).await;
+    let batch_id = report.batch().unwrap().id();
+
+    let response = app.rollback_import_batch(batch_id.to_string()).await;
+
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    assert_eq!(dec!(0), card(&app).await.balance());
+    let batches: Vec<ImportBatch> = app.list_import_batches().await.json().await.unwrap();
+    assert!(batches.is_empty());
+    // Rolled back rows can be imported again
+    let report = import(&app, csv_form(false), StatusCode::CREATED).await;
+    assert_eq!(2, report.summary().created);
+}
+
+#[tokio::test]
+async fn import_batches_are_empty_before_any_import() {
+    let app = spawn_app().await;
+
+    let response = app.list_import_batches().await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    let batches: Vec<ImportBatch> = response.json().await.unwrap();
+    assert!(batches.is_empty());
+}
+
+#[tokio::test]
+async fn rolling_back_an_unknown_import_batch_returns_404() {
+    let app = spawn_app().await;
+
+    let response = app.rollback_import_batch(Uuid::new_v4().to_string()).await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
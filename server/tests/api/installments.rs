@@ -0,0 +1,172 @@
+use berry::models::{
+    account::Account,
+    import_batch::{ImportReport, ImportRowStatus},
+    installment::InstallmentCommitments,
+    transaction::Transaction,
+};
+use chrono::{Days, Months, NaiveDate, Utc};
+use reqwest::{StatusCode, multipart::Form, multipart::Part};
+use rust_decimal_macros::dec;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, create_account_in_app, spawn_app};
+
+async fn commitments(app: &TestApp, account_id: Uuid) -> InstallmentCommitments {
+    let response = app
+        .get_installment_commitments(account_id.to_string())
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response.json().await.unwrap()
+}
+
+async fn import_installment(
+    app: &TestApp,
+    date: NaiveDate,
+    marker: &str,
+    amount: &str,
+) -> Transaction {
+    let csv = format!(
+        "Data,Descrição,Valor\n{},Loja X PARCELA {marker},{amount}\n",
+        date.format("%d/%m/%Y")
+    );
+    let form = Form::new()
+        .part("file", Part::bytes(csv.into_bytes()).file_name("card.csv"))
+        .text("source_account", "liabilities:card");
+    let response = app.post_import(form).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+    let report: ImportReport = response.json().await.unwrap();
+    let ImportRowStatus::Created {
+        transaction_id: Some(id),
+    } = report.rows()[0].status()
+    else {
+        panic!("the row was not created: {:?}", report.rows()[0].status());
+    };
+
+    app.get_transaction(id.to_string())
+        .await
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn transactions_can_be_split_into_installments() {
+    let app = spawn_app().await;
+    let card = create_account_in_app(&app).await;
+    let store = create_account_in_app(&app).await;
+
+    let response = app
+        .post_transaction_json(&json!({
+            "title": "Loja X",
+            "amount": 100,
+            "source_account_id": card.id(),
+            "destination_account_id": store.id(),
+            "installments": 3,
+        }))
+        .await;
+
+    assert_eq!(StatusCode::CREATED, response.status());
+    let first: Transaction = response.json().await.unwrap();
+    let installment = first.installment().unwrap();
+    assert_eq!(1, installment.number);
+    assert_eq!(dec!(33.34), first.amount());
+
+    let page: serde_json::Value = app
+        .list_transactions_with_query(&[("account_id", &card.id().to_string())])
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(3, page["total"]);
+    let store: Account = app
+        .get_account(store.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dec!(100), store.balance());
+
+    let commitments = commitments(&app, card.id()).await;
+    assert_eq!(dec!(66.66), commitments.remaining_amount());
+    assert_eq!(1, commitments.plans().len());
+    assert_eq!(installment.plan_id, commitments.plans()[0].plan().id());
+    assert_eq!(2, commitments.plans()[0].remaining_count());
+}
+
+#[tokio::test]
+async fn splitting_into_no_installments_returns_422() {
+    let app = spawn_app().await;
+    let card = create_account_in_app(&app).await;
+    let store = create_account_in_app(&app).await;
+
+    let response = app
+        .post_transaction_json(&json!({
+            "title": "Loja X",
+            "amount": 100,
+            "source_account_id": card.id(),
+            "destination_account_id": store.id(),
+            "installments": 0,
+        }))
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn imported_installments_of_a_purchase_share_a_plan() {
+    let app = spawn_app().await;
+    let second_date = Utc::now().date_naive() - Days::new(10);
+
+    let second = import_installment(&app, second_date, "2/3", "50").await;
+    let first = import_installment(&app, second_date - Months::new(1), "1/3", "50").await;
+
+    let plan_id = second.installment().unwrap().plan_id;
+    assert_eq!(Some(plan_id), first.installment().map(|i| i.plan_id));
+    assert_eq!(1, first.installment().unwrap().number);
+    let card: Account = app
+        .find_account_by_name("liabilities:card".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    let commitments = commitments(&app, card.id()).await;
+    assert_eq!(dec!(50), commitments.remaining_amount());
+    let commitment = &commitments.plans()[0];
+    assert_eq!("Loja X", commitment.plan().title().to_string());
+    assert_eq!(dec!(150), commitment.plan().total_amount());
+    assert_eq!(1, commitment.remaining_count());
+}
+
+#[tokio::test]
+async fn unevenly_rounded_installments_share_a_plan() {
+    let app = spawn_app().await;
+    let first_date = Utc::now().date_naive() - Days::new(10);
+
+    // Like 100 split into 3, the first installment taking the remainder
+    let first = import_installment(&app, first_date, "1/3", "33.34").await;
+    let second = import_installment(&app, first_date + Months::new(1), "2/3", "33.33").await;
+
+    let plan_id = first.installment().unwrap().plan_id;
+    assert_eq!(Some(plan_id), second.installment().map(|i| i.plan_id));
+    let card: Account = app
+        .find_account_by_name("liabilities:card".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    let commitments = commitments(&app, card.id()).await;
+    assert_eq!(1, commitments.plans().len());
+}
+
+#[tokio::test]
+async fn commitments_of_an_unknown_account_return_404() {
+    let app = spawn_app().await;
+
+    let response = app
+        .get_installment_commitments(Uuid::new_v4().to_string())
+        .await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
@@ -0,0 +1,113 @@
+use berry::{
+    models::{account::Account, transaction::AccountPosting},
+    service::Page,
+};
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, create_account_with, spawn_app, transfer};
+
+async fn list(app: &TestApp, account: &Account, query: &[(&str, &str)]) -> Page<AccountPosting> {
+    let response = app
+        .list_account_postings(account.id().to_string(), query)
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response.json().await.unwrap()
+}
+
+fn amounts_and_balances(page: &Page<AccountPosting>) -> Vec<(Decimal, Decimal)> {
+    page.items
+        .iter()
+        .map(|posting| (posting.amount(), posting.balance()))
+        .collect()
+}
+
+#[tokio::test]
+async fn account_postings_come_with_signed_amounts_and_a_running_balance() {
+    let app = spawn_app().await;
+    let salary = create_account_with(&app, "name=income:salary&type=income").await;
+    let card = create_account_with(&app, "name=liabilities:card&type=liability").await;
+    let groceries = create_account_with(&app, "name=expenses:groceries&type=expense").await;
+    let bank = create_account_with(&app, "name=assets:bank&type=asset").await;
+    transfer(&app, &salary, &bank, "1000", "2026-01-01").await;
+    transfer(&app, &card, &groceries, "40", "2026-01-10").await;
+    transfer(&app, &bank, &card, "40", "2026-02-05").await;
+    transfer(&app, &card, &groceries, "25", "2026-02-15").await;
+
+    let page = list(&app, &card, &[("direction", "asc")]).await;
+    assert_eq!(3, page.total);
+    assert_eq!(
+        vec![
+            (dec!(40), dec!(40)),
+            (dec!(-40), dec!(0)),
+            (dec!(25), dec!(25)),
+        ],
+        amounts_and_balances(&page)
+    );
+
+    let page = list(&app, &bank, &[]).await;
+    assert_eq!(
+        vec![(dec!(-40), dec!(960)), (dec!(1000), dec!(1000))],
+        amounts_and_balances(&page)
+    );
+}
+
+#[tokio::test]
+async fn account_postings_keep_their_running_balance_across_pages() {
+    let app = spawn_app().await;
+    let bank = create_account_with(&app, "name=assets:bank&type=asset").await;
+    let salary = create_account_with(&app, "name=income:salary&type=income").await;
+    for day in [
+        "2026-01-01",
+        "2026-01-02",
+        "2026-01-03",
+        "2026-01-04",
+        "2026-01-05",
+    ] {
+        transfer(&app, &salary, &bank, "10", day).await;
+    }
+
+    let mut balances = vec![];
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut query = vec![("per_page", "2")];
+        if let Some(cursor) = &cursor {
+            query.push(("cursor", cursor.as_str()));
+        }
+        let page = list(&app, &bank, &query).await;
+        assert_eq!(5, page.total);
+        balances.extend(page.items.iter().map(AccountPosting::balance));
+
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(
+        vec![dec!(50), dec!(40), dec!(30), dec!(20), dec!(10)],
+        balances
+    );
+
+    let page = list(&app, &bank, &[("per_page", "2"), ("page", "3")]).await;
+    assert_eq!(vec![(dec!(10), dec!(10))], amounts_and_balances(&page));
+    assert_eq!(None, page.next_cursor);
+}
+
+#[tokio::test]
+async fn account_postings_return_404_for_unknown_accounts() {
+    let app = spawn_app().await;
+
+    let response = app
+        .list_account_postings(Uuid::new_v4().to_string(), &[])
+        .await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let response = app
+        .list_account_postings(app.test_account().id.to_string(), &[("cursor", "garbage")])
+        .await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
@@ -1,6 +1,7 @@
 use std::str::from_utf8;
 
-use berry::models::account::Account;
+use berry::models::account::{Account, AccountType};
+use berry::service::Page;
 use reqwest::StatusCode;
 
 use crate::helpers::spawn_app;
@@ -19,9 +20,10 @@ async fn list_accounts_returns_empty_vec_if_there_are_no_accounts() {
     let body = from_utf8(&body).unwrap();
     tracing::info!(response_body = ?body);
 
-    let accounts: Vec<Account> = serde_json::from_str(body).unwrap();
+    let accounts: Page<Account> = serde_json::from_str(body).unwrap();
 
-    assert!(accounts.is_empty());
+    assert!(accounts.items.is_empty());
+    assert_eq!(accounts.next_cursor, None);
 }
 
 #[tokio::test]
@@ -37,7 +39,65 @@ async fn list_accounts_returns_all_accounts_in_the_database() {
     assert_eq!(StatusCode::OK, status);
 
     let body = response.bytes().await.unwrap();
-    let accounts: Vec<Account> = serde_json::from_slice(&body).unwrap();
+    let accounts: Page<Account> = serde_json::from_slice(&body).unwrap();
 
-    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts.items.len(), 2);
+    assert_eq!(accounts.total, 2);
+}
+
+#[tokio::test]
+async fn list_accounts_filters_by_type() {
+    let app = spawn_app().await;
+
+    app.post_account("name=Groceries&type=expense".to_string())
+        .await;
+    app.post_account("name=Restaurants&type=expense".to_string())
+        .await;
+    app.post_account("name=Salary&type=income".to_string())
+        .await;
+
+    let response = app.list_accounts_of_type("expense").await;
+    assert_eq!(StatusCode::OK, response.status().as_u16());
+
+    let accounts: Page<Account> = response.json().await.unwrap();
+
+    assert_eq!(accounts.items.len(), 2);
+    assert!(
+        accounts
+            .items
+            .iter()
+            .all(|a| a.account_type() == AccountType::Expense)
+    );
+}
+
+#[tokio::test]
+async fn list_accounts_pages_through_accounts_by_name_with_a_cursor() {
+    let app = spawn_app().await;
+    app.delete_account(app.test_account.id.to_string()).await;
+
+    for name in ["e", "b", "d", "a", "c"] {
+        app.post_account(format!("name={name}")).await;
+    }
+
+    let mut names = vec![];
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut query = vec![("per_page", "2")];
+        if let Some(cursor) = &cursor {
+            query.push(("cursor", cursor.as_str()));
+        }
+        let response = app.list_accounts_with_query(&query).await;
+        assert_eq!(StatusCode::OK, response.status().as_u16());
+
+        let page: Page<Account> = response.json().await.unwrap();
+        assert_eq!(page.total, 5);
+        names.extend(page.items.iter().map(|a| a.name().to_string()));
+
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(names, ["a", "b", "c", "d", "e"]);
 }
@@ -1,9 +1,15 @@
 use std::str::from_utf8;
 
-use berry::{models::transaction::Transaction, service::PaginationParameters};
+use berry::{
+    models::transaction::Transaction,
+    service::{Page, PaginationParameters},
+};
 use reqwest::StatusCode;
 
-use crate::helpers::{generate_fake_transaction, spawn_app};
+use crate::helpers::{
+    TestApp, create_account_in_app, create_category_in_app, generate_fake_transaction,
+    post_transfer, spawn_app,
+};
 
 #[tokio::test]
 async fn list_transactions_returns_empty_vec_if_there_are_no_transactions() {
@@ -18,9 +24,10 @@ async fn list_transactions_returns_empty_vec_if_there_are_no_transactions() {
     let body = from_utf8(&body).unwrap();
     tracing::info!(response_body = ?body);
 
-    let transactions: Vec<Transaction> = serde_json::from_str(body).unwrap();
+    let transactions: Page<Transaction> = serde_json::from_str(body).unwrap();
 
-    assert!(transactions.is_empty());
+    assert!(transactions.items.is_empty());
+    assert_eq!(transactions.total, 0);
 }
 
 #[tokio::test]
@@ -42,9 +49,10 @@ async fn list_transactions_returns_paginated_transactions_from_the_database() {
     assert_eq!(StatusCode::OK, status);
 
     let body = response.bytes().await.unwrap();
-    let transactions: Vec<Transaction> = serde_json::from_slice(&body).unwrap();
+    let transactions: Page<Transaction> = serde_json::from_slice(&body).unwrap();
 
-    assert_eq!(transactions.len(), 20);
+    assert_eq!(transactions.items.len(), 20);
+    assert_eq!(transactions.total, 30);
 
     let response = app
         .list_transactions(Some(PaginationParameters {
@@ -58,9 +66,10 @@ async fn list_transactions_returns_paginated_transactions_from_the_database() {
     assert_eq!(StatusCode::OK, status);
 
     let body = response.bytes().await.unwrap();
-    let transactions: Vec<Transaction> = serde_json::from_slice(&body).unwrap();
+    let transactions: Page<Transaction> = serde_json::from_slice(&body).unwrap();
 
-    assert_eq!(transactions.len(), 10);
+    assert_eq!(transactions.items.len(), 10);
+    assert_eq!(transactions.total, 30);
 }
 
 #[tokio::test]
@@ -77,7 +86,227 @@ async fn list_transactions_returns_all_transactions_if_no_query_parameters_are_r
     assert_eq!(StatusCode::OK, status);
 
     let body = response.bytes().await.unwrap();
-    let transactions: Vec<Transaction> = serde_json::from_slice(&body).unwrap();
+    let transactions: Page<Transaction> = serde_json::from_slice(&body).unwrap();
 
-    assert_eq!(transactions.len(), 30);
+    assert_eq!(transactions.items.len(), 30);
+    assert_eq!(transactions.total, 30);
+}
+
+async fn list_with_query(app: &TestApp, query: &[(&str, &str)]) -> Page<Transaction> {
+    let response = app.list_transactions_with_query(query).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn list_transactions_filters_by_account_on_either_side() {
+    let app = spawn_app().await;
+    let transaction = generate_fake_transaction(&app).await;
+    generate_fake_transaction(&app).await;
+
+    for leg in transaction.legs() {
+        let account_id = leg.account_id().to_string();
+        let page = list_with_query(&app, &[("account_id", &account_id)]).await;
+
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].id(), transaction.id());
+    }
+}
+
+#[tokio::test]
+async fn list_transactions_filters_by_date_range_with_an_exclusive_end() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await.id();
+    let destination = create_account_in_app(&app).await.id();
+    post_transfer(
+        &app,
+        "January",
+        "1",
+        source,
+        destination,
+        None,
+        "2026-01-15",
+    )
+    .await;
+    let february = post_transfer(
+        &app,
+        "February",
+        "1",
+        source,
+        destination,
+        None,
+        "2026-02-01",
+    )
+    .await;
+    post_transfer(&app, "March", "1", source, destination, None, "2026-03-01").await;
+
+    let page = list_with_query(&app, &[("from", "2026-02-01"), ("to", "2026-03-01")]).await;
+
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].id(), february.id());
+}
+
+#[tokio::test]
+async fn list_transactions_filters_by_amount_category_and_title() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await.id();
+    let destination = create_account_in_app(&app).await.id();
+    create_category_in_app(&app, "food:groceries").await;
+    create_category_in_app(&app, "housing").await;
+    post_transfer(
+        &app,
+        "Groceries at the market",
+        "12.50",
+        source,
+        destination,
+        Some("food:groceries"),
+        "2026-01-01",
+    )
+    .await;
+    let rent = post_transfer(
+        &app,
+        "Rent",
+        "900",
+        source,
+        destination,
+        Some("housing"),
+        "2026-01-02",
+    )
+    .await;
+    let restaurant = post_transfer(
+        &app,
+        "Dinner at 100% Pizza",
+        "45",
+        source,
+        destination,
+        Some("food"),
+        "2026-01-03",
+    )
+    .await;
+
+    let page = list_with_query(&app, &[("min_amount", "40"), ("max_amount", "900")]).await;
+    assert_eq!(page.total, 2);
+
+    let page = list_with_query(&app, &[("category", "housing")]).await;
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].id(), rent.id());
+
+    let page = list_with_query(&app, &[("title", "100%")]).await;
+    assert_eq!(page.total, 1);
+    assert_eq!(page.items[0].id(), restaurant.id());
+
+    let page = list_with_query(&app, &[("title", "AT THE"), ("category", "food")]).await;
+    assert_eq!(page.total, 1);
+
+    let page = list_with_query(&app, &[("category", "food")]).await;
+    assert_eq!(page.total, 2);
+}
+
+#[tokio::test]
+async fn list_transactions_sorts_by_the_requested_field_and_direction() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await.id();
+    let destination = create_account_in_app(&app).await.id();
+    post_transfer(&app, "b", "30", source, destination, None, "2026-01-01").await;
+    post_transfer(&app, "c", "10", source, destination, None, "2026-01-02").await;
+    post_transfer(&app, "a", "20", source, destination, None, "2026-01-03").await;
+
+    let titles = |page: Page<Transaction>| {
+        page.items
+            .iter()
+            .map(|t| t.title().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let page = list_with_query(&app, &[]).await;
+    assert_eq!(titles(page), ["a", "c", "b"]);
+
+    let page = list_with_query(&app, &[("sort", "amount"), ("direction", "asc")]).await;
+    assert_eq!(titles(page), ["c", "a", "b"]);
+
+    let page = list_with_query(&app, &[("sort", "title"), ("direction", "desc")]).await;
+    assert_eq!(titles(page), ["c", "b", "a"]);
+}
+
+#[tokio::test]
+async fn list_transactions_returns_400_for_invalid_filters() {
+    let app = spawn_app().await;
+
+    for query in [
+        [("account_id", "not-a-uuid")],
+        [("from", "yesterday")],
+        [("sort", "color")],
+    ] {
+        let response = app.list_transactions_with_query(&query).await;
+
+        assert_eq!(StatusCode::BAD_REQUEST, response.status());
+    }
+}
+
+#[tokio::test]
+async fn list_transactions_pages_through_posting_dates_with_a_cursor() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await.id();
+    let destination = create_account_in_app(&app).await.id();
+    let mut expected = vec![];
+    for day in [
+        "2026-01-03",
+        "2026-01-01",
+        "2026-01-02",
+        "2026-01-02",
+        "2026-01-04",
+    ] {
+        expected.push(post_transfer(&app, day, "1", source, destination, None, day).await);
+    }
+    expected.sort_by_key(|t| (t.posting_date(), t.id()));
+
+    let mut ids = vec![];
+    let mut cursor: Option<String> = None;
+    loop {
+        let mut query = vec![("per_page", "2"), ("direction", "asc")];
+        if let Some(cursor) = &cursor {
+            query.push(("cursor", cursor.as_str()));
+        }
+        let page = list_with_query(&app, &query).await;
+        assert_eq!(page.total, 5);
+        ids.extend(page.items.iter().map(Transaction::id));
+
+        // Transactions posted between pages must not shift the following ones
+        if cursor.is_none() {
+            post_transfer(&app, "late", "1", source, destination, None, "2025-12-31").await;
+        }
+
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(
+        ids,
+        expected.iter().map(Transaction::id).collect::<Vec<_>>()
+    );
+}
+
+#[tokio::test]
+async fn list_transactions_rejects_cursors_when_not_sorting_by_posting_date() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await.id();
+    let destination = create_account_in_app(&app).await.id();
+    post_transfer(&app, "a", "1", source, destination, None, "2026-01-01").await;
+    post_transfer(&app, "b", "1", source, destination, None, "2026-01-02").await;
+
+    let page = list_with_query(&app, &[("per_page", "1")]).await;
+    let cursor = page.next_cursor.expect("there should be a next page");
+
+    let response = app
+        .list_transactions_with_query(&[("cursor", &cursor), ("sort", "amount")])
+        .await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let response = app
+        .list_transactions_with_query(&[("cursor", "garbage")])
+        .await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
 }
@@ -1,11 +1,25 @@
+mod account_tree;
+mod budgets;
+mod categories;
 mod create_account;
 mod create_transaction;
+mod currencies;
 mod delete_account;
 mod delete_transaction;
 mod find_account_by_name;
+mod forecast;
 mod get_account;
 mod get_transaction;
 mod helpers;
+mod imports;
+mod installments;
+mod list_account_postings;
 mod list_accounts;
 mod list_transactions;
+mod prices;
+mod reports;
 mod rename_account;
+mod rules;
+mod schedules;
+mod statements;
+mod update_transaction;
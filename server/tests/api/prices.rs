@@ -0,0 +1,237 @@
+use berry::models::{
+    price::{Price, PriceImportReport, PriceQuote},
+    report::{BalanceHistory, BalanceSheet},
+};
+use reqwest::StatusCode;
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+use crate::helpers::{TestApp, create_account_with, spawn_app};
+
+async fn create_price(app: &TestApp, date: &str, rate: &str) -> Price {
+    let response = app
+        .post_price(&json!({
+            "commodity": "USD",
+            "currency": "BRL",
+            "date": date,
+            "rate": rate,
+        }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn prices_can_be_created_read_updated_and_deleted() {
+    let app = spawn_app().await;
+
+    let price = create_price(&app, "2026-01-02", "5.40").await;
+    assert_eq!("USD", price.commodity().code());
+    assert_eq!(dec!(5.40), price.rate());
+
+    let fetched: Price = app
+        .get_price(price.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(price, fetched);
+
+    let response = app
+        .update_price(
+            price.id().to_string(),
+            &json!({ "commodity": "usd", "currency": "brl", "date": "2026-01-02", "rate": "5.5" }),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let updated: Price = response.json().await.unwrap();
+    assert_eq!(dec!(5.5), updated.rate());
+
+    let response = app.delete_price(price.id().to_string()).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    let response = app.get_price(price.id().to_string()).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn invalid_and_duplicate_prices_are_rejected() {
+    let app = spawn_app().await;
+    create_price(&app, "2026-01-02", "5.40").await;
+
+    for body in [
+        json!({ "commodity": "USD", "currency": "BRL", "date": "2026-01-02", "rate": "5.5" }),
+        json!({ "commodity": "USD", "currency": "USD", "date": "2026-01-03", "rate": "1" }),
+        json!({ "commodity": "USD", "currency": "BRL", "date": "2026-01-03", "rate": "0" }),
+    ] {
+        let response = app.post_price(&body).await;
+        assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+    }
+}
+
+#[tokio::test]
+async fn prices_are_listed_by_commodity_currency_and_date() {
+    let app = spawn_app().await;
+    create_price(&app, "2026-01-03", "5.50").await;
+    create_price(&app, "2026-01-02", "5.40").await;
+    let response = app
+        .post_price(&json!({
+            "commodity": "PETR4",
+            "currency": "BRL",
+            "date": "2026-01-02",
+            "rate": "38.12",
+        }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    let prices: Vec<Price> = app.list_prices(&[]).await.json().await.unwrap();
+    let listed: Vec<_> = prices
+        .iter()
+        .map(|price| (price.commodity().to_string(), price.rate()))
+        .collect();
+    assert_eq!(
+        vec![
+            ("PETR4".to_string(), dec!(38.12)),
+            ("USD".to_string(), dec!(5.40)),
+            ("USD".to_string(), dec!(5.50)),
+        ],
+        listed
+    );
+
+    let prices: Vec<Price> = app
+        .list_prices(&[("commodity", "petr4")])
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(1, prices.len());
+}
+
+#[tokio::test]
+async fn price_files_create_and_update_prices() {
+    let app = spawn_app().await;
+    create_price(&app, "2026-01-02", "5.40").await;
+
+    let response = app
+        .import_prices(
+            "commodity,currency,date,rate\n\
+             USD,BRL,2026-01-02,5.45\n\
+             USD,BRL,2026-01-05,5.50\n\
+             EUR,BRL,2026-01-05,not a rate\n\
+             EUR,BRL,2026-01-05,6.10\n",
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let report: PriceImportReport = response.json().await.unwrap();
+
+    assert_eq!(2, report.created());
+    assert_eq!(1, report.updated());
+    assert_eq!(1, report.failed().len());
+    assert_eq!(4, report.failed()[0].line());
+
+    let prices: Vec<Price> = app
+        .list_prices(&[("commodity", "USD")])
+        .await
+        .json()
+        .await
+        .unwrap();
+    let rates: Vec<_> = prices.iter().map(Price::rate).collect();
+    assert_eq!(vec![dec!(5.45), dec!(5.50)], rates);
+}
+
+#[tokio::test]
+async fn price_files_must_have_every_column() {
+    let app = spawn_app().await;
+
+    let response = app
+        .import_prices("commodity,date,rate\nUSD,2026-01-02,5\n")
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn lookups_find_the_nearest_prior_price() {
+    let app = spawn_app().await;
+    create_price(&app, "2026-01-02", "5").await;
+    create_price(&app, "2026-01-10", "4").await;
+
+    let response = app
+        .lookup_price(&[
+            ("commodity", "USD"),
+            ("currency", "BRL"),
+            ("date", "2026-01-09"),
+        ])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let quote: PriceQuote = response.json().await.unwrap();
+    assert_eq!(dec!(5), quote.rate());
+
+    let quote: PriceQuote = app
+        .lookup_price(&[
+            ("commodity", "BRL"),
+            ("currency", "USD"),
+            ("date", "2026-02-01"),
+        ])
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dec!(0.25), quote.rate());
+    assert_eq!(dec!(4), quote.price().rate());
+
+    let response = app
+        .lookup_price(&[
+            ("commodity", "USD"),
+            ("currency", "BRL"),
+            ("date", "2026-01-01"),
+        ])
+        .await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn balances_are_valued_with_the_prices_of_each_period() {
+    let app = spawn_app().await;
+    let equity = create_account_with(&app, "name=equity:opening&type=equity&currency=USD").await;
+    let dollars = create_account_with(&app, "name=assets:dollars&currency=USD").await;
+    let response = app
+        .post_transaction_json(&json!({
+            "title": "Opening balance",
+            "amount": "100",
+            "source_account_id": equity.id().to_string(),
+            "destination_account_id": dollars.id().to_string(),
+            "posting_date": "2026-01-05",
+        }))
+        .await;
+    assert_eq!(StatusCode::CREATED, response.status());
+    create_price(&app, "2026-01-15", "5").await;
+    create_price(&app, "2026-02-10", "6").await;
+
+    let response = app
+        .get_balance_sheet(&[("from", "2026-01-01"), ("to", "2026-03-01")])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let sheet: BalanceSheet = response.json().await.unwrap();
+    assert_eq!([dec!(500), dec!(600)], sheet.assets().totals());
+
+    let response = app
+        .get_balance_history(&[
+            ("from", "2026-01-01"),
+            ("to", "2026-03-01"),
+            ("account_id", &dollars.id().to_string()),
+        ])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let history: BalanceHistory = response.json().await.unwrap();
+    assert_eq!([dec!(500), dec!(600)], history.net_worth());
+
+    let response = app
+        .get_balance_history(&[
+            ("from", "2026-01-01"),
+            ("to", "2026-03-01"),
+            ("interval", "week"),
+        ])
+        .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
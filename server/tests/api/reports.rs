@@ -0,0 +1,154 @@
+use berry::models::{
+    account::Account,
+    budget::Month,
+    report::{BalanceHistory, BalanceSheet, IncomeStatement},
+};
+use berry::service::Page;
+use reqwest::StatusCode;
+use rust_decimal_macros::dec;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, create_account_with, spawn_app, transfer};
+
+/// A salary paid into a bank account, and groceries paid with a credit card that is paid off
+/// the following month.
+async fn create_ledger(app: &TestApp) {
+    let salary = create_account_with(app, "name=income:salary&type=income").await;
+    let bank = create_account_with(app, "name=assets:bank&type=asset").await;
+    let card = create_account_with(app, "name=liabilities:card&type=liability").await;
+    let groceries = create_account_with(app, "name=expenses:groceries&type=expense").await;
+
+    transfer(app, &salary, &bank, "1000", "2025-12-20").await;
+    transfer(app, &card, &groceries, "40", "2026-01-10").await;
+    transfer(app, &bank, &card, "40", "2026-02-05").await;
+    transfer(app, &card, &groceries, "25", "2026-02-15").await;
+    transfer(app, &card, &groceries, "99", "2026-03-01").await;
+}
+
+#[tokio::test]
+async fn income_statement_groups_income_and_expenses_by_account_and_month() {
+    let app = spawn_app().await;
+    create_ledger(&app).await;
+
+    let response = app
+        .get_income_statement(&[("from", "2026-01-01"), ("to", "2026-03-01")])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let report: IncomeStatement = response.json().await.unwrap();
+
+    assert_eq!(
+        [Month::new(2026, 1).unwrap(), Month::new(2026, 2).unwrap()],
+        report.months()
+    );
+    assert!(report.income().lines().is_empty());
+    assert_eq!(1, report.expenses().lines().len());
+    assert_eq!(
+        "expenses:groceries",
+        report.expenses().lines()[0].account_name().to_string()
+    );
+    assert_eq!([dec!(40), dec!(25)], report.expenses().totals());
+    assert_eq!([dec!(-40), dec!(-25)], report.net_income());
+    assert_eq!(dec!(-65), report.net_income_total());
+}
+
+#[tokio::test]
+async fn balance_sheet_reports_closing_balances_including_earlier_transactions() {
+    let app = spawn_app().await;
+    create_ledger(&app).await;
+
+    let response = app
+        .get_balance_sheet(&[("from", "2026-01-01"), ("to", "2026-03-01")])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let report: BalanceSheet = response.json().await.unwrap();
+
+    let bank = report
+        .assets()
+        .lines()
+        .iter()
+        .find(|line| line.account_name().to_string() == "assets:bank")
+        .expect("the bank account should be in the balance sheet");
+    assert_eq!([dec!(1000), dec!(960)], bank.amounts());
+    assert_eq!([dec!(40), dec!(25)], report.liabilities().totals());
+    assert_eq!([dec!(960), dec!(935)], report.retained_earnings());
+}
+
+#[tokio::test]
+async fn reports_reject_invalid_periods() {
+    let app = spawn_app().await;
+
+    let response = app
+        .get_income_statement(&[("from", "2026-03-01"), ("to", "2026-01-01")])
+        .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    let response = app.get_balance_sheet(&[("from", "last year")]).await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+}
+
+#[tokio::test]
+async fn balance_history_reports_end_of_period_balances_of_the_selected_accounts() {
+    let app = spawn_app().await;
+    create_ledger(&app).await;
+    let accounts: Page<Account> = app.list_accounts().await.json().await.unwrap();
+    let id_of = |name: &str| {
+        accounts
+            .items
+            .iter()
+            .find(|a| a.name().to_string() == name)
+            .unwrap()
+            .id()
+            .to_string()
+    };
+    let selection = format!("{},{}", id_of("assets:bank"), id_of("liabilities:card"));
+
+    let response = app
+        .get_balance_history(&[
+            ("from", "2026-01-01"),
+            ("to", "2026-03-01"),
+            ("interval", "month"),
+            ("account_id", &selection),
+        ])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let history: BalanceHistory = response.json().await.unwrap();
+
+    assert_eq!(2, history.accounts().len());
+    assert_eq!([dec!(1000), dec!(960)], history.accounts()[0].balances());
+    assert_eq!([dec!(40), dec!(25)], history.accounts()[1].balances());
+    assert_eq!([dec!(960), dec!(935)], history.net_worth());
+
+    let response = app
+        .get_balance_history(&[
+            ("from", "2026-02-01"),
+            ("to", "2026-02-16"),
+            ("interval", "day"),
+        ])
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let history: BalanceHistory = response.json().await.unwrap();
+    assert_eq!(15, history.periods().len());
+    assert_eq!(Some(&dec!(935)), history.net_worth().last());
+}
+
+#[tokio::test]
+async fn balance_history_rejects_unknown_accounts_and_invalid_intervals() {
+    let app = spawn_app().await;
+
+    let response = app
+        .get_balance_history(&[("account_id", &Uuid::new_v4().to_string())])
+        .await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+
+    let response = app.get_balance_history(&[("interval", "year")]).await;
+    assert_eq!(StatusCode::BAD_REQUEST, response.status());
+
+    let response = app
+        .get_balance_history(&[
+            ("from", "2000-01-01"),
+            ("to", "2026-01-01"),
+            ("interval", "day"),
+        ])
+        .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
@@ -0,0 +1,324 @@
+use berry::models::{
+    account::Account,
+    import_batch::{ImportReport, ImportRowStatus},
+    rule::{ApplyRulesReport, Rule},
+    transaction::Transaction,
+};
+use reqwest::{StatusCode, multipart::Form, multipart::Part};
+use rust_decimal_macros::dec;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{
+    TestApp, create_account_with, create_category_in_app, create_expense_account, spawn_app,
+};
+
+const CSV: &str = "Data,Descrição,Valor,category
+10/04/2026,Bookstore,35.90,
+11/04/2026,Bakery,5,
+";
+
+async fn import(app: &TestApp) -> ImportReport {
+    let form = Form::new()
+        .part(
+            "file",
+            Part::bytes(CSV.as_bytes()).file_name("statement.csv"),
+        )
+        .text("source_account", "liabilities:card");
+    let response = app.post_import(form).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response.json().await.unwrap()
+}
+
+async fn create_rule(app: &TestApp, body: serde_json::Value) -> Rule {
+    let response = app.post_rule(&body).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response.json().await.unwrap()
+}
+
+async fn imported_transaction(report: &ImportReport, row: usize, app: &TestApp) -> Transaction {
+    let ImportRowStatus::Created {
+        transaction_id: Some(id),
+    } = report.rows()[row].status()
+    else {
+        panic!("row {row} was not created");
+    };
+
+    app.get_transaction(id.to_string())
+        .await
+        .json()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn rules_are_listed_in_order_of_priority() {
+    let app = spawn_app().await;
+    let second = create_rule(
+        &app,
+        json!({"name": "second", "priority": 2, "tags": ["b"]}),
+    )
+    .await;
+    let first = create_rule(&app, json!({"name": "first", "priority": 1, "tags": ["a"]})).await;
+
+    let rules: Vec<Rule> = app.list_rules().await.json().await.unwrap();
+
+    assert_eq!(vec![first, second], rules);
+}
+
+#[tokio::test]
+async fn creating_an_invalid_rule_fails() {
+    let app = spawn_app().await;
+
+    let invalid_pattern = app
+        .post_rule(&json!({"name": "rule", "title_pattern": "(", "tags": ["a"]}))
+        .await;
+    let no_actions = app.post_rule(&json!({"name": "rule"})).await;
+    let unknown_category = app
+        .post_rule(&json!({"name": "rule", "category": "unknown"}))
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, invalid_pattern.status());
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, no_actions.status());
+    assert_eq!(StatusCode::NOT_FOUND, unknown_category.status());
+}
+
+#[tokio::test]
+async fn rules_can_be_replaced_and_deleted() {
+    let app = spawn_app().await;
+    let rule = create_rule(&app, json!({"name": "rule", "tags": ["a"]})).await;
+
+    let response = app
+        .update_rule(
+            rule.id().to_string(),
+            &json!({"name": "renamed", "min_amount": 10, "tags": ["b"]}),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let rule: Rule = app
+        .get_rule(rule.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!("renamed", rule.name());
+    assert_eq!(Some(dec!(10)), rule.conditions().min_amount);
+    assert_eq!(vec!["b".to_string()], rule.actions().tags);
+
+    let response = app.delete_rule(rule.id().to_string()).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+    let response = app.get_rule(rule.id().to_string()).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn accounts_used_by_a_rule_cannot_be_deleted() {
+    let app = spawn_app().await;
+    let books = create_expense_account(&app, "expenses:books").await;
+    let rule = create_rule(
+        &app,
+        json!({"name": "books", "destination_account_id": books.id()}),
+    )
+    .await;
+
+    let response = app.delete_account(books.id().to_string()).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    let response = app.get_rule(rule.id().to_string()).await;
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn replacing_an_unknown_rule_returns_404() {
+    let app = spawn_app().await;
+
+    let response = app
+        .update_rule(
+            Uuid::new_v4().to_string(),
+            &json!({"name": "rule", "tags": ["a"]}),
+        )
+        .await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
+
+#[tokio::test]
+async fn imported_rows_are_categorized_by_the_first_matching_rule() {
+    let app = spawn_app().await;
+    create_category_in_app(&app, "leisure").await;
+    let books = create_expense_account(&app, "expenses:books").await;
+    create_rule(
+        &app,
+        json!({
+            "name": "books",
+            "title_pattern": "(?i)^book",
+            "category": "leisure",
+            "destination_account_id": books.id(),
+            "tags": ["reading"],
+        }),
+    )
+    .await;
+    create_rule(
+        &app,
+        json!({"name": "fallback", "priority": 1, "max_amount": 1, "tags": ["small"]}),
+    )
+    .await;
+
+    let report = import(&app).await;
+
+    let bookstore = imported_transaction(&report, 0, &app).await;
+    assert_eq!(
+        Some("leisure".to_string()),
+        bookstore.category().as_ref().map(|c| c.to_string())
+    );
+    assert_eq!(["reading".to_string()], bookstore.tags());
+    assert!(
+        bookstore
+            .legs()
+            .iter()
+            .any(|leg| leg.account_id() == books.id())
+    );
+    let bakery = imported_transaction(&report, 1, &app).await;
+    // The fallback rule only matches amounts up to 1
+    assert!(bakery.tags().is_empty());
+}
+
+#[tokio::test]
+async fn applying_rules_updates_existing_transactions() {
+    let app = spawn_app().await;
+    let report = import(&app).await;
+    let books = create_expense_account(&app, "expenses:books").await;
+    let rule = create_rule(
+        &app,
+        json!({
+            "name": "books",
+            "title_pattern": "Bookstore",
+            "destination_account_id": books.id(),
+            "tags": ["reading"],
+        }),
+    )
+    .await;
+
+    let response = app.apply_rules(true).await;
+    assert_eq!(StatusCode::OK, response.status());
+    let preview: ApplyRulesReport = response.json().await.unwrap();
+    assert!(preview.preview());
+    assert_eq!(1, preview.changes().len());
+    let change = &preview.changes()[0];
+    assert_eq!(rule.id(), change.rule_id());
+    assert_eq!(
+        Some(books.id()),
+        change.destination_account_id().map(|change| change.to)
+    );
+    assert!(
+        imported_transaction(&report, 0, &app)
+            .await
+            .tags()
+            .is_empty()
+    );
+
+    let applied: ApplyRulesReport = app.apply_rules(false).await.json().await.unwrap();
+    assert_eq!(preview.changes(), applied.changes());
+    let bookstore = imported_transaction(&report, 0, &app).await;
+    assert_eq!(["reading".to_string()], bookstore.tags());
+    let books: Account = app
+        .get_account(books.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(dec!(35.90), books.balance());
+
+    // Applying the rules again changes nothing
+    let again: ApplyRulesReport = app.apply_rules(false).await.json().await.unwrap();
+    assert!(again.changes().is_empty());
+}
+
+#[tokio::test]
+async fn renaming_a_category_renames_it_in_rules() {
+    let app = spawn_app().await;
+    let report = import(&app).await;
+    let leisure = create_category_in_app(&app, "leisure").await;
+    let rule = create_rule(
+        &app,
+        json!({"name": "books", "title_pattern": "Bookstore", "category": "leisure"}),
+    )
+    .await;
+
+    let response = app
+        .update_category(leisure.id().to_string(), "name=hobbies".to_string())
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let rules: Vec<Rule> = app.list_rules().await.json().await.unwrap();
+    assert_eq!(rule.id(), rules[0].id());
+    assert_eq!(
+        Some("hobbies".to_string()),
+        rules[0].actions().category.as_ref().map(|c| c.to_string())
+    );
+
+    // The rule keeps the category from being deleted
+    let response = app.delete_category(leisure.id().to_string()).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    let response = app.apply_rules(false).await;
+    assert_eq!(StatusCode::OK, response.status());
+    let bookstore = imported_transaction(&report, 0, &app).await;
+    assert_eq!(
+        Some("hobbies".to_string()),
+        bookstore.category().as_ref().map(|c| c.to_string())
+    );
+}
+
+#[tokio::test]
+async fn rules_only_move_legs_to_accounts_in_their_currency() {
+    let app = spawn_app().await;
+    let report = import(&app).await;
+    let dollars =
+        create_account_with(&app, "name=expenses:books:usd&type=expense&currency=USD").await;
+    create_rule(
+        &app,
+        json!({
+            "name": "books",
+            "title_pattern": "Bookstore",
+            "destination_account_id": dollars.id(),
+            "tags": ["reading"],
+        }),
+    )
+    .await;
+
+    let applied: ApplyRulesReport = app.apply_rules(false).await.json().await.unwrap();
+
+    assert_eq!(1, applied.changes().len());
+    assert_eq!(None, applied.changes()[0].destination_account_id());
+    let bookstore = imported_transaction(&report, 0, &app).await;
+    assert_eq!(["reading".to_string()], bookstore.tags());
+    assert!(
+        bookstore
+            .legs()
+            .iter()
+            .all(|leg| leg.account_id() != dollars.id())
+    );
+}
+
+#[tokio::test]
+async fn rules_must_keep_the_currency_of_the_source_account() {
+    let app = spawn_app().await;
+    let card = create_account_with(
+        &app,
+        "name=liabilities:card:usd&type=liability&currency=USD",
+    )
+    .await;
+    let books = create_expense_account(&app, "expenses:books").await;
+
+    let response = app
+        .post_rule(&json!({
+            "name": "books",
+            "source_account_id": card.id(),
+            "destination_account_id": books.id(),
+        }))
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
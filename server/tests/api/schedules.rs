@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use berry::models::{
+    account::Account,
+    schedule::{Schedule, UpcomingOccurrence},
+};
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+use crate::helpers::{
+    TestApp, create_account_in_app, create_account_with, create_category_in_app, spawn_app,
+};
+
+fn rent(source: &Account, destination: &Account, start_date: &str) -> serde_json::Value {
+    json!({
+        "title": "Rent",
+        "amount": "1500",
+        "source_account_id": source.id(),
+        "destination_account_id": destination.id(),
+        "frequency": "monthly",
+        "day": 5,
+        "start_date": start_date,
+    })
+}
+
+async fn create_schedule(app: &TestApp, body: &serde_json::Value) -> Schedule {
+    let response = app.post_schedule(body).await;
+    assert_eq!(StatusCode::CREATED, response.status());
+
+    response.json().await.unwrap()
+}
+
+async fn upcoming(app: &TestApp, until: &str) -> Vec<UpcomingOccurrence> {
+    let response = app.list_upcoming_occurrences(until).await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    response.json().await.unwrap()
+}
+
+/// Wait for the scheduler to bring the balance of `account` to `expected`
+async fn wait_for_balance(app: &TestApp, account: &Account, expected: Decimal) {
+    let mut balance = Decimal::ZERO;
+    for _ in 0..50 {
+        let account: Account = app
+            .get_account(account.id().to_string())
+            .await
+            .json()
+            .await
+            .unwrap();
+        balance = account.balance();
+        if balance == expected {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    panic!("expected a balance of {expected}, but it stayed at {balance}");
+}
+
+#[tokio::test]
+async fn due_occurrences_are_posted_once() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let landlord = create_account_in_app(&app).await;
+    let mut body = rent(&bank, &landlord, "2026-01-01");
+    body["end_date"] = json!("2026-03-31");
+    let schedule = create_schedule(&app, &body).await;
+
+    wait_for_balance(&app, &landlord, dec!(4500)).await;
+    // Let the scheduler run again
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+
+    let page: serde_json::Value = app
+        .list_transactions_with_query(&[("account_id", &landlord.id().to_string())])
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(3, page["total"]);
+    assert!(upcoming(&app, "2026-12-31").await.is_empty());
+
+    let response = app
+        .skip_occurrence(schedule.id().to_string(), "2026-02-05")
+        .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn upcoming_occurrences_include_skipped_ones() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let landlord = create_account_in_app(&app).await;
+    let schedule = create_schedule(&app, &rent(&bank, &landlord, "2100-01-01")).await;
+
+    let response = app
+        .skip_occurrence(schedule.id().to_string(), "2100-02-05")
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let occurrences = upcoming(&app, "2100-03-31").await;
+    let dates: Vec<_> = occurrences
+        .iter()
+        .map(|o| (o.date().to_string(), o.skipped()))
+        .collect();
+    assert_eq!(
+        vec![
+            ("2100-01-05".to_string(), false),
+            ("2100-02-05".to_string(), true),
+            ("2100-03-05".to_string(), false),
+        ],
+        dates
+    );
+}
+
+#[tokio::test]
+async fn days_without_an_occurrence_cannot_be_skipped() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let landlord = create_account_in_app(&app).await;
+    let schedule = create_schedule(&app, &rent(&bank, &landlord, "2100-01-01")).await;
+
+    let response = app
+        .skip_occurrence(schedule.id().to_string(), "2100-02-06")
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn editing_a_schedule_changes_its_upcoming_occurrences() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let employer = create_account_in_app(&app).await;
+    let schedule = create_schedule(&app, &rent(&employer, &bank, "2100-01-01")).await;
+
+    let response = app
+        .update_schedule(
+            schedule.id().to_string(),
+            &json!({
+                "title": "Salary",
+                "amount": "5000",
+                "source_account_id": employer.id(),
+                "destination_account_id": bank.id(),
+                "frequency": "last_business_day",
+                "start_date": "2100-01-01",
+            }),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let occurrences = upcoming(&app, "2100-01-31").await;
+    assert_eq!(1, occurrences.len());
+    // January 31st 2100 is a Sunday
+    assert_eq!("2100-01-29", occurrences[0].date().to_string());
+    assert_eq!(dec!(5000), occurrences[0].transfer().amount);
+}
+
+#[tokio::test]
+async fn invalid_schedules_are_rejected() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let landlord = create_account_in_app(&app).await;
+    let mut body = rent(&bank, &landlord, "2100-01-01");
+    body["day"] = json!(32);
+
+    let response = app.post_schedule(&body).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    let mut body = rent(&bank, &landlord, "2100-01-01");
+    body["interval"] = json!(u32::MAX);
+    let response = app.post_schedule(&body).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn schedules_between_accounts_in_different_currencies_are_rejected() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let dollars = create_account_with(&app, "name=assets:dollars&currency=USD").await;
+
+    let response = app
+        .post_schedule(&rent(&bank, &dollars, "2100-01-01"))
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn upcoming_occurrences_are_listed_up_to_ten_years_ahead() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let landlord = create_account_in_app(&app).await;
+    create_schedule(&app, &rent(&bank, &landlord, "2026-01-01")).await;
+
+    let response = app.list_upcoming_occurrences("9999-12-31").await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn renaming_a_category_renames_it_in_schedules() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let landlord = create_account_in_app(&app).await;
+    let housing = create_category_in_app(&app, "housing").await;
+    let mut body = rent(&bank, &landlord, "2100-01-01");
+    body["category"] = json!("housing");
+    let schedule = create_schedule(&app, &body).await;
+
+    let response = app
+        .update_category(housing.id().to_string(), "name=home".to_string())
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    let schedule: Schedule = app
+        .get_schedule(schedule.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        Some("home".to_string()),
+        schedule.transfer().category.as_ref().map(|c| c.to_string())
+    );
+    let response = app.delete_category(housing.id().to_string()).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn accounts_used_by_a_schedule_cannot_be_deleted() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let landlord = create_account_in_app(&app).await;
+    let schedule = create_schedule(&app, &rent(&bank, &landlord, "2100-01-01")).await;
+
+    let response = app.delete_account(landlord.id().to_string()).await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+
+    let response = app.get_schedule(schedule.id().to_string()).await;
+    assert_eq!(StatusCode::OK, response.status());
+}
+
+#[tokio::test]
+async fn deleted_schedules_are_not_found() {
+    let app = spawn_app().await;
+    let bank = create_account_in_app(&app).await;
+    let landlord = create_account_in_app(&app).await;
+    let schedule = create_schedule(&app, &rent(&bank, &landlord, "2100-01-01")).await;
+
+    let response = app.delete_schedule(schedule.id().to_string()).await;
+    assert_eq!(StatusCode::NO_CONTENT, response.status());
+
+    let response = app.get_schedule(schedule.id().to_string()).await;
+    assert_eq!(StatusCode::NOT_FOUND, response.status());
+}
@@ -0,0 +1,124 @@
+use berry::models::{
+    account::Account,
+    credit_card::{CardStatements, Statement, StatementStatus},
+};
+use reqwest::StatusCode;
+use rust_decimal_macros::dec;
+use serde_json::json;
+
+use crate::helpers::{TestApp, create_account_in_app, spawn_app, transfer};
+
+async fn create_card(app: &TestApp) -> Account {
+    let card: Account = app
+        .post_account("name=liabilities:card&type=liability".to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    let response = app
+        .set_credit_card(
+            card.id().to_string(),
+            &json!({ "closing_day": 3, "due_day": 10, "credit_limit": "1000" }),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+
+    card
+}
+
+#[tokio::test]
+async fn statements_sum_charges_and_payments_of_each_billing_cycle() {
+    let app = spawn_app().await;
+    let card = create_card(&app).await;
+    let store = create_account_in_app(&app).await;
+    let bank = create_account_in_app(&app).await;
+
+    transfer(&app, &card, &store, "100", "2026-01-20").await;
+    transfer(&app, &card, &store, "50", "2026-02-04").await;
+    transfer(&app, &bank, &card, "100", "2026-02-10").await;
+    transfer(&app, &card, &store, "25", "2026-03-03").await;
+
+    let response = app
+        .get_card_statements(
+            card.id().to_string(),
+            &[("from", "2026-02"), ("to", "2026-03")],
+        )
+        .await;
+
+    assert_eq!(StatusCode::OK, response.status());
+    let statements: CardStatements = response.json().await.unwrap();
+    assert_eq!(dec!(75), statements.balance());
+    assert_eq!(dec!(925), statements.available_credit());
+    let [february, march] = statements.statements() else {
+        panic!("expected two statements: {:?}", statements.statements());
+    };
+    assert_eq!(dec!(100), february.closing_balance());
+    assert_eq!(StatementStatus::Closed, march.status());
+    assert_eq!(dec!(100), march.opening_balance());
+    assert_eq!(dec!(75), march.charges());
+    assert_eq!(dec!(100), march.credits());
+    assert_eq!(dec!(75), march.closing_balance());
+    assert_eq!("2026-03-10", march.due_date().to_string());
+
+    let response = app
+        .get_card_statement(card.id().to_string(), "2026-03")
+        .await;
+    assert_eq!(StatusCode::OK, response.status());
+    let statement: Statement = response.json().await.unwrap();
+    assert_eq!(march, &statement);
+}
+
+#[tokio::test]
+async fn only_liability_accounts_can_be_credit_cards() {
+    let app = spawn_app().await;
+    let account = create_account_in_app(&app).await;
+
+    let response = app
+        .set_credit_card(
+            account.id().to_string(),
+            &json!({ "closing_day": 3, "due_day": 10, "credit_limit": "1000" }),
+        )
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn statements_of_accounts_without_card_details_are_rejected() {
+    let app = spawn_app().await;
+    let account = create_account_in_app(&app).await;
+
+    let response = app.get_card_statements(account.id().to_string(), &[]).await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn statement_ranges_are_capped() {
+    let app = spawn_app().await;
+    let card = create_card(&app).await;
+
+    let response = app
+        .get_card_statements(
+            card.id().to_string(),
+            &[("from", "1000-01"), ("to", "9000-12")],
+        )
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
+
+#[tokio::test]
+async fn invalid_closing_days_are_rejected() {
+    let app = spawn_app().await;
+    let card = create_card(&app).await;
+
+    let response = app
+        .set_credit_card(
+            card.id().to_string(),
+            &json!({ "closing_day": 32, "due_day": 10, "credit_limit": "1000" }),
+        )
+        .await;
+
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+}
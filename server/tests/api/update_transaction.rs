@@ -0,0 +1,185 @@
+use berry::models::{account::Account, transaction::Transaction};
+use chrono::{TimeZone as _, Utc};
+use reqwest::StatusCode;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helpers::{TestApp, create_account_in_app, create_category_in_app, spawn_app};
+
+async fn create_transfer(app: &TestApp, source: &Account, destination: &Account) -> Transaction {
+    create_category_in_app(app, "food").await;
+    let body = format!(
+        "title=Groceries&amount=42&source_account_id={}&destination_account_id={}&category=food",
+        source.id(),
+        destination.id()
+    );
+
+    app.post_transaction(body).await.json().await.unwrap()
+}
+
+async fn balance_of(app: &TestApp, account: &Account) -> Decimal {
+    let account: Account = app
+        .get_account(account.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    account.balance()
+}
+
+#[tokio::test]
+async fn update_title_category_and_date() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await;
+    let destination = create_account_in_app(&app).await;
+    let transaction = create_transfer(&app, &source, &destination).await;
+
+    let response = app
+        .update_transaction(
+            transaction.id().to_string(),
+            "title=Supermarket&category=&posting_date=2024-02-10T12%3A00%3A00Z".to_string(),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status().as_u16());
+
+    let updated: Transaction = app
+        .get_transaction(transaction.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!("Supermarket", updated.title().to_string());
+    assert_eq!(&None, updated.category());
+    assert_eq!(
+        Utc.with_ymd_and_hms(2024, 2, 10, 12, 0, 0).unwrap(),
+        updated.posting_date()
+    );
+    assert_eq!(transaction.legs(), updated.legs());
+    assert_eq!(dec!(42), balance_of(&app, &destination).await);
+}
+
+#[tokio::test]
+async fn updating_the_amount_rebalances_the_accounts() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await;
+    let destination = create_account_in_app(&app).await;
+    let transaction = create_transfer(&app, &source, &destination).await;
+
+    let response = app
+        .update_transaction(transaction.id().to_string(), "amount=50".to_string())
+        .await;
+    assert_eq!(StatusCode::OK, response.status().as_u16());
+
+    assert_eq!(dec!(-50), balance_of(&app, &source).await);
+    assert_eq!(dec!(50), balance_of(&app, &destination).await);
+}
+
+#[tokio::test]
+async fn updating_to_a_non_positive_amount_is_rejected() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await;
+    let destination = create_account_in_app(&app).await;
+    let transaction = create_transfer(&app, &source, &destination).await;
+
+    for amount in ["0", "-50"] {
+        let response = app
+            .update_transaction(transaction.id().to_string(), format!("amount={amount}"))
+            .await;
+        assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status());
+    }
+
+    assert_eq!(dec!(-42), balance_of(&app, &source).await);
+    assert_eq!(dec!(42), balance_of(&app, &destination).await);
+}
+
+#[tokio::test]
+async fn updating_an_account_moves_the_balance() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await;
+    let destination = create_account_in_app(&app).await;
+    let other = create_account_in_app(&app).await;
+    let transaction = create_transfer(&app, &source, &destination).await;
+
+    let response = app
+        .update_transaction(
+            transaction.id().to_string(),
+            format!("destination_account_id={}", other.id()),
+        )
+        .await;
+    assert_eq!(StatusCode::OK, response.status().as_u16());
+
+    assert_eq!(dec!(-42), balance_of(&app, &source).await);
+    assert_eq!(dec!(0), balance_of(&app, &destination).await);
+    assert_eq!(dec!(42), balance_of(&app, &other).await);
+}
+
+#[tokio::test]
+async fn legs_may_be_replaced() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await;
+    let destination = create_account_in_app(&app).await;
+    let other = create_account_in_app(&app).await;
+    let transaction = create_transfer(&app, &source, &destination).await;
+
+    let body = json!({
+        "legs": [
+            { "account_id": source.id(), "amount": "-60" },
+            { "account_id": destination.id(), "amount": "20" },
+            { "account_id": other.id(), "amount": "40" },
+        ],
+    });
+    let response = app
+        .update_transaction_json(transaction.id().to_string(), &body)
+        .await;
+    assert_eq!(StatusCode::OK, response.status().as_u16());
+
+    let updated: Transaction = response.json().await.unwrap();
+    assert_eq!(3, updated.legs().len());
+    assert_eq!(dec!(-60), balance_of(&app, &source).await);
+    assert_eq!(dec!(20), balance_of(&app, &destination).await);
+    assert_eq!(dec!(40), balance_of(&app, &other).await);
+}
+
+#[tokio::test]
+async fn unbalanced_updates_leave_the_transaction_untouched() {
+    let app = spawn_app().await;
+    let source = create_account_in_app(&app).await;
+    let destination = create_account_in_app(&app).await;
+    let transaction = create_transfer(&app, &source, &destination).await;
+
+    let body = json!({
+        "title": "Changed",
+        "legs": [
+            { "account_id": source.id(), "amount": "-60" },
+            { "account_id": destination.id(), "amount": "20" },
+        ],
+    });
+    let response = app
+        .update_transaction_json(transaction.id().to_string(), &body)
+        .await;
+    assert_eq!(StatusCode::UNPROCESSABLE_ENTITY, response.status().as_u16());
+
+    let stored: Transaction = app
+        .get_transaction(transaction.id().to_string())
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(transaction, stored);
+    assert_eq!(dec!(-42), balance_of(&app, &source).await);
+}
+
+#[tokio::test]
+async fn updating_an_unexisting_transaction_returns_not_found() {
+    let app = spawn_app().await;
+
+    let response = app
+        .update_transaction(Uuid::new_v4().to_string(), "title=Test".to_string())
+        .await;
+
+    assert_eq!(StatusCode::NOT_FOUND, response.status().as_u16());
+}